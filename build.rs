@@ -0,0 +1,59 @@
+//! Embeds the Node helper scripts (extract.js, interact.js, browser_server.js
+//! and their siblings) into the binary so it runs from any directory.
+//!
+//! Scripts are read from `SCREEN_DETECTION_SCRIPTS_SRC` or, by default, the
+//! `node/dom-extraction` checkout two levels above this crate. When neither
+//! exists the build warns and the binary is built without embedded scripts,
+//! finding them at runtime instead (see `browser::scripts`).
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-env-changed=SCREEN_DETECTION_SCRIPTS_SRC");
+
+    let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR is set by cargo"));
+    let source = env::var_os("SCREEN_DETECTION_SCRIPTS_SRC")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| manifest_dir.join("../../node/dom-extraction"));
+
+    // Watched even while missing, so checking the scripts out later rebuilds
+    println!("cargo:rerun-if-changed={}", source.display());
+
+    let mut scripts = Vec::new();
+    if let Ok(entries) = fs::read_dir(&source) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let name = entry.file_name().to_string_lossy().to_string();
+            let wanted = name == "package.json" || [".js", ".mjs", ".cjs"].iter().any(|ext| name.ends_with(ext));
+            if wanted && path.is_file() {
+                let path = path.canonicalize().unwrap_or(path);
+                println!("cargo:rerun-if-changed={}", path.display());
+                scripts.push((name, path));
+            }
+        }
+    }
+    scripts.sort();
+    if scripts.is_empty() {
+        println!(
+            "cargo:warning=no Node scripts found in {}; building without embedded scripts \
+             (set SCREEN_DETECTION_SCRIPTS_SRC to embed them, or ship them next to the binary)",
+            source.display()
+        );
+    }
+
+    let mut code = String::from("pub static EMBEDDED_SCRIPTS: &[(&str, &str)] = &[\n");
+    for (name, path) in &scripts {
+        code.push_str(&format!("    ({:?}, include_str!({:?})),\n", name, path.display().to_string()));
+    }
+    code.push_str("];\n");
+
+    let out_dir = PathBuf::from(env::var("OUT_DIR").expect("OUT_DIR is set by cargo"));
+    let target = out_dir.join("embedded_scripts.rs");
+    // Rewriting identical code would recompile the crate on every rerun
+    if fs::read_to_string(&target).ok().as_deref() != Some(code.as_str()) {
+        fs::write(target, code).expect("write embedded_scripts.rs");
+    }
+}
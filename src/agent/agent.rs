@@ -8,12 +8,13 @@ use crate::{
             MIN_CONFIDENCE, ModelDecision, Policy,
         },
         budget::{BudgetDecision, check_budgets},
+        data_generator::upload_files,
         ai_model::{DeterministicPolicy, HybridPolicy, ModelPolicy, MockBackend, OllamaBackend},
         error::AgentError,
     },
     browser::{
+        backend::BrowserBackend,
         playwright::{BrowserCommand, SelectorHint, execute_browser_action},
-        session::BrowserSession,
     },
     canonical::diff::{SemanticSignal, SemanticStateDiff},
     screen::screen_model::ElementKind,
@@ -199,6 +200,8 @@ fn selector_from_target(target: &IdentifiedElement, form_id: Option<&str>) -> Se
         tag: target.element.tag.clone(),
         input_type: target.element.input_type.clone(),
         form_id: form_id.map(|s| s.to_string()),
+        frame_path: target.element.frame_path.clone(),
+        shadow_path: Vec::new(),
     }
 }
 
@@ -220,6 +223,7 @@ pub fn execute_action(action: &AgentAction, state: &ScreenState) -> Result<(), A
                 .ok_or_else(|| AgentError::ElementNotFound {
                     element: input_label.clone(),
                     context: format!("form '{}'", form_id),
+                    candidates: Vec::new(),
                 })?;
 
             println!(
@@ -247,6 +251,7 @@ pub fn execute_action(action: &AgentAction, state: &ScreenState) -> Result<(), A
                 .ok_or_else(|| AgentError::ElementNotFound {
                     element: action_label.clone(),
                     context: format!("form '{}'", form_id),
+                    candidates: Vec::new(),
                 })?;
 
             println!(
@@ -298,6 +303,7 @@ pub fn execute_action(action: &AgentAction, state: &ScreenState) -> Result<(), A
                     .ok_or_else(|| AgentError::ElementNotFound {
                         element: label.clone(),
                         context: format!("form '{}'", form_id),
+                        candidates: Vec::new(),
                     })?;
 
                 println!(
@@ -324,6 +330,7 @@ pub fn execute_action(action: &AgentAction, state: &ScreenState) -> Result<(), A
                 .ok_or_else(|| AgentError::ElementNotFound {
                     element: label.clone(),
                     context: "standalone actions".into(),
+                    candidates: Vec::new(),
                 })?;
 
             println!("Clicking standalone action [{}] '{}'", target.id, label);
@@ -338,6 +345,16 @@ pub fn execute_action(action: &AgentAction, state: &ScreenState) -> Result<(), A
             execute_browser_action(&command)
         }
 
+        AgentAction::Hover { .. }
+        | AgentAction::PressKey { .. }
+        | AgentAction::TypeText { .. }
+        | AgentAction::Scroll { .. }
+        | AgentAction::DragAndDrop { .. }
+        | AgentAction::UploadFiles { .. } => Err(AgentError::BrowserAction(format!(
+            "{:?} needs a browser session; interact.js only supports fill, click and wait",
+            action
+        ))),
+
         AgentAction::Wait { reason } => {
             println!("Waiting: {}", reason);
             thread::sleep(Duration::from_secs(2));
@@ -362,13 +379,13 @@ pub fn execute_action(action: &AgentAction, state: &ScreenState) -> Result<(), A
     }
 }
 
-/// Execute an agent action using a persistent BrowserSession.
+/// Execute an agent action using a persistent browser (any `BrowserBackend`).
 /// Reuses the same helper functions as execute_action() but routes
 /// browser commands through the session instead of spawning subprocesses.
 pub fn execute_action_session(
     action: &AgentAction,
     state: &ScreenState,
-    session: &mut BrowserSession,
+    session: &mut dyn BrowserBackend,
 ) -> Result<(), AgentError> {
     match action {
         AgentAction::FillInput {
@@ -382,6 +399,7 @@ pub fn execute_action_session(
                 .ok_or_else(|| AgentError::ElementNotFound {
                     element: input_label.clone(),
                     context: format!("form '{}'", form_id),
+                    candidates: Vec::new(),
                 })?;
 
             println!(
@@ -389,8 +407,7 @@ pub fn execute_action_session(
                 target.id, input_label, value
             );
 
-            let selector = selector_from_target(target, Some(form_id));
-            session.fill(&selector, value)
+            fill_in_session(session, target, form_id, value)
         }
 
         AgentAction::SubmitForm {
@@ -403,6 +420,7 @@ pub fn execute_action_session(
                 .ok_or_else(|| AgentError::ElementNotFound {
                     element: action_label.clone(),
                     context: format!("form '{}'", form_id),
+                    candidates: Vec::new(),
                 })?;
 
             println!(
@@ -429,8 +447,7 @@ pub fn execute_action_session(
                         target.id, input_label, value
                     );
 
-                    let selector = selector_from_target(target, Some(form_id));
-                    session.fill(&selector, value)?;
+                    fill_in_session(session, target, form_id, value)?;
                 } else {
                     println!("Warning: input '{}' not found in form '{}', skipping", input_label, form_id);
                 }
@@ -442,6 +459,7 @@ pub fn execute_action_session(
                     .ok_or_else(|| AgentError::ElementNotFound {
                         element: label.clone(),
                         context: format!("form '{}'", form_id),
+                        candidates: Vec::new(),
                     })?;
 
                 println!(
@@ -462,6 +480,7 @@ pub fn execute_action_session(
                 .ok_or_else(|| AgentError::ElementNotFound {
                     element: label.clone(),
                     context: "standalone actions".into(),
+                    candidates: Vec::new(),
                 })?;
 
             println!("Clicking standalone action [{}] '{}'", target.id, label);
@@ -470,6 +489,86 @@ pub fn execute_action_session(
             session.click(&selector)
         }
 
+        AgentAction::Hover { label, identity } => {
+            println!("Hovering over '{}'", label);
+            let selector = resolve_target(identity, state)
+                .map(target_selector)
+                .unwrap_or_else(|| label_selector(label, state));
+            session.hover(&selector)
+        }
+
+        AgentAction::PressKey { key, label } => {
+            println!("Pressing '{}'{}", key, label.as_ref().map(|l| format!(" on '{}'", l)).unwrap_or_default());
+            let selector = label.as_ref().map(|l| label_selector(l, state));
+            session.press(selector.as_ref(), key)
+        }
+
+        AgentAction::TypeText {
+            form_id,
+            input_label,
+            text,
+            delay_ms,
+            identity,
+        } => {
+            let target = resolve_target(identity, state)
+                .or_else(|| find_input_by_label(form_id, input_label, state))
+                .ok_or_else(|| AgentError::ElementNotFound {
+                    element: input_label.clone(),
+                    context: format!("form '{}'", form_id),
+                    candidates: Vec::new(),
+                })?;
+
+            println!("Typing into input [{}] '{}': '{}'", target.id, input_label, text);
+
+            let selector = selector_from_target(target, Some(form_id));
+            session.type_text(&selector, text, *delay_ms)
+        }
+
+        AgentAction::Scroll {
+            label,
+            delta_x,
+            delta_y,
+        } => match label {
+            Some(label) => {
+                println!("Scrolling '{}' into view", label);
+                session.scroll_into_view(&label_selector(label, state))
+            }
+            None => {
+                println!("Scrolling by ({}, {})", delta_x, delta_y);
+                session.scroll_by(*delta_x, *delta_y)
+            }
+        },
+
+        AgentAction::DragAndDrop {
+            source_label,
+            target_label,
+        } => {
+            println!("Dragging '{}' onto '{}'", source_label, target_label);
+            let source = label_selector(source_label, state);
+            let target = label_selector(target_label, state);
+            session.drag_to(&source, &target)
+        }
+
+        AgentAction::UploadFiles {
+            form_id,
+            input_label,
+            files,
+            identity,
+        } => {
+            let target = resolve_target(identity, state)
+                .or_else(|| find_input_by_label(form_id, input_label, state))
+                .ok_or_else(|| AgentError::ElementNotFound {
+                    element: input_label.clone(),
+                    context: format!("form '{}'", form_id),
+                    candidates: Vec::new(),
+                })?;
+
+            println!("Uploading {} file(s) to input [{}] '{}'", files.len(), target.id, input_label);
+
+            let selector = selector_from_target(target, Some(form_id));
+            session.set_input_files(&selector, files)
+        }
+
         AgentAction::Wait { reason } => {
             println!("Waiting: {}", reason);
             session.wait_idle(2000)
@@ -490,6 +589,51 @@ pub fn execute_action_session(
     }
 }
 
+/// Fill an input through the session; file inputs get their value
+/// (comma-separated paths) uploaded instead.
+fn fill_in_session(
+    session: &mut dyn BrowserBackend,
+    target: &IdentifiedElement,
+    form_id: &str,
+    value: &str,
+) -> Result<(), AgentError> {
+    let selector = selector_from_target(target, Some(form_id));
+    if target.element.input_type.as_deref() == Some("file") {
+        session.set_input_files(&selector, &upload_files(value)?)
+    } else {
+        session.fill(&selector, value)
+    }
+}
+
+/// SelectorHint for an identified element, scoped to its form if it has one.
+fn target_selector(target: &IdentifiedElement) -> SelectorHint {
+    selector_from_target(target, target.scope.strip_prefix("form:"))
+}
+
+/// SelectorHint for any element labelled `label`.
+///
+/// Hover triggers, drop zones and scroll targets are often not classified
+/// inputs or actions, so an unknown label falls back to matching by
+/// accessible name alone and the browser decides whether it exists.
+fn label_selector(label: &str, state: &ScreenState) -> SelectorHint {
+    match state
+        .identities
+        .values()
+        .find(|el| el.element.label.as_deref() == Some(label))
+    {
+        Some(target) => target_selector(target),
+        None => SelectorHint {
+            role: None,
+            name: Some(label.to_string()),
+            tag: None,
+            input_type: None,
+            form_id: None,
+            frame_path: Vec::new(),
+            shadow_path: Vec::new(),
+        },
+    }
+}
+
 fn resolve_target<'a>(
     identity: &Option<String>,
     state: &'a ScreenState,
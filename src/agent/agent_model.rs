@@ -44,6 +44,36 @@ pub enum AgentAction {
         label: String,
         identity: Option<String>,
     },
+    Hover {
+        label: String,
+        identity: Option<String>,
+    },
+    PressKey {
+        key: String,                       // Playwright key or chord, e.g. "Enter", "Control+A"
+        label: Option<String>,             // element to focus first; None = focused element
+    },
+    TypeText {
+        form_id: String,
+        input_label: String,
+        text: String,
+        delay_ms: u64,                     // pause between keystrokes
+        identity: Option<String>,
+    },
+    Scroll {
+        label: Option<String>,             // scroll this element into view; None = scroll by delta
+        delta_x: i64,
+        delta_y: i64,
+    },
+    DragAndDrop {
+        source_label: String,
+        target_label: String,
+    },
+    UploadFiles {
+        form_id: String,
+        input_label: String,
+        files: Vec<String>,                // local paths
+        identity: Option<String>,
+    },
     Wait {
         reason: String,
     },
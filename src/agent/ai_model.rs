@@ -2,9 +2,13 @@ use crate::{
     agent::agent_model::{
         AgentAction, AgentMemory, DecisionType, MIN_CONFIDENCE, ModelDecision, Policy,
     },
+    agent::data_generator::placeholder_upload_path,
     canonical::diff::SemanticSignal,
     canonical::diff::SemanticStateDiff,
-    screen::screen_model::Form,
+    screen::{
+        overlay::dismiss_action,
+        screen_model::{Form, Overlay},
+    },
     state::state_model::ScreenState,
 };
 use serde::{Deserialize, Serialize};
@@ -45,6 +49,11 @@ pub struct DeterministicPolicy;
 /// Pattern ordering: specific before general (e.g., "card number" before "number",
 /// "first name" before "name", "confirm password" before "password").
 pub fn guess_value(label: &str, input_type: Option<&str>) -> String {
+    // Uploads take a file path, whatever the label says
+    if input_type == Some("file") {
+        return placeholder_upload_path();
+    }
+
     let l = label.to_lowercase();
 
     // Label-based heuristics (checked in order — specific before general)
@@ -293,6 +302,32 @@ impl Policy for DeterministicPolicy {
                 }),
             ),
 
+            SemanticSignal::ModalOpened => match close_modal(screen) {
+                Some(action) => (DecisionType::Act, Some(action)),
+                None => (
+                    DecisionType::Wait,
+                    Some(AgentAction::Wait {
+                        reason: "Modal opened without a dismiss button".to_string(),
+                    }),
+                ),
+            },
+
+            // Whatever opened the dialog already happened; refilling the
+            // form would only open it again
+            SemanticSignal::ModalClosed => (
+                DecisionType::Wait,
+                Some(AgentAction::Wait {
+                    reason: "Modal closed".to_string(),
+                }),
+            ),
+
+            SemanticSignal::ToastAppeared { text } => (
+                DecisionType::Wait,
+                Some(AgentAction::Wait {
+                    reason: format!("Toast appeared: {}", text),
+                }),
+            ),
+
             SemanticSignal::NoOp => return None,
         };
 
@@ -304,6 +339,24 @@ impl Policy for DeterministicPolicy {
     }
 }
 
+/// Click the open dialog's dismiss button (see `dismiss_action`), if it has one.
+fn close_modal(screen: &ScreenState) -> Option<AgentAction> {
+    let mut candidates: Vec<_> = screen
+        .identities
+        .values()
+        .filter(|i| i.element.overlay == Some(Overlay::Modal))
+        .collect();
+    candidates.sort_by(|a, b| a.id.cmp(&b.id));
+    let button = dismiss_action(candidates.iter().map(|i| &i.element))?;
+    Some(AgentAction::ClickAction {
+        label: button.label.clone().unwrap_or_default(),
+        identity: candidates
+            .iter()
+            .find(|i| std::ptr::eq(&i.element, button))
+            .map(|i| i.id.clone()),
+    })
+}
+
 pub struct HybridPolicy {
     pub deterministic: DeterministicPolicy,
     pub model: ModelPolicy,
@@ -387,6 +440,8 @@ struct ModelActionResponse {
     #[serde(default)]
     label: Option<String>,
     #[serde(default)]
+    key: Option<String>,
+    #[serde(default)]
     reason: Option<String>,
     #[serde(default)]
     confidence: Option<f32>,
@@ -456,7 +511,10 @@ AVAILABLE ACTIONS (respond with exactly one as JSON):
 1. FillInput: {{"action":"FillInput","form_id":"...","input_label":"...","value":"...","confidence":0.9}}
 2. SubmitForm: {{"action":"SubmitForm","form_id":"...","action_label":"...","confidence":0.9}}
 3. ClickAction: {{"action":"ClickAction","label":"...","confidence":0.9}}
-4. Wait: {{"action":"Wait","reason":"...","confidence":0.9}}
+4. TypeText (autocomplete fields): {{"action":"TypeText","form_id":"...","input_label":"...","value":"...","confidence":0.9}}
+5. Hover: {{"action":"Hover","label":"...","confidence":0.9}}
+6. PressKey: {{"action":"PressKey","key":"Enter","label":"...","confidence":0.9}}
+7. Wait: {{"action":"Wait","reason":"...","confidence":0.9}}
 
 Respond with ONLY valid JSON, no explanation."#,
             screen.url.as_deref().unwrap_or("unknown"),
@@ -488,6 +546,21 @@ Respond with ONLY valid JSON, no explanation."#,
                 label: parsed.label?,
                 identity: None,
             },
+            "TypeText" => AgentAction::TypeText {
+                form_id: parsed.form_id?,
+                input_label: parsed.input_label?,
+                text: parsed.value.unwrap_or_default(),
+                delay_ms: 50,
+                identity: None,
+            },
+            "Hover" => AgentAction::Hover {
+                label: parsed.label?,
+                identity: None,
+            },
+            "PressKey" => AgentAction::PressKey {
+                key: parsed.key?,
+                label: parsed.label,
+            },
             "Wait" => AgentAction::Wait {
                 reason: parsed.reason.unwrap_or_else(|| "Waiting".to_string()),
             },
@@ -1,5 +1,6 @@
 use crate::agent::ai_model::guess_value;
 use crate::agent::app_context::AppContext;
+use crate::agent::error::AgentError;
 use crate::agent::page_model::{FieldAnalysis, FieldModel};
 
 // ============================================================================
@@ -81,3 +82,39 @@ impl<'a> DataGenerator<'a> {
             .collect()
     }
 }
+
+// ============================================================================
+// File uploads
+// ============================================================================
+
+/// File name of the placeholder used for upload fields without a configured file.
+const PLACEHOLDER_UPLOAD: &str = "screen-detection-upload.txt";
+
+/// Path of the placeholder upload file in the system temp directory.
+///
+/// `guess_value()` returns this for file inputs; `upload_files()` creates the
+/// file on first use.
+pub fn placeholder_upload_path() -> String {
+    std::env::temp_dir()
+        .join(PLACEHOLDER_UPLOAD)
+        .to_string_lossy()
+        .to_string()
+}
+
+/// Split a file field's value (comma-separated local paths) into the list
+/// passed to `set_input_files`, creating the placeholder file if it is named.
+pub fn upload_files(value: &str) -> Result<Vec<String>, AgentError> {
+    let files: Vec<String> = value
+        .split(',')
+        .map(str::trim)
+        .filter(|f| !f.is_empty())
+        .map(String::from)
+        .collect();
+    let placeholder = placeholder_upload_path();
+    if files.contains(&placeholder) && !std::path::Path::new(&placeholder).exists() {
+        std::fs::write(&placeholder, "screen-detection test upload\n").map_err(|e| {
+            AgentError::BrowserAction(format!("Cannot create placeholder upload '{}': {}", placeholder, e))
+        })?;
+    }
+    Ok(files)
+}
@@ -1,6 +1,8 @@
 use std::fmt;
 use std::process::ExitStatus;
 
+use crate::browser::candidates::ElementCandidate;
+
 #[derive(Debug)]
 pub enum AgentError {
     /// Node.js subprocess failed to spawn (extract.js or interact.js)
@@ -21,8 +23,15 @@ pub enum AgentError {
     /// DOM extraction returned unexpected structure
     DomStructure(String),
 
-    /// Element not found in screen state
-    ElementNotFound { element: String, context: String },
+    /// Element not found in screen state or on the page, with the closest
+    /// matches found instead (best first; empty when none came close)
+    ElementNotFound { element: String, context: String, candidates: Vec<ElementCandidate> },
+
+    /// The element is on the page but disabled, so it cannot be acted on
+    ElementDisabled { element: String, context: String },
+
+    /// Several elements match a selector that could not be scoped to its form
+    AmbiguousElement { element: String, context: String, matches: usize },
 
     /// Missing required data in screen state
     MissingState(String),
@@ -32,6 +41,24 @@ pub enum AgentError {
 
     /// Session protocol error (unexpected response from browser_server.js)
     SessionProtocol { command: String, error: String },
+
+    /// browser_server.js did not answer a command within its timeout
+    Timeout { command: String, timeout_ms: u64 },
+
+    /// Page could not be loaded for static extraction (file read or HTTP fetch)
+    PageLoad { url: String, error: String },
+
+    /// A Node helper script was not found in any of the searched locations
+    ScriptNotFound { script: String, searched: Vec<String> },
+
+    /// browser_server.js speaks another protocol version
+    ProtocolMismatch { expected: u32, found: u32 },
+
+    /// browser_server.js does not implement a command
+    UnsupportedCommand { command: String, error: String },
+
+    /// A pipelined request was cancelled before its response arrived
+    Cancelled { command: String },
 }
 
 impl fmt::Display for AgentError {
@@ -55,8 +82,19 @@ impl fmt::Display for AgentError {
             AgentError::DomStructure(msg) => {
                 write!(f, "Unexpected DOM structure: {}", msg)
             }
-            AgentError::ElementNotFound { element, context } => {
-                write!(f, "Element '{}' not found: {}", element, context)
+            AgentError::ElementNotFound { element, context, candidates } => {
+                write!(f, "Element '{}' not found: {}", element, context)?;
+                if !candidates.is_empty() {
+                    let closest: Vec<String> = candidates.iter().map(ElementCandidate::summary).collect();
+                    write!(f, " (closest: {})", closest.join(", "))?;
+                }
+                Ok(())
+            }
+            AgentError::ElementDisabled { element, context } => {
+                write!(f, "Element '{}' is disabled: {}", element, context)
+            }
+            AgentError::AmbiguousElement { element, context, matches } => {
+                write!(f, "Element '{}' is ambiguous: {} elements match on {}", element, matches, context)
             }
             AgentError::MissingState(msg) => {
                 write!(f, "Missing state: {}", msg)
@@ -67,6 +105,35 @@ impl fmt::Display for AgentError {
             AgentError::SessionProtocol { command, error } => {
                 write!(f, "Session protocol error on '{}': {}", command, error)
             }
+            AgentError::Timeout { command, timeout_ms } => {
+                write!(f, "Timed out after {}ms waiting for '{}'", timeout_ms, command)
+            }
+            AgentError::PageLoad { url, error } => {
+                write!(f, "Failed to load '{}': {}", url, error)
+            }
+            AgentError::ScriptNotFound { script, searched } => {
+                write!(
+                    f,
+                    "Cannot find {} (searched: {}); set session.scripts_dir in screen-detection.yaml \
+                     or SCREEN_DETECTION_SCRIPTS_DIR to the directory containing the Node scripts",
+                    script,
+                    searched.join(", ")
+                )
+            }
+            AgentError::ProtocolMismatch { expected, found } => {
+                write!(
+                    f,
+                    "browser_server.js speaks protocol {} but this build needs protocol {}; \
+                     use the Node scripts from the same release (see `screen-detection doctor`)",
+                    found, expected
+                )
+            }
+            AgentError::UnsupportedCommand { command, error } => {
+                write!(f, "browser_server.js does not support '{}': {}", command, error)
+            }
+            AgentError::Cancelled { command } => {
+                write!(f, "'{}' was cancelled before browser_server.js answered", command)
+            }
         }
     }
 }
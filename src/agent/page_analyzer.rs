@@ -63,7 +63,8 @@ pub fn classify_field_type(input_type: Option<&str>, label: Option<&str>, tag: O
             "search" => return FieldType::Search,
             "time" => return FieldType::Time,
             "hidden" => return FieldType::Hidden,
-            "color" | "range" | "file" | "month" | "week" => return FieldType::Other,
+            "file" => return FieldType::File,
+            "color" | "range" | "month" | "week" => return FieldType::Other,
             _ => {}
         }
     }
@@ -288,6 +289,7 @@ impl PageAnalyzer for MockPageAnalyzer {
                     fields,
                     submit_label,
                     expected_outcome: ExpectedOutcome::default(),
+                    frame_path: form.frame_path.clone(),
                 }
             })
             .collect();
@@ -21,6 +21,8 @@ pub enum FieldType {
     Search,
     Hidden,
     Time,
+    /// `<input type=file>`; the value is a comma-separated list of local paths
+    File,
     Other,
 }
 
@@ -52,6 +54,7 @@ impl FieldModel {
             FieldType::Search => Some("search"),
             FieldType::Time => Some("time"),
             FieldType::Hidden => Some("hidden"),
+            FieldType::File => Some("file"),
             FieldType::Text | FieldType::Textarea | FieldType::Select | FieldType::Other => None,
         }
     }
@@ -88,6 +91,9 @@ pub struct FormModel {
     /// What to expect after submitting this form.
     #[serde(default)]
     pub expected_outcome: ExpectedOutcome,
+    /// Frame the form lives in (see `Form::frame_path`); empty for the main frame.
+    #[serde(default)]
+    pub frame_path: Vec<String>,
 }
 
 /// Semantic classification of an output element's meaning.
@@ -0,0 +1,280 @@
+use serde_json::Value;
+
+use crate::agent::error::AgentError;
+use crate::browser::candidates::nearest_candidates;
+use crate::browser::console::ConsoleEntry;
+use crate::browser::dialogs::{DialogEntry, DialogPolicy};
+use crate::browser::network::NetworkEntry;
+use crate::browser::options::SessionOptions;
+use crate::browser::playwright::{SelectorHint, is_virtual_form_id};
+use crate::browser::recording::RecordedEvent;
+use crate::browser::routes::RouteMock;
+use crate::browser::tabs::{TabInfo, TabSelector};
+
+// ============================================================================
+// BrowserBackend trait — abstraction over a live (or fake) browser
+// ============================================================================
+
+/// Operations the runner, explorer and agent need from a browser.
+///
+/// `BrowserSession` implements this against `browser_server.js`; `MockBrowser`
+/// implements it in memory from scripted `extract` payloads so that specs,
+/// exploration and login can be exercised without Node or Chromium.
+pub trait BrowserBackend {
+    /// Apply browser context overrides on top of the launch options.
+    /// Empty overrides restore the launch options.
+    fn configure(&mut self, overrides: &SessionOptions) -> Result<(), AgentError>;
+
+    /// Navigate to a URL.
+    fn navigate(&mut self, url: &str) -> Result<(), AgentError>;
+
+    /// Extract the current page as `{url, title, dom, structural_outline}` JSON.
+    fn extract(&mut self) -> Result<Value, AgentError>;
+
+    /// Fill an input element.
+    fn fill(&mut self, selector: &SelectorHint, value: &str) -> Result<(), AgentError>;
+
+    /// Click an element.
+    fn click(&mut self, selector: &SelectorHint) -> Result<(), AgentError>;
+
+    /// Select an option in a dropdown element.
+    fn select_option(&mut self, selector: &SelectorHint, value: &str) -> Result<(), AgentError>;
+
+    /// Check a checkbox element.
+    fn check(&mut self, selector: &SelectorHint) -> Result<(), AgentError>;
+
+    /// Uncheck a checkbox element.
+    fn uncheck(&mut self, selector: &SelectorHint) -> Result<(), AgentError>;
+
+    /// Move the mouse over an element.
+    fn hover(&mut self, selector: &SelectorHint) -> Result<(), AgentError>;
+
+    /// Press a key or chord ("Enter", "Control+A") on an element, or on the
+    /// focused element when `selector` is `None`.
+    fn press(&mut self, selector: Option<&SelectorHint>, key: &str) -> Result<(), AgentError>;
+
+    /// Type text key by key with `delay_ms` between keystrokes.
+    fn type_text(&mut self, selector: &SelectorHint, text: &str, delay_ms: u64) -> Result<(), AgentError>;
+
+    /// Scroll an element into the viewport.
+    fn scroll_into_view(&mut self, selector: &SelectorHint) -> Result<(), AgentError>;
+
+    /// Scroll the page by a pixel offset.
+    fn scroll_by(&mut self, delta_x: i64, delta_y: i64) -> Result<(), AgentError>;
+
+    /// Drag `source` and drop it onto `target`.
+    fn drag_to(&mut self, source: &SelectorHint, target: &SelectorHint) -> Result<(), AgentError>;
+
+    /// Set the files of a file input from local paths.
+    fn set_input_files(&mut self, selector: &SelectorHint, files: &[String]) -> Result<(), AgentError>;
+
+    /// Wait for the page to settle.
+    fn wait_idle(&mut self, ms: u64) -> Result<(), AgentError>;
+
+    /// Answer requests matching `mock` with its canned response.
+    fn route(&mut self, mock: &RouteMock) -> Result<(), AgentError>;
+
+    /// Remove the routes for the URL glob `url`, or every route when `None`.
+    fn unroute(&mut self, url: Option<&str>) -> Result<(), AgentError>;
+
+    /// Take a screenshot.
+    fn screenshot(&mut self, path: &str) -> Result<(), AgentError>;
+
+    /// Start a Playwright trace (screenshots, DOM snapshots, network).
+    fn start_trace(&mut self) -> Result<(), AgentError>;
+
+    /// Stop the trace and save it as a zip at `path`, or discard it when `None`.
+    fn stop_trace(&mut self, path: Option<&str>) -> Result<(), AgentError>;
+
+    /// Start recording a video of the active page.
+    fn start_video(&mut self) -> Result<(), AgentError>;
+
+    /// Stop the video and save it at `path`, or discard it when `None`.
+    fn stop_video(&mut self, path: Option<&str>) -> Result<(), AgentError>;
+
+    /// Get the current URL from the browser.
+    fn current_url(&mut self) -> Result<String, AgentError>;
+
+    /// Query the text content of an element by CSS selector.
+    /// Returns None if the element is not found.
+    fn query_text(&mut self, selector: &str) -> Result<Option<String>, AgentError>;
+
+    /// Query whether an element is visible by CSS selector.
+    fn query_visible(&mut self, selector: &str) -> Result<bool, AgentError>;
+
+    /// Query the count of elements matching a CSS selector.
+    fn query_count(&mut self, selector: &str) -> Result<u32, AgentError>;
+
+    /// Return and clear the HTTP requests recorded since the last call.
+    fn take_network_log(&mut self) -> Result<Vec<NetworkEntry>, AgentError>;
+
+    /// Return and clear the console messages and uncaught page errors
+    /// recorded since the last call.
+    fn take_console_log(&mut self) -> Result<Vec<ConsoleEntry>, AgentError>;
+
+    /// Answer JavaScript dialogs opened from now on according to `policy`.
+    fn set_dialog_policy(&mut self, policy: &DialogPolicy) -> Result<(), AgentError>;
+
+    /// Return and clear the dialogs opened since the last call.
+    fn take_dialogs(&mut self) -> Result<Vec<DialogEntry>, AgentError>;
+
+    /// List the open tabs in opening order.
+    fn tabs(&mut self) -> Result<Vec<TabInfo>, AgentError>;
+
+    /// Make the matching tab (newest when `tab` is empty) the active one.
+    fn switch_tab(&mut self, tab: &TabSelector) -> Result<TabInfo, AgentError>;
+
+    /// Close the matching tab (the active one when `tab` is empty).
+    fn close_tab(&mut self, tab: &TabSelector) -> Result<(), AgentError>;
+
+    /// Wait for a newly opened tab (popup, `target=_blank` link) and switch to it.
+    fn wait_for_popup(&mut self, timeout_ms: u64) -> Result<TabInfo, AgentError>;
+
+    /// Start reporting the user's own clicks, fills, selects and navigations.
+    fn start_recording(&mut self) -> Result<(), AgentError>;
+
+    /// Return and clear the interactions recorded since the last call.
+    fn take_recorded_events(&mut self) -> Result<Vec<RecordedEvent>, AgentError>;
+
+    /// Save cookies and localStorage to a JSON file.
+    fn save_storage_state(&mut self, path: &str) -> Result<(), AgentError>;
+
+    /// Load cookies and localStorage from a JSON file.
+    fn load_storage_state(&mut self, path: &str) -> Result<(), AgentError>;
+}
+
+// ============================================================================
+// Shared matching helpers for in-process backends
+// ============================================================================
+
+/// Candidate accessible names for a raw DOM element, most specific first.
+///
+/// Button-like inputs (`submit`, `button`, `reset`) are named by their
+/// `value`; the `title` attribute is the last resort, as in the browser.
+pub(crate) fn element_labels(el: &Value) -> Vec<String> {
+    let button_value = el["tag"].as_str() == Some("input")
+        && matches!(el["type"].as_str(), Some("submit" | "button" | "reset"));
+    ["associated_label_text", "ariaLabel", "text", "value", "placeholder", "name", "title_attr"]
+        .iter()
+        .filter(|k| **k != "value" || button_value)
+        .filter_map(|k| el[*k].as_str())
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+/// Whether a raw DOM element is something Playwright's role locators can target.
+pub(crate) fn is_targetable(el: &Value) -> bool {
+    matches!(
+        el["tag"].as_str(),
+        Some("input" | "select" | "textarea" | "button" | "a")
+    ) || matches!(
+        el["role"].as_str(),
+        Some(
+            "button" | "link" | "checkbox" | "radio" | "textbox" | "searchbox" | "combobox"
+                | "spinbutton" | "slider" | "switch" | "tab" | "menuitem" | "option"
+        )
+    )
+}
+
+/// A string-array field of a raw DOM element (missing = empty).
+pub(crate) fn string_list<'a>(el: &'a Value, key: &str) -> Vec<&'a str> {
+    el[key]
+        .as_array()
+        .map(|a| a.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default()
+}
+
+/// Whether a raw DOM element satisfies a `SelectorHint`.
+///
+/// Mirrors Playwright's default accessible-name matching (case-insensitive
+/// substring) over interactive elements only. The element must live in the
+/// hint's frame (the main frame when `frame_path` is empty) and, like
+/// Playwright's locators, open shadow roots are pierced: a non-empty
+/// `shadow_path` only narrows the search to elements under those hosts. The
+/// form scope must match exactly when given; role and tag are not checked
+/// because callers use them as hints, not constraints. Disabled elements
+/// never match.
+pub(crate) fn hint_matches(el: &Value, selector: &SelectorHint) -> bool {
+    !is_disabled(el) && hint_locates(el, selector)
+}
+
+fn is_disabled(el: &Value) -> bool {
+    el["disabled"].as_bool() == Some(true)
+}
+
+/// `hint_matches` without the disabled check: whether the selector points
+/// at this element at all.
+fn hint_locates(el: &Value, selector: &SelectorHint) -> bool {
+    if !is_targetable(el) {
+        return false;
+    }
+    // Virtual forms only gather elements outside any <form>
+    if selector.form_id.as_deref().is_some_and(is_virtual_form_id) && el["formId"].is_string() {
+        return false;
+    }
+    if string_list(el, "frame_path") != selector.frame_path {
+        return false;
+    }
+    let shadow_path = string_list(el, "shadow_path");
+    if shadow_path.len() < selector.shadow_path.len()
+        || shadow_path.iter().zip(&selector.shadow_path).any(|(a, b)| a != b)
+    {
+        return false;
+    }
+    if let Some(form_id) = selector.form_scope()
+        && el["formId"].as_str() != Some(form_id)
+    {
+        return false;
+    }
+    match &selector.name {
+        Some(name) => {
+            let wanted = name.trim().to_lowercase();
+            element_labels(el)
+                .iter()
+                .any(|l| l.to_lowercase().contains(&wanted))
+        }
+        None => true,
+    }
+}
+
+/// The element of `dom` an action on `selector` applies to.
+///
+/// A hint whose form cannot be scoped to (see `SelectorHint::has_unscoped_form`)
+/// is looked up across its frame, so it must match a single element, as
+/// Playwright's strict locators require; otherwise the first match wins.
+pub(crate) fn resolve_hint<'a>(
+    dom: &'a [Value],
+    selector: &SelectorHint,
+    context: impl FnOnce() -> String,
+) -> Result<&'a Value, AgentError> {
+    let mut matches = dom.iter().filter(|el| hint_matches(el, selector));
+    let Some(first) = matches.next() else {
+        return Err(unmatched_element_error(dom, selector, context()));
+    };
+    if selector.has_unscoped_form() {
+        let count = 1 + matches.count();
+        if count > 1 {
+            return Err(AgentError::AmbiguousElement {
+                element: selector.name.clone().unwrap_or_else(|| "<unnamed>".into()),
+                context: context(),
+                matches: count,
+            });
+        }
+    }
+    Ok(first)
+}
+
+/// The error for a selector that no usable element in `dom` matches.
+///
+/// When everything the selector points at is disabled the element is
+/// there, so `ElementDisabled` is reported; otherwise `ElementNotFound`
+/// with the closest candidates.
+fn unmatched_element_error(dom: &[Value], selector: &SelectorHint, context: String) -> AgentError {
+    let element = selector.name.clone().unwrap_or_else(|| "<unnamed>".into());
+    let mut located = dom.iter().filter(|el| hint_locates(el, selector)).peekable();
+    if located.peek().is_some() && located.all(is_disabled) {
+        return AgentError::ElementDisabled { element, context };
+    }
+    AgentError::ElementNotFound { element, context, candidates: nearest_candidates(dom, selector) }
+}
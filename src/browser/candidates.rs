@@ -0,0 +1,141 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::browser::backend::{element_labels, is_targetable, string_list};
+use crate::browser::playwright::SelectorHint;
+
+// ============================================================================
+// Nearest-match suggestions for selectors that matched nothing
+// ============================================================================
+
+/// Most candidates reported for one unresolved selector
+pub const MAX_CANDIDATES: usize = 3;
+
+/// Candidates scoring below this are not worth suggesting
+const MIN_SCORE: f64 = 0.5;
+
+/// An element on the page that resembles a selector which matched nothing.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ElementCandidate {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub role: Option<String>,
+
+    /// The element's accessible name closest to the wanted one
+    pub name: String,
+
+    pub tag: String,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub form_id: Option<String>,
+
+    /// Name similarity in `0.0..=1.0` (1.0: same words, ignoring case and punctuation)
+    pub score: f64,
+}
+
+impl ElementCandidate {
+    /// Compact form for error messages: `button 'Sign In' <button> in form 'login' (0.92)`.
+    pub fn summary(&self) -> String {
+        let form = self
+            .form_id
+            .as_ref()
+            .map(|f| format!(" in form '{}'", f))
+            .unwrap_or_default();
+        format!(
+            "{} '{}' <{}>{} ({:.2})",
+            self.role.as_deref().unwrap_or("element"),
+            self.name,
+            self.tag,
+            form,
+            self.score
+        )
+    }
+}
+
+/// The interactive elements of `dom` whose accessible names come closest to
+/// `selector.name`, best first (at most `MAX_CANDIDATES`).
+///
+/// Only the selector's frame is searched. Elements in another form than the
+/// one asked for, or disabled ones, are still suggested since they are the
+/// usual reason an otherwise right name did not match; their score is
+/// lowered slightly so an exact hit in the right place ranks first.
+pub fn nearest_candidates(dom: &[Value], selector: &SelectorHint) -> Vec<ElementCandidate> {
+    let Some(wanted) = selector.name.as_deref().map(normalize).filter(|w| !w.is_empty()) else {
+        return Vec::new();
+    };
+
+    let mut candidates: Vec<ElementCandidate> = dom
+        .iter()
+        .filter(|el| is_targetable(el) && string_list(el, "frame_path") == selector.frame_path)
+        .filter_map(|el| {
+            let (name, mut score) = element_labels(el)
+                .into_iter()
+                .map(|label| {
+                    let score = similarity(&wanted, &normalize(&label));
+                    (label, score)
+                })
+                .max_by(|a, b| a.1.total_cmp(&b.1))?;
+            let form_id = el["formId"].as_str().map(String::from);
+            if let Some(scope) = selector.form_scope()
+                && form_id.as_deref() != Some(scope)
+            {
+                score *= 0.9;
+            }
+            if el["disabled"].as_bool() == Some(true) {
+                score *= 0.9;
+            }
+            Some(ElementCandidate {
+                role: el["role"].as_str().map(String::from),
+                name,
+                tag: el["tag"].as_str().unwrap_or("").to_string(),
+                form_id,
+                score,
+            })
+        })
+        .filter(|c| c.score >= MIN_SCORE)
+        .collect();
+
+    candidates.sort_by(|a, b| b.score.total_cmp(&a.score));
+    candidates.truncate(MAX_CANDIDATES);
+    candidates
+}
+
+/// Lowercase and reduce punctuation runs to single spaces ("Sign-in" -> "sign in").
+fn normalize(s: &str) -> String {
+    s.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Similarity of two normalized names: edit-distance based, but a name
+/// containing the other scores at least by how much of it the shorter covers.
+fn similarity(a: &str, b: &str) -> f64 {
+    let (len_a, len_b) = (a.chars().count(), b.chars().count());
+    let longest = len_a.max(len_b);
+    if longest == 0 {
+        return 0.0;
+    }
+    let edit = 1.0 - levenshtein(a, b) as f64 / longest as f64;
+    let containment = if a.contains(b) || b.contains(a) {
+        len_a.min(len_b) as f64 / longest as f64
+    } else {
+        0.0
+    };
+    edit.max(containment)
+}
+
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(ca != *cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    row[b.len()]
+}
@@ -0,0 +1,84 @@
+use serde::{Deserialize, Serialize};
+
+// ============================================================================
+// Console capture — console output and uncaught errors from the page
+// ============================================================================
+
+/// Severity of a captured console message, named after Playwright's
+/// `ConsoleMessage.type()`. Uncaught exceptions (`pageerror` events) are
+/// reported as `PageError`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ConsoleLevel {
+    Debug,
+    Log,
+    Info,
+    Warning,
+    Error,
+    PageError,
+    /// Any other console method (`table`, `trace`, `assert`, ...)
+    #[serde(other)]
+    Other,
+}
+
+/// One console message or uncaught exception buffered by `browser_server.js`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ConsoleEntry {
+    pub level: ConsoleLevel,
+
+    pub text: String,
+
+    /// Source location as `url:line:column`, when the browser reports one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+
+    /// JavaScript stack trace (uncaught exceptions only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stack: Option<String>,
+
+    /// Index of the spec step during which the message was logged; `None`
+    /// for messages logged while loading `start_url`. Set by the runner.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub step: Option<usize>,
+}
+
+impl ConsoleEntry {
+    /// Whether this is an uncaught exception.
+    pub fn is_page_error(&self) -> bool {
+        self.level == ConsoleLevel::PageError
+    }
+
+    /// Whether this is an uncaught exception or a `console.error` call.
+    pub fn is_error(&self) -> bool {
+        matches!(self.level, ConsoleLevel::Error | ConsoleLevel::PageError)
+    }
+
+    /// Compact one-line form for reports: `[step 2] error: boom (app.js:10:5)`.
+    pub fn summary(&self) -> String {
+        let step = match self.step {
+            Some(step) => format!("[step {}] ", step),
+            None => "[start] ".to_string(),
+        };
+        let location = self
+            .location
+            .as_ref()
+            .map(|l| format!(" ({})", l))
+            .unwrap_or_default();
+        format!("{}{}: {}{}", step, self.level.as_str(), self.text, location)
+    }
+}
+
+impl ConsoleLevel {
+    /// Label used in reports.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ConsoleLevel::Debug => "debug",
+            ConsoleLevel::Log => "log",
+            ConsoleLevel::Info => "info",
+            ConsoleLevel::Warning => "warning",
+            ConsoleLevel::Error => "error",
+            ConsoleLevel::PageError => "uncaught exception",
+            ConsoleLevel::Other => "console",
+        }
+    }
+}
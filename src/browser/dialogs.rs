@@ -0,0 +1,99 @@
+use serde::{Deserialize, Serialize};
+
+// ============================================================================
+// Dialogs — alert / confirm / prompt / beforeunload handling
+// ============================================================================
+
+/// How `browser_server.js` answers JavaScript dialogs.
+///
+/// Dialogs are dismissed unless told otherwise (Playwright's own default),
+/// so a `confirm()` guarding a destructive action is cancelled rather than
+/// stalling the page. `alert` dialogs can only be acknowledged, so every
+/// policy closes them the same way.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "policy", rename_all = "snake_case")]
+pub enum DialogPolicy {
+    /// Press OK (a `prompt` keeps its default value)
+    Accept,
+
+    /// Press Cancel
+    #[default]
+    Dismiss,
+
+    /// Press OK, answering a `prompt` with `text`
+    Respond { text: String },
+}
+
+impl DialogPolicy {
+    /// The policy an `ExpectDialog` step asks for.
+    pub fn from_expectation(accept: bool, prompt_text: Option<&str>) -> Self {
+        match (accept, prompt_text) {
+            (false, _) => DialogPolicy::Dismiss,
+            (true, Some(text)) => DialogPolicy::Respond { text: text.to_string() },
+            (true, None) => DialogPolicy::Accept,
+        }
+    }
+
+    /// Whether OK is pressed.
+    pub fn accepts(&self) -> bool {
+        !matches!(self, DialogPolicy::Dismiss)
+    }
+}
+
+/// Which JavaScript API opened a dialog, named after Playwright's `Dialog.type()`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum DialogKind {
+    Alert,
+    Confirm,
+    Prompt,
+    BeforeUnload,
+}
+
+impl DialogKind {
+    /// Label used in reports.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DialogKind::Alert => "alert",
+            DialogKind::Confirm => "confirm",
+            DialogKind::Prompt => "prompt",
+            DialogKind::BeforeUnload => "beforeunload",
+        }
+    }
+}
+
+/// One dialog the page opened, and how it was answered.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DialogEntry {
+    pub kind: DialogKind,
+
+    pub message: String,
+
+    /// Pre-filled value of a `prompt`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_value: Option<String>,
+
+    /// Whether OK was pressed
+    pub accepted: bool,
+
+    /// Text entered into a `prompt` that was accepted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompt_text: Option<String>,
+
+    /// URL of the page that opened the dialog
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+impl DialogEntry {
+    /// Whether the message contains `fragment` (case-insensitive).
+    pub fn mentions(&self, fragment: &str) -> bool {
+        self.message.to_lowercase().contains(&fragment.to_lowercase())
+    }
+
+    /// Compact one-line form for reports: `confirm "Delete item?" (accepted)`.
+    pub fn summary(&self) -> String {
+        let outcome = if self.accepted { "accepted" } else { "dismissed" };
+        format!("{} \"{}\" ({})", self.kind.as_str(), self.message, outcome)
+    }
+}
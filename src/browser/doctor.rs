@@ -0,0 +1,174 @@
+use std::path::Path;
+use std::process::Output;
+
+use crate::agent::error::AgentError;
+use crate::browser::scripts::{self, EXTRACT_SCRIPT, INTERACT_SCRIPT, SERVER_SCRIPT};
+use crate::browser::session::SessionConfig;
+
+// ============================================================================
+// Doctor — verify the Node.js / Playwright / Chromium toolchain
+// ============================================================================
+
+/// Outcome of one environment check.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DoctorCheck {
+    pub name: String,
+    pub ok: bool,
+    /// What was found (version, path) or what went wrong
+    pub detail: String,
+    /// What to do about a failed check
+    pub fix: Option<String>,
+}
+
+impl DoctorCheck {
+    fn pass(name: &str, detail: impl Into<String>) -> Self {
+        DoctorCheck { name: name.to_string(), ok: true, detail: detail.into(), fix: None }
+    }
+
+    fn fail(name: &str, detail: impl Into<String>, fix: impl Into<String>) -> Self {
+        DoctorCheck { name: name.to_string(), ok: false, detail: detail.into(), fix: Some(fix.into()) }
+    }
+}
+
+/// Oldest Node.js major version Playwright supports
+const MIN_NODE_MAJOR: u32 = 18;
+
+/// Check, in order, that Node.js runs, the helper scripts can be found, and
+/// Playwright and its Chromium build can be loaded from the scripts'
+/// directory. Checks that depend on a failed one are reported as failed
+/// without being run.
+pub fn run_checks(config: &SessionConfig) -> Vec<DoctorCheck> {
+    let mut checks = vec![check_node(&config.node_path)];
+    let node_ok = checks[0].ok;
+
+    checks.push(check_script(SERVER_SCRIPT, &config.server_script, config.scripts_dir.as_deref()));
+    let server_dir = scripts::resolve_script(&config.server_script, config.scripts_dir.as_deref())
+        .ok()
+        .filter(|p| p.is_file())
+        .and_then(|p| p.parent().map(Path::to_path_buf));
+    for script in [EXTRACT_SCRIPT, INTERACT_SCRIPT] {
+        checks.push(check_script(script, script, config.scripts_dir.as_deref()));
+    }
+
+    let Some(dir) = server_dir.filter(|_| node_ok) else {
+        let reason = "skipped: needs Node.js and the server script";
+        checks.push(DoctorCheck::fail("Playwright", reason, "fix the checks above first"));
+        checks.push(DoctorCheck::fail("Chromium", reason, "fix the checks above first"));
+        return checks;
+    };
+    let playwright = check_playwright(&config.node_path, &dir);
+    let playwright_ok = playwright.ok;
+    checks.push(playwright);
+    if playwright_ok {
+        checks.push(check_chromium(&config.node_path, &dir));
+    } else {
+        checks.push(DoctorCheck::fail("Chromium", "skipped: needs Playwright", "fix the Playwright check first"));
+    }
+    checks
+}
+
+fn check_node(node_path: &str) -> DoctorCheck {
+    let name = "Node.js";
+    let output = match scripts::node_command(node_path).arg("--version").output() {
+        Ok(output) if output.status.success() => output,
+        Ok(output) => {
+            return DoctorCheck::fail(
+                name,
+                format!("'{} --version' failed: {}", node_path, stderr_of(&output)),
+                "install Node.js 18 or newer, or point session.node_path / SCREEN_DETECTION_NODE at it",
+            );
+        }
+        Err(e) => {
+            return DoctorCheck::fail(
+                name,
+                format!("cannot run '{}': {}", node_path, e),
+                "install Node.js 18 or newer, or point session.node_path / SCREEN_DETECTION_NODE at it",
+            );
+        }
+    };
+    let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    let major = version.trim_start_matches('v').split('.').next().and_then(|m| m.parse::<u32>().ok());
+    match major {
+        Some(major) if major < MIN_NODE_MAJOR => DoctorCheck::fail(
+            name,
+            format!("{} is too old", version),
+            format!("upgrade to Node.js {} or newer", MIN_NODE_MAJOR),
+        ),
+        _ => DoctorCheck::pass(name, version),
+    }
+}
+
+fn check_script(name: &str, script: &str, scripts_dir: Option<&str>) -> DoctorCheck {
+    match scripts::resolve_script(script, scripts_dir) {
+        Ok(path) if path.is_file() => DoctorCheck::pass(name, path.display().to_string()),
+        Ok(path) => DoctorCheck::fail(
+            name,
+            format!("{} does not exist", path.display()),
+            "fix session.server_script in screen-detection.yaml",
+        ),
+        Err(AgentError::ScriptNotFound { searched, .. }) => DoctorCheck::fail(
+            name,
+            format!("not found (searched: {})", searched.join(", ")),
+            "set session.scripts_dir or SCREEN_DETECTION_SCRIPTS_DIR to the node/dom-extraction directory",
+        ),
+        Err(e) => DoctorCheck::fail(name, e.to_string(), "check that the cache directory is writable"),
+    }
+}
+
+fn check_playwright(node_path: &str, dir: &Path) -> DoctorCheck {
+    let name = "Playwright";
+    match run_node(node_path, dir, "console.log(require.resolve('playwright'))") {
+        Ok(path) => DoctorCheck::pass(name, path),
+        Err(error) => DoctorCheck::fail(
+            name,
+            error,
+            format!(
+                "run `npm install playwright` in {} (or install it globally with `npm install -g playwright`)",
+                dir.display()
+            ),
+        ),
+    }
+}
+
+fn check_chromium(node_path: &str, dir: &Path) -> DoctorCheck {
+    let name = "Chromium";
+    let script = "const p = require('playwright').chromium.executablePath(); \
+                  if (!require('fs').existsSync(p)) { console.error('missing ' + p); process.exit(1); } \
+                  console.log(p)";
+    match run_node(node_path, dir, script) {
+        Ok(path) => DoctorCheck::pass(name, path),
+        Err(error) => DoctorCheck::fail(
+            name,
+            error,
+            format!("run `npx playwright install chromium` in {}", dir.display()),
+        ),
+    }
+}
+
+/// Run `node -e script` in `dir` (so `require` resolves from there).
+fn run_node(node_path: &str, dir: &Path, script: &str) -> Result<String, String> {
+    let output = scripts::node_command(node_path)
+        .arg("-e")
+        .arg(script)
+        .current_dir(dir)
+        .output()
+        .map_err(|e| e.to_string())?;
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    } else {
+        Err(stderr_of(&output))
+    }
+}
+
+/// The error line of Node's stderr (skipping the stack), else its last line.
+fn stderr_of(output: &Output) -> String {
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    stderr
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty() && !l.starts_with("at "))
+        .find(|l| l.contains("Error") || l.starts_with("missing"))
+        .or_else(|| stderr.lines().map(str::trim).rfind(|l| !l.is_empty()))
+        .unwrap_or("no output")
+        .to_string()
+}
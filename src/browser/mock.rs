@@ -0,0 +1,656 @@
+use std::collections::{HashMap, VecDeque};
+
+use serde_json::Value;
+
+use crate::agent::error::AgentError;
+use crate::browser::backend::{BrowserBackend, element_labels, resolve_hint};
+use crate::browser::console::ConsoleEntry;
+use crate::browser::dialogs::{DialogEntry, DialogKind, DialogPolicy};
+use crate::browser::network::NetworkEntry;
+use crate::browser::options::SessionOptions;
+use crate::browser::playwright::SelectorHint;
+use crate::browser::recording::RecordedEvent;
+use crate::browser::routes::RouteMock;
+use crate::browser::tabs::{TabInfo, TabSelector};
+
+// ============================================================================
+// MockBrowser — in-memory BrowserBackend serving scripted page states
+// ============================================================================
+
+/// An interaction recorded by `MockBrowser`, in the order it was performed.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MockAction {
+    Configure(SessionOptions),
+    Navigate(String),
+    Fill { name: String, value: String },
+    Click(String),
+    Select { name: String, value: String },
+    Check(String),
+    Uncheck(String),
+    Hover(String),
+    /// Key press on a named element, or on the focused element when `None`
+    Press { name: Option<String>, key: String },
+    Type { name: String, text: String },
+    ScrollIntoView(String),
+    ScrollBy(i64, i64),
+    Drag { source: String, target: String },
+    SetInputFiles { name: String, files: Vec<String> },
+    Wait(u64),
+    Screenshot(String),
+    StartTrace,
+    /// Trace stopped and saved at the path, or discarded when `None`
+    StopTrace(Option<String>),
+    StartVideo,
+    /// Video stopped and saved at the path, or discarded when `None`
+    StopVideo(Option<String>),
+    SaveStorageState(String),
+    LoadStorageState(String),
+    SwitchTab(usize),
+    CloseTab(usize),
+    Route(String),
+    Unroute(Option<String>),
+    StartRecording,
+    SetDialogPolicy(DialogPolicy),
+}
+
+/// Deterministic, in-memory `BrowserBackend` for tests (no Node or Chromium).
+///
+/// Pages are canned `extract` payloads (`{url, title, dom, structural_outline}`)
+/// keyed by URL. Clicking an element whose label has a registered click target
+/// "navigates" to that page; a registered popup target opens it in a new tab
+/// instead. Fills, selects, checks and the other element interactions are
+/// validated against the current page's `dom` and recorded in `actions()`.
+/// Scripted requests that match an installed route report the route's
+/// status in the network log, as if the mock had answered them. Scripted
+/// recording batches stand in for a user driving the page by hand. A
+/// scripted dialog opens when its element is clicked and is answered with
+/// the current dialog policy; dismissing a `confirm` cancels the click's
+/// navigation, like a guarded delete button.
+#[derive(Debug, Clone, Default)]
+pub struct MockBrowser {
+    pages: HashMap<String, Value>,
+    click_targets: HashMap<String, String>,
+    popup_targets: HashMap<String, String>,
+    redirects: HashMap<String, String>,
+    requests: HashMap<String, Vec<NetworkEntry>>,
+    network_log: Vec<NetworkEntry>,
+    console: HashMap<String, Vec<ConsoleEntry>>,
+    console_log: Vec<ConsoleEntry>,
+    /// Dialog opened by clicking the element with the (lowercased) label
+    dialogs: HashMap<String, (DialogKind, String)>,
+    dialog_log: Vec<DialogEntry>,
+    dialog_policy: DialogPolicy,
+    routes: Vec<RouteMock>,
+    /// Batches returned by successive `take_recorded_events` calls once recording
+    recorded: VecDeque<Vec<RecordedEvent>>,
+    recording: bool,
+    tracing: bool,
+    video: bool,
+    current_url: Option<String>,
+    /// URL of every open tab; empty until a tab command or popup needs it.
+    /// The active tab's entry is refreshed from `current_url` before use.
+    tabs: Vec<Option<String>>,
+    active_tab: usize,
+    /// Tabs opened by popups and not yet switched to, oldest first
+    popups: Vec<usize>,
+    actions: Vec<MockAction>,
+}
+
+impl MockBrowser {
+    /// Create an empty mock browser with no scripted pages.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register the `extract` payload served for `url`.
+    pub fn with_page(mut self, url: &str, payload: Value) -> Self {
+        self.pages.insert(url.to_string(), payload);
+        self
+    }
+
+    /// Clicking an element labelled `label` (case-insensitive) moves to `url`.
+    pub fn with_click_target(mut self, label: &str, url: &str) -> Self {
+        self.click_targets
+            .insert(label.trim().to_lowercase(), url.to_string());
+        self
+    }
+
+    /// Clicking an element labelled `label` opens `url` in a new tab, like a
+    /// `target=_blank` link or `window.open`. The active tab does not change.
+    pub fn with_popup_target(mut self, label: &str, url: &str) -> Self {
+        self.popup_targets
+            .insert(label.trim().to_lowercase(), url.to_string());
+        self
+    }
+
+    /// Navigating to `from` lands on `to` (e.g. a login page that forwards
+    /// an already-authenticated user to the dashboard).
+    pub fn with_redirect(mut self, from: &str, to: &str) -> Self {
+        self.redirects.insert(from.to_string(), to.to_string());
+        self
+    }
+
+    /// Requests "made" each time `url` is loaded (by navigate or click target).
+    pub fn with_requests(mut self, url: &str, requests: Vec<NetworkEntry>) -> Self {
+        self.requests.insert(url.to_string(), requests);
+        self
+    }
+
+    /// Console messages "logged" each time `url` is loaded (by navigate or click target).
+    pub fn with_console(mut self, url: &str, messages: Vec<ConsoleEntry>) -> Self {
+        self.console.insert(url.to_string(), messages);
+        self
+    }
+
+    /// Clicking an element labelled `label` (case-insensitive) opens a
+    /// `kind` dialog showing `message` before any click target is followed.
+    pub fn with_dialog(mut self, label: &str, kind: DialogKind, message: &str) -> Self {
+        self.dialogs
+            .insert(label.trim().to_lowercase(), (kind, message.to_string()));
+        self
+    }
+
+    /// Queue a batch of user interactions for one `take_recorded_events`
+    /// call. When the batch is taken, clicks on labels with a click target
+    /// and navigations move the mock to that page, as the user's would.
+    pub fn with_recorded_events(mut self, events: Vec<RecordedEvent>) -> Self {
+        self.recorded.push_back(events);
+        self
+    }
+
+    /// All interactions performed so far.
+    pub fn actions(&self) -> &[MockAction] {
+        &self.actions
+    }
+
+    /// Get the last known URL (no scripted lookup).
+    pub fn last_url(&self) -> Option<&str> {
+        self.current_url.as_deref()
+    }
+
+    /// Move to `url` and record the requests and console messages scripted for it.
+    fn land_on(&mut self, url: &str) {
+        self.record_load(url);
+        self.current_url = Some(url.to_string());
+    }
+
+    fn record_load(&mut self, url: &str) {
+        if let Some(requests) = self.requests.get(url) {
+            for request in requests {
+                let mut entry = request.clone();
+                // The newest matching route wins, as in Playwright
+                if let Some(route) = self.routes.iter().rev().find(|r| r.matches(&entry.url, &entry.method)) {
+                    entry.status = Some(route.status);
+                    entry.failure = None;
+                    entry.mime_type = route.resolve().ok().map(|r| r.content_type);
+                }
+                self.network_log.push(entry);
+            }
+        }
+        if let Some(messages) = self.console.get(url) {
+            self.console_log.extend(messages.iter().cloned());
+        }
+    }
+
+    /// Open the dialog scripted for `key`, if any, and answer it with the
+    /// current policy. Returns false when a cancelled dialog stops the click.
+    fn open_dialog(&mut self, key: &str) -> bool {
+        let Some((kind, message)) = self.dialogs.get(key).cloned() else {
+            return true;
+        };
+        let accepted = self.dialog_policy.accepts();
+        let prompt_text = match (&self.dialog_policy, kind) {
+            (DialogPolicy::Respond { text }, DialogKind::Prompt) => Some(text.clone()),
+            _ => None,
+        };
+        self.dialog_log.push(DialogEntry {
+            kind,
+            message,
+            default_value: None,
+            accepted,
+            prompt_text,
+            url: self.current_url.clone(),
+        });
+        accepted || kind == DialogKind::Alert
+    }
+
+    /// Bring `tabs` up to date with the active tab's URL.
+    fn sync_tabs(&mut self) {
+        if self.tabs.is_empty() {
+            self.tabs.push(self.current_url.clone());
+            self.active_tab = 0;
+        } else {
+            self.tabs[self.active_tab] = self.current_url.clone();
+        }
+    }
+
+    fn open_popup(&mut self, url: &str) {
+        self.sync_tabs();
+        self.record_load(url);
+        self.tabs.push(Some(url.to_string()));
+        self.popups.push(self.tabs.len() - 1);
+    }
+
+    fn tab_infos(&mut self) -> Vec<TabInfo> {
+        self.sync_tabs();
+        self.tabs
+            .iter()
+            .enumerate()
+            .map(|(index, url)| {
+                let url = url.clone().unwrap_or_default();
+                let title = self
+                    .pages
+                    .get(&url)
+                    .and_then(|p| p["title"].as_str())
+                    .unwrap_or("")
+                    .to_string();
+                TabInfo {
+                    index,
+                    url,
+                    title,
+                    active: index == self.active_tab,
+                }
+            })
+            .collect()
+    }
+
+    fn find_tab(&mut self, tab: &TabSelector, command: &str) -> Result<TabInfo, AgentError> {
+        let tabs = self.tab_infos();
+        tab.pick(&tabs).cloned().ok_or_else(|| AgentError::SessionProtocol {
+            command: command.into(),
+            error: format!("No {} is open", tab.describe()),
+        })
+    }
+
+    fn activate(&mut self, index: usize) -> TabInfo {
+        self.sync_tabs();
+        self.active_tab = index;
+        self.current_url = self.tabs[index].clone();
+        self.popups.retain(|&p| p != index);
+        self.actions.push(MockAction::SwitchTab(index));
+        self.tab_infos().remove(index)
+    }
+
+    fn current_page(&self, command: &str) -> Result<&Value, AgentError> {
+        self.current_url
+            .as_ref()
+            .and_then(|url| self.pages.get(url))
+            .ok_or_else(|| AgentError::SessionProtocol {
+                command: command.into(),
+                error: "No page loaded in MockBrowser".into(),
+            })
+    }
+
+    fn current_dom(&self, command: &str) -> Result<Vec<Value>, AgentError> {
+        let page = self.current_page(command)?;
+        Ok(page["dom"].as_array().cloned().unwrap_or_default())
+    }
+
+    /// Resolve a `SelectorHint` against the current page, returning the matched label.
+    fn resolve(&self, selector: &SelectorHint, command: &str) -> Result<String, AgentError> {
+        let dom = self.current_dom(command)?;
+        let el = resolve_hint(&dom, selector, || {
+            format!("MockBrowser page '{}'", self.current_url.as_deref().unwrap_or(""))
+        })?;
+        Ok(selector
+            .name
+            .clone()
+            .or_else(|| element_labels(el).into_iter().next())
+            .unwrap_or_default())
+    }
+
+    fn query(&self, selector: &str, command: &str) -> Result<Vec<Value>, AgentError> {
+        let dom = self.current_dom(command)?;
+        let mut matched = Vec::new();
+        for part in selector.split(',') {
+            let (tag, id) = parse_simple_css(part.trim()).ok_or_else(|| {
+                AgentError::SessionProtocol {
+                    command: command.into(),
+                    error: format!(
+                        "MockBrowser only supports 'tag', '#id' and 'tag#id' selectors, got '{}'",
+                        selector
+                    ),
+                }
+            })?;
+            for el in &dom {
+                let tag_ok = tag.as_ref().is_none_or(|t| el["tag"].as_str() == Some(t.as_str()));
+                let id_ok = id.as_ref().is_none_or(|i| el["id"].as_str() == Some(i.as_str()));
+                if tag_ok && id_ok {
+                    matched.push(el.clone());
+                }
+            }
+        }
+        Ok(matched)
+    }
+}
+
+impl BrowserBackend for MockBrowser {
+    fn configure(&mut self, overrides: &SessionOptions) -> Result<(), AgentError> {
+        self.actions.push(MockAction::Configure(overrides.clone()));
+        Ok(())
+    }
+
+    fn navigate(&mut self, url: &str) -> Result<(), AgentError> {
+        if !self.pages.contains_key(url) {
+            return Err(AgentError::SessionProtocol {
+                command: "navigate".into(),
+                error: format!("No scripted page for '{}'", url),
+            });
+        }
+        self.actions.push(MockAction::Navigate(url.to_string()));
+        let landed = self.redirects.get(url).cloned().unwrap_or_else(|| url.to_string());
+        self.land_on(&landed);
+        Ok(())
+    }
+
+    fn extract(&mut self) -> Result<Value, AgentError> {
+        let mut payload = self.current_page("extract")?.clone();
+        if payload.get("url").is_none() {
+            payload["url"] = Value::String(self.current_url.clone().unwrap_or_default());
+        }
+        Ok(payload)
+    }
+
+    fn fill(&mut self, selector: &SelectorHint, value: &str) -> Result<(), AgentError> {
+        let name = self.resolve(selector, "fill")?;
+        self.actions.push(MockAction::Fill {
+            name,
+            value: value.to_string(),
+        });
+        Ok(())
+    }
+
+    fn click(&mut self, selector: &SelectorHint) -> Result<(), AgentError> {
+        let name = self.resolve(selector, "click")?;
+        self.actions.push(MockAction::Click(name.clone()));
+        let key = name.trim().to_lowercase();
+        if !self.open_dialog(&key) {
+            return Ok(());
+        }
+        if let Some(target) = self.popup_targets.get(&key).cloned() {
+            self.open_popup(&target);
+        } else if let Some(target) = self.click_targets.get(&key).cloned() {
+            self.land_on(&target);
+        }
+        Ok(())
+    }
+
+    fn select_option(&mut self, selector: &SelectorHint, value: &str) -> Result<(), AgentError> {
+        let name = self.resolve(selector, "select_option")?;
+        self.actions.push(MockAction::Select {
+            name,
+            value: value.to_string(),
+        });
+        Ok(())
+    }
+
+    fn check(&mut self, selector: &SelectorHint) -> Result<(), AgentError> {
+        let name = self.resolve(selector, "check")?;
+        self.actions.push(MockAction::Check(name));
+        Ok(())
+    }
+
+    fn uncheck(&mut self, selector: &SelectorHint) -> Result<(), AgentError> {
+        let name = self.resolve(selector, "uncheck")?;
+        self.actions.push(MockAction::Uncheck(name));
+        Ok(())
+    }
+
+    fn hover(&mut self, selector: &SelectorHint) -> Result<(), AgentError> {
+        let name = self.resolve(selector, "hover")?;
+        self.actions.push(MockAction::Hover(name));
+        Ok(())
+    }
+
+    fn press(&mut self, selector: Option<&SelectorHint>, key: &str) -> Result<(), AgentError> {
+        let name = selector.map(|s| self.resolve(s, "press")).transpose()?;
+        self.actions.push(MockAction::Press {
+            name,
+            key: key.to_string(),
+        });
+        Ok(())
+    }
+
+    fn type_text(&mut self, selector: &SelectorHint, text: &str, _delay_ms: u64) -> Result<(), AgentError> {
+        let name = self.resolve(selector, "type")?;
+        self.actions.push(MockAction::Type {
+            name,
+            text: text.to_string(),
+        });
+        Ok(())
+    }
+
+    fn scroll_into_view(&mut self, selector: &SelectorHint) -> Result<(), AgentError> {
+        let name = self.resolve(selector, "scroll_into_view")?;
+        self.actions.push(MockAction::ScrollIntoView(name));
+        Ok(())
+    }
+
+    fn scroll_by(&mut self, delta_x: i64, delta_y: i64) -> Result<(), AgentError> {
+        self.actions.push(MockAction::ScrollBy(delta_x, delta_y));
+        Ok(())
+    }
+
+    fn drag_to(&mut self, source: &SelectorHint, target: &SelectorHint) -> Result<(), AgentError> {
+        let source = self.resolve(source, "drag")?;
+        let target = self.resolve(target, "drag")?;
+        self.actions.push(MockAction::Drag { source, target });
+        Ok(())
+    }
+
+    fn set_input_files(&mut self, selector: &SelectorHint, files: &[String]) -> Result<(), AgentError> {
+        let name = self.resolve(selector, "set_input_files")?;
+        self.actions.push(MockAction::SetInputFiles {
+            name,
+            files: files.to_vec(),
+        });
+        Ok(())
+    }
+
+    fn wait_idle(&mut self, ms: u64) -> Result<(), AgentError> {
+        self.actions.push(MockAction::Wait(ms));
+        Ok(())
+    }
+
+    fn route(&mut self, mock: &RouteMock) -> Result<(), AgentError> {
+        mock.resolve()?;
+        self.routes
+            .retain(|r| (r.url.as_str(), &r.method) != (mock.url.as_str(), &mock.method));
+        self.routes.push(mock.clone());
+        self.actions.push(MockAction::Route(mock.url.clone()));
+        Ok(())
+    }
+
+    fn unroute(&mut self, url: Option<&str>) -> Result<(), AgentError> {
+        self.routes.retain(|r| url.is_some_and(|u| u != r.url));
+        self.actions.push(MockAction::Unroute(url.map(String::from)));
+        Ok(())
+    }
+
+    fn screenshot(&mut self, path: &str) -> Result<(), AgentError> {
+        self.actions.push(MockAction::Screenshot(path.to_string()));
+        Ok(())
+    }
+
+    fn start_trace(&mut self) -> Result<(), AgentError> {
+        self.tracing = true;
+        self.actions.push(MockAction::StartTrace);
+        Ok(())
+    }
+
+    fn stop_trace(&mut self, path: Option<&str>) -> Result<(), AgentError> {
+        if !std::mem::take(&mut self.tracing) {
+            return Err(AgentError::BrowserAction("No trace is being recorded".into()));
+        }
+        self.actions.push(MockAction::StopTrace(path.map(String::from)));
+        Ok(())
+    }
+
+    fn start_video(&mut self) -> Result<(), AgentError> {
+        self.video = true;
+        self.actions.push(MockAction::StartVideo);
+        Ok(())
+    }
+
+    fn stop_video(&mut self, path: Option<&str>) -> Result<(), AgentError> {
+        if !std::mem::take(&mut self.video) {
+            return Err(AgentError::BrowserAction("No video is being recorded".into()));
+        }
+        self.actions.push(MockAction::StopVideo(path.map(String::from)));
+        Ok(())
+    }
+
+    fn current_url(&mut self) -> Result<String, AgentError> {
+        self.current_url
+            .clone()
+            .ok_or_else(|| AgentError::SessionProtocol {
+                command: "current_url".into(),
+                error: "No page loaded in MockBrowser".into(),
+            })
+    }
+
+    fn query_text(&mut self, selector: &str) -> Result<Option<String>, AgentError> {
+        let matched = self.query(selector, "query_text")?;
+        Ok(matched
+            .first()
+            .map(|el| el["text"].as_str().unwrap_or("").to_string()))
+    }
+
+    fn query_visible(&mut self, selector: &str) -> Result<bool, AgentError> {
+        let matched = self.query(selector, "query_visible")?;
+        Ok(matched
+            .first()
+            .map(|el| el["visible"].as_bool().unwrap_or(true))
+            .unwrap_or(false))
+    }
+
+    fn query_count(&mut self, selector: &str) -> Result<u32, AgentError> {
+        Ok(self.query(selector, "query_count")?.len() as u32)
+    }
+
+    fn take_network_log(&mut self) -> Result<Vec<NetworkEntry>, AgentError> {
+        Ok(std::mem::take(&mut self.network_log))
+    }
+
+    fn take_console_log(&mut self) -> Result<Vec<ConsoleEntry>, AgentError> {
+        Ok(std::mem::take(&mut self.console_log))
+    }
+
+    fn set_dialog_policy(&mut self, policy: &DialogPolicy) -> Result<(), AgentError> {
+        self.actions.push(MockAction::SetDialogPolicy(policy.clone()));
+        self.dialog_policy = policy.clone();
+        Ok(())
+    }
+
+    fn take_dialogs(&mut self) -> Result<Vec<DialogEntry>, AgentError> {
+        Ok(std::mem::take(&mut self.dialog_log))
+    }
+
+    fn tabs(&mut self) -> Result<Vec<TabInfo>, AgentError> {
+        Ok(self.tab_infos())
+    }
+
+    fn switch_tab(&mut self, tab: &TabSelector) -> Result<TabInfo, AgentError> {
+        let found = self.find_tab(tab, "switch_tab")?;
+        Ok(self.activate(found.index))
+    }
+
+    /// Closing the active tab activates the tab before it.
+    fn close_tab(&mut self, tab: &TabSelector) -> Result<(), AgentError> {
+        let index = if tab.is_empty() {
+            self.sync_tabs();
+            self.active_tab
+        } else {
+            self.find_tab(tab, "close_tab")?.index
+        };
+        if self.tabs.len() == 1 {
+            return Err(AgentError::SessionProtocol {
+                command: "close_tab".into(),
+                error: "Cannot close the last open tab".into(),
+            });
+        }
+        self.tabs.remove(index);
+        self.popups.retain(|&p| p != index);
+        for p in &mut self.popups {
+            if *p > index {
+                *p -= 1;
+            }
+        }
+        if index < self.active_tab || (index == self.active_tab && index > 0) {
+            self.active_tab -= 1;
+        }
+        self.current_url = self.tabs[self.active_tab].clone();
+        self.actions.push(MockAction::CloseTab(index));
+        Ok(())
+    }
+
+    fn wait_for_popup(&mut self, timeout_ms: u64) -> Result<TabInfo, AgentError> {
+        match self.popups.first().copied() {
+            Some(index) => Ok(self.activate(index)),
+            None => Err(AgentError::Timeout {
+                command: "wait_for_popup".into(),
+                timeout_ms,
+            }),
+        }
+    }
+
+    fn start_recording(&mut self) -> Result<(), AgentError> {
+        self.actions.push(MockAction::StartRecording);
+        self.recording = true;
+        Ok(())
+    }
+
+    fn take_recorded_events(&mut self) -> Result<Vec<RecordedEvent>, AgentError> {
+        if !self.recording {
+            return Ok(Vec::new());
+        }
+        let events = self.recorded.pop_front().unwrap_or_default();
+        for event in &events {
+            match event {
+                RecordedEvent::Click { element } => {
+                    let target = [&element.associated_label_text, &element.aria_label, &element.text]
+                        .into_iter()
+                        .flatten()
+                        .find_map(|label| self.click_targets.get(&label.trim().to_lowercase()).cloned());
+                    if let Some(target) = target {
+                        self.land_on(&target);
+                    }
+                }
+                RecordedEvent::Navigate { url } => self.land_on(url),
+                RecordedEvent::Fill { .. } | RecordedEvent::Select { .. } => {}
+            }
+        }
+        Ok(events)
+    }
+
+    fn save_storage_state(&mut self, path: &str) -> Result<(), AgentError> {
+        self.actions.push(MockAction::SaveStorageState(path.to_string()));
+        Ok(())
+    }
+
+    fn load_storage_state(&mut self, path: &str) -> Result<(), AgentError> {
+        self.actions.push(MockAction::LoadStorageState(path.to_string()));
+        Ok(())
+    }
+}
+
+// ============================================================================
+// Matching helpers
+// ============================================================================
+
+/// Parse `tag`, `#id` or `tag#id` into its parts. Returns None for anything richer.
+fn parse_simple_css(selector: &str) -> Option<(Option<String>, Option<String>)> {
+    if selector.is_empty()
+        || selector
+            .chars()
+            .any(|c| matches!(c, '.' | '[' | ']' | ' ' | '>' | '+' | '~' | ':'))
+    {
+        return None;
+    }
+    let (tag, id) = match selector.split_once('#') {
+        Some((t, i)) => (t, Some(i.to_string())),
+        None => (selector, None),
+    };
+    let tag = if tag.is_empty() { None } else { Some(tag.to_lowercase()) };
+    Some((tag, id))
+}
@@ -1,2 +1,17 @@
+pub mod backend;
+pub mod candidates;
+pub mod console;
+pub mod dialogs;
+pub mod doctor;
+pub mod mock;
+pub mod network;
+pub mod options;
+pub mod pipeline;
 pub mod playwright;
+pub mod recording;
+pub mod routes;
+pub mod scripts;
 pub mod session;
+pub mod static_html;
+pub mod tabs;
+pub mod url;
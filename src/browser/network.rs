@@ -0,0 +1,144 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+// ============================================================================
+// Network capture — requests recorded by the browser during a test
+// ============================================================================
+
+/// One HTTP request/response pair recorded by `browser_server.js`.
+///
+/// `status` is `None` when no response arrived: either the request failed
+/// (DNS failure, connection refused, aborted — `failure` carries the browser's
+/// reason) or it was still in flight when the log was read.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct NetworkEntry {
+    pub url: String,
+
+    pub method: String,
+
+    #[serde(default)]
+    pub status: Option<u16>,
+
+    /// Playwright resource type: document, xhr, fetch, script, image, ...
+    #[serde(default)]
+    pub resource_type: Option<String>,
+
+    /// Response `Content-Type`
+    #[serde(default)]
+    pub mime_type: Option<String>,
+
+    /// Request start as an ISO 8601 timestamp
+    #[serde(default)]
+    pub started_at: Option<String>,
+
+    /// Total time from request start to response end, in milliseconds
+    #[serde(default)]
+    pub duration_ms: Option<f64>,
+
+    /// Response body size in bytes
+    #[serde(default)]
+    pub size: Option<u64>,
+
+    /// Network-level failure reason (e.g. "net::ERR_CONNECTION_REFUSED")
+    #[serde(default)]
+    pub failure: Option<String>,
+}
+
+impl NetworkEntry {
+    /// Whether this request failed at the network level or got a status >= `min_status`.
+    pub fn is_failed(&self, min_status: u16) -> bool {
+        self.failure.is_some() || self.status.is_some_and(|s| s >= min_status)
+    }
+
+    /// Compact one-line form for assertion messages: `GET /api/x → 500`.
+    pub fn summary(&self) -> String {
+        let outcome = match (self.status, &self.failure) {
+            (Some(status), _) => status.to_string(),
+            (None, Some(failure)) => failure.clone(),
+            (None, None) => "pending".to_string(),
+        };
+        format!("{} {} → {}", self.method, self.url, outcome)
+    }
+}
+
+// ============================================================================
+// HAR export
+// ============================================================================
+
+/// Build a HAR 1.2 document from recorded entries.
+///
+/// Only the fields the server reports are filled in; headers, cookies and
+/// bodies are left empty. Failed requests carry a `_failureText` extension.
+pub fn to_har(entries: &[NetworkEntry], page_title: &str) -> Value {
+    let har_entries: Vec<Value> = entries
+        .iter()
+        .map(|e| {
+            let time = e.duration_ms.unwrap_or(0.0);
+            let size = e.size.map(|s| s as i64).unwrap_or(-1);
+            let mut entry = json!({
+                "pageref": "page_1",
+                "startedDateTime": e.started_at.as_deref().unwrap_or("1970-01-01T00:00:00.000Z"),
+                "time": time,
+                "request": {
+                    "method": e.method,
+                    "url": e.url,
+                    "httpVersion": "HTTP/1.1",
+                    "cookies": [],
+                    "headers": [],
+                    "queryString": [],
+                    "headersSize": -1,
+                    "bodySize": -1
+                },
+                "response": {
+                    "status": e.status.unwrap_or(0),
+                    "statusText": "",
+                    "httpVersion": "HTTP/1.1",
+                    "cookies": [],
+                    "headers": [],
+                    "content": {
+                        "size": size,
+                        "mimeType": e.mime_type.as_deref().unwrap_or("")
+                    },
+                    "redirectURL": "",
+                    "headersSize": -1,
+                    "bodySize": size
+                },
+                "cache": {},
+                "timings": { "send": 0, "wait": time, "receive": 0 }
+            });
+            if let Some(resource_type) = &e.resource_type {
+                entry["_resourceType"] = json!(resource_type);
+            }
+            if let Some(failure) = &e.failure {
+                entry["_failureText"] = json!(failure);
+            }
+            entry
+        })
+        .collect();
+
+    let started = entries
+        .iter()
+        .find_map(|e| e.started_at.as_deref())
+        .unwrap_or("1970-01-01T00:00:00.000Z");
+
+    json!({
+        "log": {
+            "version": "1.2",
+            "creator": { "name": "screen-detection", "version": env!("CARGO_PKG_VERSION") },
+            "pages": [{
+                "startedDateTime": started,
+                "id": "page_1",
+                "title": page_title,
+                "pageTimings": {}
+            }],
+            "entries": har_entries
+        }
+    })
+}
+
+/// Write entries as a HAR file (pretty-printed JSON).
+pub fn write_har(path: &str, entries: &[NetworkEntry], page_title: &str) -> std::io::Result<()> {
+    let har = to_har(entries, page_title);
+    let content = serde_json::to_string_pretty(&har).map_err(std::io::Error::other)?;
+    std::fs::write(path, content)
+}
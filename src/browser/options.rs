@@ -0,0 +1,103 @@
+use serde::{Deserialize, Serialize};
+
+// ============================================================================
+// SessionOptions — browser context settings sent to browser_server.js
+// ============================================================================
+
+/// Browser context settings: viewport, device emulation, locale and friends.
+///
+/// Passed to `browser_server.js` at launch and again whenever a spec asks for
+/// different settings. Every field is optional; unset fields keep Playwright's
+/// defaults (or the device preset's values when `device` is set). Explicit
+/// fields win over the device preset.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct SessionOptions {
+    /// Run Chromium with a visible window (default: headless)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub headed: Option<bool>,
+
+    /// Playwright device preset name, e.g. "iPhone 13" or "Pixel 7"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub device: Option<String>,
+
+    /// Viewport size in CSS pixels
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub viewport: Option<Viewport>,
+
+    /// User-Agent header and `navigator.userAgent` override
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_agent: Option<String>,
+
+    /// BCP 47 locale, e.g. "de-DE"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
+
+    /// IANA timezone id, e.g. "Europe/Berlin"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
+
+    /// Emulated `prefers-color-scheme`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color_scheme: Option<ColorScheme>,
+
+    /// Emulated geolocation (usually paired with the "geolocation" permission)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub geolocation: Option<Geolocation>,
+
+    /// Permissions granted to every origin, e.g. ["geolocation", "notifications"]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub permissions: Vec<String>,
+}
+
+/// Viewport dimensions in CSS pixels.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Viewport {
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Emulated geographic position.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct Geolocation {
+    pub latitude: f64,
+    pub longitude: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub accuracy: Option<f64>,
+}
+
+/// Value for the `prefers-color-scheme` media feature.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum ColorScheme {
+    Light,
+    Dark,
+    NoPreference,
+}
+
+impl SessionOptions {
+    /// Layer `overrides` on top of `self`: any field set in `overrides` wins.
+    ///
+    /// Permissions are replaced, not merged, when `overrides` lists any.
+    pub fn merged(&self, overrides: &SessionOptions) -> SessionOptions {
+        SessionOptions {
+            headed: overrides.headed.or(self.headed),
+            device: overrides.device.clone().or_else(|| self.device.clone()),
+            viewport: overrides.viewport.or(self.viewport),
+            user_agent: overrides.user_agent.clone().or_else(|| self.user_agent.clone()),
+            locale: overrides.locale.clone().or_else(|| self.locale.clone()),
+            timezone: overrides.timezone.clone().or_else(|| self.timezone.clone()),
+            color_scheme: overrides.color_scheme.or(self.color_scheme),
+            geolocation: overrides.geolocation.or(self.geolocation),
+            permissions: if overrides.permissions.is_empty() {
+                self.permissions.clone()
+            } else {
+                overrides.permissions.clone()
+            },
+        }
+    }
+
+    /// Whether no option is set (Playwright defaults, headless).
+    pub fn is_empty(&self) -> bool {
+        *self == SessionOptions::default()
+    }
+}
@@ -0,0 +1,419 @@
+use std::collections::BTreeMap;
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Child, ChildStdin, Stdio};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender, TryRecvError};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::agent::error::AgentError;
+use crate::browser::console::ConsoleEntry;
+use crate::browser::dialogs::DialogEntry;
+use crate::browser::scripts;
+use crate::browser::session::{BrowserRequest, BrowserResponse, PROTOCOL_VERSION, ServerHandshake, SessionConfig};
+
+// ============================================================================
+// Events — unsolicited lines pushed by the server
+// ============================================================================
+
+/// Kinds of events a client can subscribe to (see `BrowserRequest::subscribe`).
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum EventKind {
+    Console,
+    Dialog,
+    Navigation,
+}
+
+/// A main-frame navigation observed by the server.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct NavigationEvent {
+    pub url: String,
+
+    /// Index of the tab that navigated
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tab: Option<usize>,
+}
+
+/// A line the server sent on its own rather than in answer to a request:
+/// `{"event":"console","data":{...}}`. Events carry no request ID and are
+/// only sent for subscribed kinds.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "event", content = "data", rename_all = "snake_case")]
+pub enum ServerEvent {
+    Console(ConsoleEntry),
+    Dialog(DialogEntry),
+    Navigation(NavigationEvent),
+    /// An event this build does not know (or could not read)
+    #[serde(other)]
+    Other,
+}
+
+impl ServerEvent {
+    /// The subscription this event belongs to (`None` for `Other`).
+    pub fn kind(&self) -> Option<EventKind> {
+        match self {
+            ServerEvent::Console(_) => Some(EventKind::Console),
+            ServerEvent::Dialog(_) => Some(EventKind::Dialog),
+            ServerEvent::Navigation(_) => Some(EventKind::Navigation),
+            ServerEvent::Other => None,
+        }
+    }
+}
+
+// ============================================================================
+// Pipelined session — many requests in flight over one server
+// ============================================================================
+
+/// A request with the ID the server echoes in its response.
+#[derive(Serialize)]
+struct Envelope<'a> {
+    id: u64,
+    #[serde(flatten)]
+    request: &'a BrowserRequest,
+}
+
+type Reply = Result<BrowserResponse, AgentError>;
+
+/// Requests still waiting for their response, shared with the reader thread.
+///
+/// A request nobody waits for any more (timed out, cancelled or dropped)
+/// keeps its slot, emptied, until its answer arrives, so an answer without
+/// an ID can never be handed to a later request.
+#[derive(Default)]
+struct Waiters {
+    pending: BTreeMap<u64, Option<Sender<Reply>>>,
+    /// Set once the server's stdout closed; later requests fail at once
+    closed: bool,
+}
+
+/// A browser_server.js process that accepts requests without waiting for
+/// earlier ones to be answered.
+///
+/// A dedicated reader thread routes each response to its request by the
+/// echoed ID, so responses may arrive in any order; a response without an
+/// ID answers the oldest request still waiting (servers that handle one
+/// request at a time). Event lines go to a separate queue read with
+/// `take_events` / `next_event`. All methods take `&self`, so the session
+/// can be shared between threads.
+///
+/// Unlike `BrowserSession` there is no crash recovery: a dead server fails
+/// every waiting request, and every later one.
+pub struct PipelinedSession {
+    child: Mutex<Child>,
+    stdin: Mutex<ChildStdin>,
+    waiters: Arc<Mutex<Waiters>>,
+    events: Mutex<Receiver<ServerEvent>>,
+    handshake: ServerHandshake,
+    next_id: AtomicU64,
+}
+
+/// The not-yet-received response to one pipelined request.
+///
+/// Dropping it without waiting discards the response when it arrives.
+pub struct PendingResponse {
+    id: u64,
+    command: String,
+    reply: Receiver<Reply>,
+    waiters: Arc<Mutex<Waiters>>,
+}
+
+impl PendingResponse {
+    /// ID the request was sent with.
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// Name the request is reported under in errors.
+    pub fn command(&self) -> &str {
+        &self.command
+    }
+
+    /// Block up to `timeout` for the response. On timeout the request is
+    /// abandoned: its response is discarded if it arrives later.
+    pub fn wait(self, timeout: Duration) -> Result<BrowserResponse, AgentError> {
+        match self.reply.recv_timeout(timeout) {
+            Ok(reply) => reply,
+            Err(RecvTimeoutError::Timeout) => Err(AgentError::Timeout {
+                command: self.command.clone(),
+                timeout_ms: timeout.as_millis() as u64,
+            }),
+            Err(RecvTimeoutError::Disconnected) => Err(server_gone()),
+        }
+    }
+
+    /// The response if it has arrived, without blocking.
+    pub fn try_wait(&self) -> Option<Result<BrowserResponse, AgentError>> {
+        match self.reply.try_recv() {
+            Ok(reply) => Some(reply),
+            Err(TryRecvError::Empty) => None,
+            Err(TryRecvError::Disconnected) => Some(Err(server_gone())),
+        }
+    }
+}
+
+impl Drop for PendingResponse {
+    fn drop(&mut self) {
+        if let Some(waiter) = self.waiters.lock().unwrap().pending.get_mut(&self.id) {
+            *waiter = None;
+        }
+    }
+}
+
+impl PipelinedSession {
+    /// Spawn the server and wait (bounded by `launch_timeout_ms`) for its
+    /// ready signal, which must announce this build's protocol version.
+    ///
+    /// Non-default launch options are passed as `--options <json>`.
+    pub fn launch(config: &SessionConfig) -> Result<Self, AgentError> {
+        let script = scripts::resolve_script(&config.server_script, config.scripts_dir.as_deref())?;
+        let mut command = scripts::node_command(&config.node_path);
+        command.arg(script);
+        if !config.options.is_empty() {
+            let options = serde_json::to_string(&config.options).map_err(|e| {
+                AgentError::JsonSerialize {
+                    context: "SessionOptions".into(),
+                    source: e,
+                }
+            })?;
+            command.arg("--options").arg(options);
+        }
+        let mut child = command
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| AgentError::SubprocessSpawn {
+                script: "browser_server.js".into(),
+                source: e,
+            })?;
+
+        let stdin = child.stdin.take().ok_or_else(|| {
+            AgentError::SessionIO("Failed to capture stdin of browser_server.js".into())
+        })?;
+
+        let stdout = child.stdout.take().ok_or_else(|| {
+            AgentError::SessionIO("Failed to capture stdout of browser_server.js".into())
+        })?;
+
+        // Drain stderr so a chatty server can never block on a full pipe.
+        if let Some(mut stderr) = child.stderr.take() {
+            thread::spawn(move || {
+                let _ = std::io::copy(&mut stderr, &mut std::io::sink());
+            });
+        }
+
+        // The ready line has no ID: it answers this placeholder request 0.
+        let waiters = Arc::new(Mutex::new(Waiters::default()));
+        let (ready_tx, ready_rx) = mpsc::channel();
+        waiters.lock().unwrap().pending.insert(0, Some(ready_tx));
+        let ready = PendingResponse {
+            id: 0,
+            command: "launch".into(),
+            reply: ready_rx,
+            waiters: Arc::clone(&waiters),
+        };
+
+        let (events_tx, events) = mpsc::channel();
+        let reader_waiters = Arc::clone(&waiters);
+        thread::spawn(move || read_responses(BufReader::new(stdout), &reader_waiters, &events_tx));
+
+        let mut session = PipelinedSession {
+            child: Mutex::new(child),
+            stdin: Mutex::new(stdin),
+            waiters,
+            events: Mutex::new(events),
+            handshake: ServerHandshake { protocol: PROTOCOL_VERSION, capabilities: Vec::new() },
+            next_id: AtomicU64::new(1),
+        };
+        let handshake = ready
+            .wait(Duration::from_millis(config.launch_timeout_ms))
+            .and_then(|response| ServerHandshake::from_ready(&response));
+        match handshake {
+            Ok(handshake) => {
+                session.handshake = handshake;
+                Ok(session)
+            }
+            Err(e) => {
+                session.kill();
+                Err(e)
+            }
+        }
+    }
+
+    /// Protocol version and capabilities announced in the ready line.
+    pub fn handshake(&self) -> &ServerHandshake {
+        &self.handshake
+    }
+
+    /// Send `request` without waiting for its response.
+    ///
+    /// Commands the server did not announce are rejected without being sent.
+    pub fn send(&self, request: &BrowserRequest) -> Result<PendingResponse, AgentError> {
+        self.send_as(request, request.command())
+    }
+
+    /// `send`, reporting errors under `command_name` (e.g. "click" for an
+    /// `action` request).
+    pub(crate) fn send_as(&self, request: &BrowserRequest, command_name: &str) -> Result<PendingResponse, AgentError> {
+        if !self.handshake.supports(request.command()) {
+            return Err(AgentError::UnsupportedCommand {
+                command: request.command().into(),
+                error: format!("not among the capabilities of protocol {}", self.handshake.protocol),
+            });
+        }
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let json = serde_json::to_string(&Envelope { id, request }).map_err(|e| AgentError::JsonSerialize {
+            context: "BrowserRequest".into(),
+            source: e,
+        })?;
+
+        let (tx, reply) = mpsc::channel();
+        {
+            let mut waiters = self.waiters.lock().unwrap();
+            if waiters.closed {
+                return Err(server_gone());
+            }
+            waiters.pending.insert(id, Some(tx));
+        }
+        let pending = PendingResponse {
+            id,
+            command: command_name.into(),
+            reply,
+            waiters: Arc::clone(&self.waiters),
+        };
+
+        let mut stdin = self.stdin.lock().unwrap();
+        writeln!(stdin, "{}", json).map_err(|e| {
+            AgentError::SessionIO(format!("Failed to write to browser_server.js stdin: {}", e))
+        })?;
+        stdin.flush().map_err(|e| {
+            AgentError::SessionIO(format!("Failed to flush browser_server.js stdin: {}", e))
+        })?;
+        Ok(pending)
+    }
+
+    /// Send `request` and block up to `timeout` for its response.
+    pub fn call(
+        &self,
+        request: &BrowserRequest,
+        command_name: &str,
+        timeout: Duration,
+    ) -> Result<BrowserResponse, AgentError> {
+        self.send_as(request, command_name)?.wait(timeout)
+    }
+
+    /// Ask the server to abort the request behind `pending` (a slow
+    /// `navigate`, say). Whoever waits on it gets `Cancelled` right away;
+    /// whatever the server answers for it later is discarded.
+    pub fn cancel(&self, pending: &PendingResponse) -> Result<(), AgentError> {
+        // Dropped at once: the cancel request's own answer is not needed.
+        self.send(&BrowserRequest::cancel(pending.id))?;
+        let waiter = self.waiters.lock().unwrap().pending.get_mut(&pending.id).and_then(Option::take);
+        if let Some(waiter) = waiter {
+            let _ = waiter.send(Err(AgentError::Cancelled { command: pending.command.clone() }));
+        }
+        Ok(())
+    }
+
+    /// Events received so far, oldest first (clears the queue).
+    pub fn take_events(&self) -> Vec<ServerEvent> {
+        self.events.lock().unwrap().try_iter().collect()
+    }
+
+    /// Block up to `timeout` for the next event.
+    pub fn next_event(&self, timeout: Duration) -> Option<ServerEvent> {
+        self.events.lock().unwrap().recv_timeout(timeout).ok()
+    }
+
+    /// Whether the server process is still running.
+    pub fn is_alive(&self) -> bool {
+        matches!(self.child.lock().unwrap().try_wait(), Ok(None))
+    }
+
+    /// Ask the server to quit, then make sure the child is reaped.
+    pub fn shutdown(self) {
+        let _ = self.call(&BrowserRequest::quit(), "quit", Duration::from_secs(5));
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while Instant::now() < deadline {
+            if !self.is_alive() {
+                return;
+            }
+            thread::sleep(Duration::from_millis(20));
+        }
+    }
+
+    /// Kill the server at once.
+    pub fn kill(&self) {
+        let mut child = self.child.lock().unwrap();
+        let _ = child.kill();
+        let _ = child.wait();
+    }
+}
+
+impl Drop for PipelinedSession {
+    fn drop(&mut self) {
+        // No-op when the server already exited
+        self.kill();
+    }
+}
+
+/// Reader thread: route each stdout line to its waiter or the event queue
+/// until the server closes stdout, then fail everything still waiting.
+fn read_responses(stdout: impl BufRead, waiters: &Mutex<Waiters>, events: &Sender<ServerEvent>) {
+    for line in stdout.lines() {
+        let Ok(line) = line else { break };
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let value: Value = match serde_json::from_str(line) {
+            Ok(value) => value,
+            Err(e) => {
+                deliver(waiters, None, Err(json_error(e)));
+                continue;
+            }
+        };
+        if value.get("event").is_some() {
+            let event = serde_json::from_value(value).unwrap_or(ServerEvent::Other);
+            let _ = events.send(event);
+            continue;
+        }
+        let id = value["id"].as_u64();
+        deliver(waiters, id, serde_json::from_value(value).map_err(json_error));
+    }
+
+    let mut waiters = waiters.lock().unwrap();
+    waiters.closed = true;
+    for waiter in std::mem::take(&mut waiters.pending).into_values().flatten() {
+        let _ = waiter.send(Err(server_gone()));
+    }
+}
+
+/// Hand `reply` to the request with `id` (the oldest one when `None`).
+/// Replies to requests nobody waits for any more are dropped.
+fn deliver(waiters: &Mutex<Waiters>, id: Option<u64>, reply: Reply) {
+    let mut waiters = waiters.lock().unwrap();
+    let waiter = match id {
+        Some(id) => waiters.pending.remove(&id),
+        None => waiters.pending.pop_first().map(|(_, waiter)| waiter),
+    };
+    if let Some(Some(waiter)) = waiter {
+        let _ = waiter.send(reply);
+    }
+}
+
+fn json_error(source: serde_json::Error) -> AgentError {
+    AgentError::JsonParse {
+        context: "browser_server.js response".into(),
+        source,
+    }
+}
+
+fn server_gone() -> AgentError {
+    AgentError::SessionIO("browser_server.js closed its output (process may have died)".into())
+}
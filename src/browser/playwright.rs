@@ -1,12 +1,18 @@
-use serde::{Deserialize, Serialize};
+use serde::ser::SerializeMap;
+use serde::{Deserialize, Serialize, Serializer};
 use serde_json::Value;
-use std::process::Command;
 
 use crate::agent::error::AgentError;
+use crate::browser::scripts::{self, EXTRACT_SCRIPT, INTERACT_SCRIPT};
 
+/// Extract the DOM of `url` with a one-shot extract.js run.
+///
+/// Node and the script are located like `BrowserSession`'s, from the
+/// `SCREEN_DETECTION_NODE` / `SCREEN_DETECTION_SCRIPTS_DIR` environment.
 pub fn extract_screen(url: &str) -> Result<Value, AgentError> {
-    let output = Command::new("node")
-        .arg("../../node/dom-extraction/extract.js")
+    let script = scripts::resolve_script(EXTRACT_SCRIPT, None)?;
+    let output = scripts::node_command(&scripts::default_node_path())
+        .arg(script)
         .arg(url)
         .output()
         .map_err(|e| AgentError::SubprocessSpawn {
@@ -30,19 +36,69 @@ pub fn extract_screen(url: &str) -> Result<Value, AgentError> {
     })
 }
 
+/// Prefix of the synthetic ids given to virtual forms (see `classify`).
+pub const VIRTUAL_FORM_PREFIX: &str = "virtual:";
+
+/// Whether `form_id` names a virtual form, which has no `<form>` element a
+/// selector could be scoped to.
+pub fn is_virtual_form_id(form_id: &str) -> bool {
+    form_id.starts_with(VIRTUAL_FORM_PREFIX)
+}
+
 /// Selector hints used by interact.js to locate elements in the DOM.
-#[derive(Debug, Clone, Serialize)]
+///
+/// Serialized by hand so that `formId` carries `form_scope()` rather than
+/// the raw form id.
+#[derive(Debug, Clone)]
 pub struct SelectorHint {
-    #[serde(skip_serializing_if = "Option::is_none")]
     pub role: Option<String>, // ARIA role, e.g. "textbox", "button"
-    #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>, // accessible name (aria-label or visible text)
-    #[serde(skip_serializing_if = "Option::is_none")]
     pub tag: Option<String>, // HTML tag, e.g. "input", "button", "a"
-    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
-    pub input_type: Option<String>, // type attribute, e.g. "text", "submit", "email"
-    #[serde(rename = "formId", skip_serializing_if = "Option::is_none")]
-    pub form_id: Option<String>, // parent form ID, e.g. "search"
+    pub input_type: Option<String>, // type attribute ("type"), e.g. "text", "submit", "email"
+    pub form_id: Option<String>, // parent form ID ("formId"), e.g. "search"; only `form_scope()` is sent
+    pub frame_path: Vec<String>, // iframe selectors from the top document down ("framePath"); empty = main frame
+    pub shadow_path: Vec<String>, // shadow hosts to search within ("shadowPath"), outermost first; empty = whole page
+}
+
+impl SelectorHint {
+    /// The `<form>` id to look in, if any.
+    ///
+    /// Virtual forms (see `classify`) and the implicit form of an iframe,
+    /// named after its frame, have no `<form>` element of their own, so they
+    /// do not narrow the search.
+    pub fn form_scope(&self) -> Option<&str> {
+        self.form_id
+            .as_deref()
+            .filter(|id| !is_virtual_form_id(id) && self.frame_path.last().map(String::as_str) != Some(*id))
+    }
+
+    /// Whether the hint names a form that `form_scope` cannot narrow the
+    /// search to, so the browser looks for the element across its frame.
+    pub fn has_unscoped_form(&self) -> bool {
+        self.form_id.is_some() && self.form_scope().is_none()
+    }
+}
+
+impl Serialize for SelectorHint {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        let optional = [("role", &self.role), ("name", &self.name), ("tag", &self.tag), ("type", &self.input_type)];
+        for (key, value) in optional {
+            if let Some(value) = value {
+                map.serialize_entry(key, value)?;
+            }
+        }
+        if let Some(form_id) = self.form_scope() {
+            map.serialize_entry("formId", form_id)?;
+        }
+        if !self.frame_path.is_empty() {
+            map.serialize_entry("framePath", &self.frame_path)?;
+        }
+        if !self.shadow_path.is_empty() {
+            map.serialize_entry("shadowPath", &self.shadow_path)?;
+        }
+        map.end()
+    }
 }
 
 /// Command sent to interact.js for execution.
@@ -72,8 +128,9 @@ pub fn execute_browser_action(command: &BrowserCommand) -> Result<(), AgentError
         source: e,
     })?;
 
-    let output = Command::new("node")
-        .arg("../../node/dom-extraction/interact.js")
+    let script = scripts::resolve_script(INTERACT_SCRIPT, None)?;
+    let output = scripts::node_command(&scripts::default_node_path())
+        .arg(script)
         .arg(&command_json)
         .output()
         .map_err(|e| AgentError::SubprocessSpawn {
@@ -0,0 +1,27 @@
+use serde::Deserialize;
+
+use crate::screen::screen_model::DomElement;
+
+// ============================================================================
+// Recording — user interactions captured in a headed browser
+// ============================================================================
+
+/// One user interaction reported by `browser_server.js` while recording.
+///
+/// The target element is serialized exactly like an `extract` DOM entry, so
+/// it classifies (label, form id, frame path) the same way a snapshot would.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum RecordedEvent {
+    /// Mouse click on an element
+    Click { element: DomElement },
+
+    /// Final value of a text-like input after the user edited it (`change` event)
+    Fill { element: DomElement, value: String },
+
+    /// Option chosen in a `<select>`
+    Select { element: DomElement, value: String },
+
+    /// Top-level navigation of the active tab, whatever caused it
+    Navigate { url: String },
+}
@@ -0,0 +1,165 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::agent::error::AgentError;
+
+// ============================================================================
+// Route mocks — canned responses for intercepted requests
+// ============================================================================
+
+/// A stubbed response for requests matching `url` (and `method`, if set).
+///
+/// Declared in a spec's `mocks:` section or in `explore.mocks` of the config
+/// file; installed with `BrowserBackend::route` before the page loads.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RouteMock {
+    /// URL glob in Playwright syntax: `*` matches within a path segment,
+    /// `**` matches across segments (e.g. `**/api/orders*`)
+    pub url: String,
+
+    /// HTTP method to intercept (case-insensitive); any method when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub method: Option<String>,
+
+    #[serde(default = "default_status")]
+    pub status: u16,
+
+    /// Response body. Strings are sent verbatim; any other value is sent as JSON.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<Value>,
+
+    /// File whose contents are the response body (instead of `body`),
+    /// relative to the spec file
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fixture: Option<String>,
+
+    /// `Content-Type` header; inferred from `body` or the fixture's extension when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+
+    /// Delay before the response is delivered
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub delay_ms: u64,
+}
+
+fn default_status() -> u16 {
+    200
+}
+
+fn is_zero(ms: &u64) -> bool {
+    *ms == 0
+}
+
+/// A `RouteMock` with its body loaded, as sent to `browser_server.js`.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct ResolvedRoute {
+    pub url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub method: Option<String>,
+    pub status: u16,
+    pub body: String,
+    pub content_type: String,
+    pub delay_ms: u64,
+}
+
+impl RouteMock {
+    /// Intercept `url` and answer with `status` and an empty body.
+    pub fn status(url: &str, status: u16) -> Self {
+        RouteMock {
+            url: url.to_string(),
+            method: None,
+            status,
+            body: None,
+            fixture: None,
+            content_type: None,
+            delay_ms: 0,
+        }
+    }
+
+    /// Whether a request to `url` with `method` would be intercepted.
+    pub fn matches(&self, url: &str, method: &str) -> bool {
+        self.method
+            .as_ref()
+            .is_none_or(|m| m.eq_ignore_ascii_case(method))
+            && glob_matches(&self.url, url)
+    }
+
+    /// Make a relative `fixture` path relative to `dir` instead of the
+    /// working directory.
+    pub fn rebase_fixture(&mut self, dir: &std::path::Path) {
+        if let Some(fixture) = &mut self.fixture
+            && std::path::Path::new(fixture.as_str()).is_relative()
+        {
+            *fixture = dir.join(&*fixture).to_string_lossy().to_string();
+        }
+    }
+
+    /// Load the fixture (if any) and settle the body and content type.
+    pub fn resolve(&self) -> Result<ResolvedRoute, AgentError> {
+        let (body, inferred_type) = match (&self.body, &self.fixture) {
+            (Some(_), Some(_)) => {
+                return Err(AgentError::BrowserAction(format!(
+                    "Mock for '{}' sets both body and fixture",
+                    self.url
+                )));
+            }
+            (Some(Value::String(text)), None) => (text.clone(), "text/plain"),
+            (Some(value), None) => (value.to_string(), "application/json"),
+            (None, Some(path)) => {
+                let body = std::fs::read_to_string(path).map_err(|e| {
+                    AgentError::BrowserAction(format!(
+                        "Cannot read fixture '{}' for mock '{}': {}",
+                        path, self.url, e
+                    ))
+                })?;
+                (body, content_type_for(path))
+            }
+            (None, None) => (String::new(), "text/plain"),
+        };
+        Ok(ResolvedRoute {
+            url: self.url.clone(),
+            method: self.method.as_ref().map(|m| m.to_uppercase()),
+            status: self.status,
+            body,
+            content_type: self
+                .content_type
+                .clone()
+                .unwrap_or_else(|| inferred_type.to_string()),
+            delay_ms: self.delay_ms,
+        })
+    }
+}
+
+/// Content type implied by a fixture file's extension.
+fn content_type_for(path: &str) -> &'static str {
+    let extension = std::path::Path::new(path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("")
+        .to_lowercase();
+    match extension.as_str() {
+        "json" => "application/json",
+        "html" | "htm" => "text/html",
+        "xml" => "application/xml",
+        "js" => "application/javascript",
+        "css" => "text/css",
+        _ => "text/plain",
+    }
+}
+
+/// Match `url` against a Playwright-style glob: `**` matches any run of
+/// characters, `*` any run without `/`; everything else is literal.
+pub fn glob_matches(pattern: &str, url: &str) -> bool {
+    fn matches(pattern: &[u8], text: &[u8]) -> bool {
+        match pattern {
+            [] => text.is_empty(),
+            [b'*', b'*', rest @ ..] => (0..=text.len()).any(|i| matches(rest, &text[i..])),
+            [b'*', rest @ ..] => {
+                let segment = text.iter().position(|&c| c == b'/').unwrap_or(text.len());
+                (0..=segment).any(|i| matches(rest, &text[i..]))
+            }
+            [c, rest @ ..] => text.first() == Some(c) && matches(rest, &text[1..]),
+        }
+    }
+    matches(pattern.as_bytes(), url.as_bytes())
+}
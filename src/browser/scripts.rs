@@ -0,0 +1,172 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::OnceLock;
+
+use crate::agent::error::AgentError;
+
+// ============================================================================
+// Node helper scripts — location, embedding and unpacking
+// ============================================================================
+
+/// One-shot DOM extraction script (`extract_screen`)
+pub const EXTRACT_SCRIPT: &str = "extract.js";
+
+/// One-shot interaction script (`execute_browser_action`)
+pub const INTERACT_SCRIPT: &str = "interact.js";
+
+/// Long-lived server behind `BrowserSession`
+pub const SERVER_SCRIPT: &str = "browser_server.js";
+
+/// Overrides the Node.js executable when `session.node_path` is not set
+pub const NODE_ENV: &str = "SCREEN_DETECTION_NODE";
+
+/// Overrides the script directory when `session.scripts_dir` is not set
+pub const SCRIPTS_DIR_ENV: &str = "SCREEN_DETECTION_SCRIPTS_DIR";
+
+/// Where the scripts lived before they were embedded, relative to the
+/// working directory; still searched so source checkouts keep working.
+const LEGACY_SCRIPTS_DIR: &str = "../../node/dom-extraction";
+
+mod embedded {
+    include!(concat!(env!("OUT_DIR"), "/embedded_scripts.rs"));
+}
+
+/// Scripts compiled into this binary as `(file name, contents)`; empty when
+/// the build found no script sources (see `build.rs`).
+pub fn embedded_scripts() -> &'static [(&'static str, &'static str)] {
+    embedded::EMBEDDED_SCRIPTS
+}
+
+/// Node.js executable: `SCREEN_DETECTION_NODE`, else `node` from `PATH`.
+pub fn default_node_path() -> String {
+    std::env::var(NODE_ENV)
+        .ok()
+        .filter(|p| !p.trim().is_empty())
+        .unwrap_or_else(|| "node".to_string())
+}
+
+/// Script directory from `SCREEN_DETECTION_SCRIPTS_DIR`, if set.
+pub fn env_scripts_dir() -> Option<String> {
+    std::env::var(SCRIPTS_DIR_ENV).ok().filter(|d| !d.trim().is_empty())
+}
+
+/// Find the script to run for `configured`.
+///
+/// A value with a directory part (`./server.js`, `/opt/x/extract.js`) is
+/// used as given. A bare file name is looked up, in order, in `scripts_dir`
+/// (from the config), `SCREEN_DETECTION_SCRIPTS_DIR`, the embedded scripts
+/// (unpacked to the cache dir on first use) and the legacy
+/// `../../node/dom-extraction`. An explicit directory is authoritative:
+/// when one is set, nothing else is searched.
+pub fn resolve_script(configured: &str, scripts_dir: Option<&str>) -> Result<PathBuf, AgentError> {
+    let path = Path::new(configured);
+    if path.components().count() > 1 {
+        return Ok(path.to_path_buf());
+    }
+
+    let not_found = |searched: Vec<String>| AgentError::ScriptNotFound {
+        script: configured.to_string(),
+        searched,
+    };
+
+    if let Some(dir) = scripts_dir.map(String::from).or_else(env_scripts_dir) {
+        let candidate = Path::new(&dir).join(configured);
+        return if candidate.is_file() {
+            Ok(candidate)
+        } else {
+            Err(not_found(vec![dir]))
+        };
+    }
+
+    let mut searched = Vec::new();
+    if let Some(dir) = unpacked_scripts_dir()? {
+        let candidate = dir.join(configured);
+        if candidate.is_file() {
+            return Ok(candidate);
+        }
+        searched.push(dir.display().to_string());
+    } else {
+        searched.push("<no scripts embedded in this build>".to_string());
+    }
+    let legacy = Path::new(LEGACY_SCRIPTS_DIR).join(configured);
+    if legacy.is_file() {
+        return Ok(legacy);
+    }
+    searched.push(LEGACY_SCRIPTS_DIR.to_string());
+    Err(not_found(searched))
+}
+
+/// Directory the embedded scripts are unpacked to, unique per version and
+/// script contents: `$XDG_CACHE_HOME/screen-detection/scripts-<version>-<hash>`
+/// (falling back to `~/.cache`, then the temp dir).
+pub fn cache_dir() -> PathBuf {
+    use sha1::{Digest, Sha1};
+
+    let mut hasher = Sha1::new();
+    for (name, contents) in embedded_scripts() {
+        hasher.update(name.as_bytes());
+        hasher.update(contents.as_bytes());
+    }
+    let hash = format!("{:x}", hasher.finalize());
+
+    let base = std::env::var_os("XDG_CACHE_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|h| PathBuf::from(h).join(".cache")))
+        .unwrap_or_else(std::env::temp_dir);
+    base.join("screen-detection")
+        .join(format!("scripts-{}-{}", env!("CARGO_PKG_VERSION"), &hash[..12]))
+}
+
+/// Unpack the embedded scripts into `cache_dir()` (once per process).
+/// Returns `None` when this build embeds no scripts.
+fn unpacked_scripts_dir() -> Result<Option<PathBuf>, AgentError> {
+    static UNPACKED: OnceLock<Result<PathBuf, String>> = OnceLock::new();
+    if embedded_scripts().is_empty() {
+        return Ok(None);
+    }
+    let result = UNPACKED.get_or_init(|| {
+        let dir = cache_dir();
+        unpack_scripts(embedded_scripts(), &dir).map(|_| dir).map_err(|e| e.to_string())
+    });
+    result.clone().map(Some).map_err(AgentError::SessionIO)
+}
+
+/// Write `scripts` into `dir`, skipping files that are already up to date.
+pub fn unpack_scripts(scripts: &[(&str, &str)], dir: &Path) -> Result<(), AgentError> {
+    let io_error = |path: &Path, e: std::io::Error| {
+        AgentError::SessionIO(format!("Cannot unpack Node scripts to '{}': {}", path.display(), e))
+    };
+    std::fs::create_dir_all(dir).map_err(|e| io_error(dir, e))?;
+    for (name, contents) in scripts {
+        let path = dir.join(name);
+        if std::fs::read_to_string(&path).is_ok_and(|existing| existing == *contents) {
+            continue;
+        }
+        std::fs::write(&path, contents).map_err(|e| io_error(&path, e))?;
+    }
+    Ok(())
+}
+
+/// A `node` command that can load Playwright from the global npm root when
+/// the script's own directory has no `node_modules` (as for unpacked
+/// scripts). An existing `NODE_PATH` is left alone.
+pub fn node_command(node_path: &str) -> Command {
+    let mut command = Command::new(node_path);
+    if std::env::var_os("NODE_PATH").is_none()
+        && let Some(root) = global_node_modules()
+    {
+        command.env("NODE_PATH", root);
+    }
+    command
+}
+
+/// `npm root -g`, looked up once per process.
+fn global_node_modules() -> Option<&'static str> {
+    static ROOT: OnceLock<Option<String>> = OnceLock::new();
+    ROOT.get_or_init(|| {
+        let output = Command::new("npm").args(["root", "-g"]).output().ok()?;
+        let root = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        (output.status.success() && !root.is_empty()).then_some(root)
+    })
+    .as_deref()
+}
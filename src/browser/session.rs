@@ -1,13 +1,109 @@
-use std::io::{BufRead, BufReader, Write};
-use std::process::{Child, Command, Stdio};
+use std::time::Duration;
 
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
 use crate::agent::error::AgentError;
+use crate::browser::backend::{BrowserBackend, resolve_hint};
+use crate::browser::console::ConsoleEntry;
+use crate::browser::dialogs::{DialogEntry, DialogPolicy};
+use crate::browser::network::NetworkEntry;
+use crate::browser::options::SessionOptions;
+use crate::browser::pipeline::{EventKind, PipelinedSession, ServerEvent};
 use crate::browser::playwright::SelectorHint;
+use crate::browser::recording::RecordedEvent;
+use crate::browser::routes::{ResolvedRoute, RouteMock};
+use crate::browser::scripts;
+use crate::browser::tabs::{TabInfo, TabSelector};
+
+// ============================================================================
+// Protocol — NDJSON over the server's stdin/stdout
+// ============================================================================
+
+/// Version of the request/response protocol this build speaks. The server
+/// announces its own in the ready line; launch fails if it announces another.
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// Protocol assumed for servers whose ready line carries no version (those
+/// predating the handshake).
+pub const LEGACY_PROTOCOL_VERSION: u32 = 0;
+
+/// Commands every legacy server implements.
+const LEGACY_CAPABILITIES: &[&str] = &[
+    "navigate",
+    "extract",
+    "action",
+    "screenshot",
+    "current_url",
+    "query_count",
+    "query_text",
+    "query_visible",
+    "quit",
+];
+
+/// What the server announced in its ready line.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServerHandshake {
+    pub protocol: u32,
+
+    /// Commands (`cmd` values) the server implements; empty when it did not
+    /// say, in which case every command is attempted
+    pub capabilities: Vec<String>,
+}
+
+impl ServerHandshake {
+    /// Validate the ready line: it must be `ok`, `ready` and carry either
+    /// this build's `PROTOCOL_VERSION` or no version at all. An unversioned
+    /// server speaks `LEGACY_PROTOCOL_VERSION` and, unless it lists its
+    /// capabilities, is limited to the legacy commands.
+    pub fn from_ready(response: &BrowserResponse) -> Result<Self, AgentError> {
+        if !response.ok || response.ready != Some(true) {
+            return Err(AgentError::SessionProtocol {
+                command: "launch".into(),
+                error: "Did not receive ready signal from browser_server.js".into(),
+            });
+        }
+        match response.protocol {
+            Some(PROTOCOL_VERSION) => Ok(ServerHandshake {
+                protocol: PROTOCOL_VERSION,
+                capabilities: response.capabilities.clone().unwrap_or_default(),
+            }),
+            Some(found) => Err(AgentError::ProtocolMismatch {
+                expected: PROTOCOL_VERSION,
+                found,
+            }),
+            None => Ok(ServerHandshake {
+                protocol: LEGACY_PROTOCOL_VERSION,
+                capabilities: response
+                    .capabilities
+                    .clone()
+                    .unwrap_or_else(|| LEGACY_CAPABILITIES.iter().map(|c| c.to_string()).collect()),
+            }),
+        }
+    }
+
+    /// Whether the server implements `command` (a `cmd` value).
+    pub fn supports(&self, command: &str) -> bool {
+        self.capabilities.is_empty() || self.capabilities.iter().any(|c| c == command)
+    }
+}
+
+/// Machine-readable reason attached to a failed response.
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    /// The server does not implement the command (or action)
+    UnsupportedCommand,
+    /// The request was malformed (missing or invalid fields)
+    InvalidRequest,
+    /// Any code this build does not know yet
+    #[serde(other)]
+    Other,
+}
 
 /// Request sent to browser_server.js over stdin (one JSON line).
+///
+/// Each line also carries an `id` that the server echoes in its response.
 #[derive(Debug, Serialize)]
 #[serde(untagged)]
 pub enum BrowserRequest {
@@ -27,11 +123,31 @@ pub enum BrowserRequest {
         value: Option<String>,
         #[serde(skip_serializing_if = "Option::is_none")]
         duration_ms: Option<u64>,
+        #[serde(flatten)]
+        args: ActionArgs,
     },
     Screenshot {
         cmd: &'static str,
         path: String,
     },
+    TraceStart {
+        cmd: &'static str,
+    },
+    TraceStop {
+        cmd: &'static str,
+        /// Where to save the trace zip (discarded when absent)
+        #[serde(skip_serializing_if = "Option::is_none")]
+        path: Option<String>,
+    },
+    VideoStart {
+        cmd: &'static str,
+    },
+    VideoStop {
+        cmd: &'static str,
+        /// Where to save the video (discarded when absent)
+        #[serde(skip_serializing_if = "Option::is_none")]
+        path: Option<String>,
+    },
     CurrentUrl {
         cmd: &'static str,
     },
@@ -47,12 +163,145 @@ pub enum BrowserRequest {
         cmd: &'static str,
         selector: String,
     },
+    LoadStorageState {
+        cmd: &'static str,
+        path: String,
+    },
+    SaveStorageState {
+        cmd: &'static str,
+        path: String,
+    },
+    Configure {
+        cmd: &'static str,
+        options: SessionOptions,
+    },
+    Network {
+        cmd: &'static str,
+    },
+    Console {
+        cmd: &'static str,
+    },
+    DialogPolicy {
+        cmd: &'static str,
+        #[serde(flatten)]
+        policy: DialogPolicy,
+    },
+    Dialogs {
+        cmd: &'static str,
+    },
+    Tabs {
+        cmd: &'static str,
+    },
+    SwitchTab {
+        cmd: &'static str,
+        #[serde(flatten)]
+        tab: TabSelector,
+    },
+    CloseTab {
+        cmd: &'static str,
+        #[serde(flatten)]
+        tab: TabSelector,
+    },
+    WaitForPopup {
+        cmd: &'static str,
+        timeout_ms: u64,
+    },
+    Route {
+        cmd: &'static str,
+        #[serde(flatten)]
+        route: ResolvedRoute,
+    },
+    Unroute {
+        cmd: &'static str,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        url: Option<String>,
+    },
+    RecordStart {
+        cmd: &'static str,
+    },
+    RecordEvents {
+        cmd: &'static str,
+    },
+    Subscribe {
+        cmd: &'static str,
+        events: Vec<EventKind>,
+    },
+    Cancel {
+        cmd: &'static str,
+        /// ID of the request to abort
+        target: u64,
+    },
     Quit {
         cmd: &'static str,
     },
 }
 
+/// Parameters of the interaction primitives beyond fill, click and select.
+/// Unset fields are omitted from the request.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ActionArgs {
+    /// Key or chord for `press`, in Playwright notation ("Enter", "Control+A")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+
+    /// Pause between keystrokes for `type`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delay_ms: Option<u64>,
+
+    /// Drop target for `drag`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target: Option<Box<SelectorHint>>,
+
+    /// Horizontal scroll distance in pixels for `scroll_by`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delta_x: Option<i64>,
+
+    /// Vertical scroll distance in pixels for `scroll_by`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delta_y: Option<i64>,
+
+    /// Absolute paths for `set_input_files`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub files: Option<Vec<String>>,
+}
+
 impl BrowserRequest {
+    /// The `cmd` value this request is sent with.
+    pub fn command(&self) -> &'static str {
+        match self {
+            BrowserRequest::Navigate { cmd, .. }
+            | BrowserRequest::Extract { cmd }
+            | BrowserRequest::Action { cmd, .. }
+            | BrowserRequest::Screenshot { cmd, .. }
+            | BrowserRequest::TraceStart { cmd }
+            | BrowserRequest::TraceStop { cmd, .. }
+            | BrowserRequest::VideoStart { cmd }
+            | BrowserRequest::VideoStop { cmd, .. }
+            | BrowserRequest::CurrentUrl { cmd }
+            | BrowserRequest::QueryText { cmd, .. }
+            | BrowserRequest::QueryVisible { cmd, .. }
+            | BrowserRequest::QueryCount { cmd, .. }
+            | BrowserRequest::LoadStorageState { cmd, .. }
+            | BrowserRequest::SaveStorageState { cmd, .. }
+            | BrowserRequest::Configure { cmd, .. }
+            | BrowserRequest::Network { cmd }
+            | BrowserRequest::Console { cmd }
+            | BrowserRequest::DialogPolicy { cmd, .. }
+            | BrowserRequest::Dialogs { cmd }
+            | BrowserRequest::Tabs { cmd }
+            | BrowserRequest::SwitchTab { cmd, .. }
+            | BrowserRequest::CloseTab { cmd, .. }
+            | BrowserRequest::WaitForPopup { cmd, .. }
+            | BrowserRequest::Route { cmd, .. }
+            | BrowserRequest::Unroute { cmd, .. }
+            | BrowserRequest::RecordStart { cmd }
+            | BrowserRequest::RecordEvents { cmd }
+            | BrowserRequest::Subscribe { cmd, .. }
+            | BrowserRequest::Cancel { cmd, .. }
+            | BrowserRequest::Quit { cmd } => cmd,
+        }
+    }
+
     pub fn navigate(url: &str) -> Self {
         BrowserRequest::Navigate {
             cmd: "navigate",
@@ -71,6 +320,7 @@ impl BrowserRequest {
             selector: Some(selector.clone()),
             value: Some(value.to_string()),
             duration_ms: None,
+            args: ActionArgs::default(),
         }
     }
 
@@ -81,6 +331,7 @@ impl BrowserRequest {
             selector: Some(selector.clone()),
             value: None,
             duration_ms: None,
+            args: ActionArgs::default(),
         }
     }
 
@@ -91,6 +342,7 @@ impl BrowserRequest {
             selector: None,
             value: None,
             duration_ms: Some(duration_ms),
+            args: ActionArgs::default(),
         }
     }
 
@@ -101,6 +353,28 @@ impl BrowserRequest {
         }
     }
 
+    pub fn trace_start() -> Self {
+        BrowserRequest::TraceStart { cmd: "trace_start" }
+    }
+
+    pub fn trace_stop(path: Option<&str>) -> Self {
+        BrowserRequest::TraceStop {
+            cmd: "trace_stop",
+            path: path.map(String::from),
+        }
+    }
+
+    pub fn video_start() -> Self {
+        BrowserRequest::VideoStart { cmd: "video_start" }
+    }
+
+    pub fn video_stop(path: Option<&str>) -> Self {
+        BrowserRequest::VideoStop {
+            cmd: "video_stop",
+            path: path.map(String::from),
+        }
+    }
+
     pub fn current_url() -> Self {
         BrowserRequest::CurrentUrl { cmd: "current_url" }
     }
@@ -133,6 +407,7 @@ impl BrowserRequest {
             selector: Some(selector.clone()),
             value: Some(value.to_string()),
             duration_ms: None,
+            args: ActionArgs::default(),
         }
     }
 
@@ -143,6 +418,7 @@ impl BrowserRequest {
             selector: Some(selector.clone()),
             value: None,
             duration_ms: None,
+            args: ActionArgs::default(),
         }
     }
 
@@ -153,6 +429,170 @@ impl BrowserRequest {
             selector: Some(selector.clone()),
             value: None,
             duration_ms: None,
+            args: ActionArgs::default(),
+        }
+    }
+
+    pub fn hover(selector: &SelectorHint) -> Self {
+        Self::interaction("hover", Some(selector), None, ActionArgs::default())
+    }
+
+    pub fn press(selector: Option<&SelectorHint>, key: &str) -> Self {
+        let args = ActionArgs {
+            key: Some(key.to_string()),
+            ..ActionArgs::default()
+        };
+        Self::interaction("press", selector, None, args)
+    }
+
+    pub fn type_text(selector: &SelectorHint, text: &str, delay_ms: u64) -> Self {
+        let args = ActionArgs {
+            delay_ms: Some(delay_ms),
+            ..ActionArgs::default()
+        };
+        Self::interaction("type", Some(selector), Some(text), args)
+    }
+
+    pub fn scroll_into_view(selector: &SelectorHint) -> Self {
+        Self::interaction("scroll_into_view", Some(selector), None, ActionArgs::default())
+    }
+
+    pub fn scroll_by(delta_x: i64, delta_y: i64) -> Self {
+        let args = ActionArgs {
+            delta_x: Some(delta_x),
+            delta_y: Some(delta_y),
+            ..ActionArgs::default()
+        };
+        Self::interaction("scroll_by", None, None, args)
+    }
+
+    pub fn drag(source: &SelectorHint, target: &SelectorHint) -> Self {
+        let args = ActionArgs {
+            target: Some(Box::new(target.clone())),
+            ..ActionArgs::default()
+        };
+        Self::interaction("drag", Some(source), None, args)
+    }
+
+    pub fn set_input_files(selector: &SelectorHint, files: &[String]) -> Self {
+        let args = ActionArgs {
+            files: Some(files.to_vec()),
+            ..ActionArgs::default()
+        };
+        Self::interaction("set_input_files", Some(selector), None, args)
+    }
+
+    fn interaction(
+        action: &str,
+        selector: Option<&SelectorHint>,
+        value: Option<&str>,
+        args: ActionArgs,
+    ) -> Self {
+        BrowserRequest::Action {
+            cmd: "action",
+            action: action.into(),
+            selector: selector.cloned(),
+            value: value.map(String::from),
+            duration_ms: None,
+            args,
+        }
+    }
+
+    pub fn load_storage_state(path: &str) -> Self {
+        BrowserRequest::LoadStorageState {
+            cmd: "load_storage_state",
+            path: path.to_string(),
+        }
+    }
+
+    pub fn save_storage_state(path: &str) -> Self {
+        BrowserRequest::SaveStorageState {
+            cmd: "save_storage_state",
+            path: path.to_string(),
+        }
+    }
+
+    pub fn network() -> Self {
+        BrowserRequest::Network { cmd: "network" }
+    }
+
+    pub fn console() -> Self {
+        BrowserRequest::Console { cmd: "console" }
+    }
+
+    pub fn dialog_policy(policy: &DialogPolicy) -> Self {
+        BrowserRequest::DialogPolicy {
+            cmd: "dialog_policy",
+            policy: policy.clone(),
+        }
+    }
+
+    pub fn dialogs() -> Self {
+        BrowserRequest::Dialogs { cmd: "dialogs" }
+    }
+
+    pub fn tabs() -> Self {
+        BrowserRequest::Tabs { cmd: "tabs" }
+    }
+
+    pub fn switch_tab(tab: &TabSelector) -> Self {
+        BrowserRequest::SwitchTab {
+            cmd: "switch_tab",
+            tab: tab.clone(),
+        }
+    }
+
+    pub fn close_tab(tab: &TabSelector) -> Self {
+        BrowserRequest::CloseTab {
+            cmd: "close_tab",
+            tab: tab.clone(),
+        }
+    }
+
+    pub fn wait_for_popup(timeout_ms: u64) -> Self {
+        BrowserRequest::WaitForPopup {
+            cmd: "wait_for_popup",
+            timeout_ms,
+        }
+    }
+
+    pub fn route(route: &ResolvedRoute) -> Self {
+        BrowserRequest::Route {
+            cmd: "route",
+            route: route.clone(),
+        }
+    }
+
+    pub fn unroute(url: Option<&str>) -> Self {
+        BrowserRequest::Unroute {
+            cmd: "unroute",
+            url: url.map(String::from),
+        }
+    }
+
+    pub fn record_start() -> Self {
+        BrowserRequest::RecordStart { cmd: "record_start" }
+    }
+
+    pub fn record_events() -> Self {
+        BrowserRequest::RecordEvents { cmd: "record_events" }
+    }
+
+    pub fn subscribe(events: &[EventKind]) -> Self {
+        BrowserRequest::Subscribe {
+            cmd: "subscribe",
+            events: events.to_vec(),
+        }
+    }
+
+    pub fn cancel(target: u64) -> Self {
+        BrowserRequest::Cancel { cmd: "cancel", target }
+    }
+
+    pub fn configure(options: &SessionOptions) -> Self {
+        BrowserRequest::Configure {
+            cmd: "configure",
+            options: options.clone(),
         }
     }
 
@@ -164,15 +604,27 @@ impl BrowserRequest {
 /// Response received from browser_server.js over stdout (one JSON line).
 #[derive(Debug, Deserialize)]
 pub struct BrowserResponse {
+    /// ID of the request this answers (absent in the ready line)
+    #[serde(default)]
+    pub id: Option<u64>,
     pub ok: bool,
     #[serde(default)]
     pub error: Option<String>,
+    /// Why the request failed, when the server can say
+    #[serde(default)]
+    pub code: Option<ErrorCode>,
     #[serde(default)]
     pub data: Option<Value>,
     #[serde(default)]
     pub url: Option<String>,
     #[serde(default)]
     pub ready: Option<bool>,
+    /// Protocol version (ready line only)
+    #[serde(default)]
+    pub protocol: Option<u32>,
+    /// Supported commands (ready line only)
+    #[serde(default)]
+    pub capabilities: Option<Vec<String>>,
     #[serde(default)]
     pub text: Option<String>,
     #[serde(default)]
@@ -181,114 +633,357 @@ pub struct BrowserResponse {
     pub count: Option<u32>,
 }
 
+// ============================================================================
+// Session configuration
+// ============================================================================
+
+/// Timeouts, crash-recovery and server-location settings for `BrowserSession`.
+///
+/// Loaded from the `session:` section of `screen-detection.yaml`; every field
+/// has a default so the section may be omitted or partially specified.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionConfig {
+    /// Max time to wait for the ready signal after spawning (default: 30000)
+    #[serde(default = "default_launch_timeout_ms")]
+    pub launch_timeout_ms: u64,
+
+    /// Max time to wait for a response to any command (default: 30000)
+    #[serde(default = "default_command_timeout_ms")]
+    pub command_timeout_ms: u64,
+
+    /// Max time to wait for a `navigate` response (default: 60000)
+    #[serde(default = "default_navigate_timeout_ms")]
+    pub navigate_timeout_ms: u64,
+
+    /// How many times a crashed or wedged server is respawned (default: 3)
+    #[serde(default = "default_max_restarts")]
+    pub max_restarts: u32,
+
+    /// Storage-state file (cookies + localStorage) loaded after a respawn
+    #[serde(default)]
+    pub restore_storage_state: Option<String>,
+
+    /// Node.js executable (default: `SCREEN_DETECTION_NODE`, else "node")
+    #[serde(default = "default_node_path")]
+    pub node_path: String,
+
+    /// Directory holding the Node helper scripts (default:
+    /// `SCREEN_DETECTION_SCRIPTS_DIR`, else the scripts embedded in the binary)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scripts_dir: Option<String>,
+
+    /// Server script: a file name looked up as described in
+    /// `scripts::resolve_script`, or a path used as given
+    #[serde(default = "default_server_script")]
+    pub server_script: String,
+
+    /// Browser context settings applied at launch (viewport, locale, ...)
+    #[serde(default)]
+    pub options: SessionOptions,
+}
+
+impl Default for SessionConfig {
+    fn default() -> Self {
+        Self {
+            launch_timeout_ms: default_launch_timeout_ms(),
+            command_timeout_ms: default_command_timeout_ms(),
+            navigate_timeout_ms: default_navigate_timeout_ms(),
+            max_restarts: default_max_restarts(),
+            restore_storage_state: None,
+            node_path: default_node_path(),
+            scripts_dir: None,
+            server_script: default_server_script(),
+            options: SessionOptions::default(),
+        }
+    }
+}
+
+fn default_launch_timeout_ms() -> u64 { 30_000 }
+fn default_command_timeout_ms() -> u64 { 30_000 }
+fn default_navigate_timeout_ms() -> u64 { 60_000 }
+fn default_max_restarts() -> u32 { 3 }
+fn default_node_path() -> String { scripts::default_node_path() }
+fn default_server_script() -> String { scripts::SERVER_SCRIPT.to_string() }
+
+// ============================================================================
+// BrowserSession
+// ============================================================================
+
 /// A persistent browser session backed by browser_server.js.
 ///
 /// Launches a long-lived Node.js process that keeps a Chromium browser open.
-/// Commands are sent as NDJSON over stdin, responses read from stdout.
+/// Commands are sent as NDJSON over stdin, responses read from stdout. The
+/// server's ready line must announce `PROTOCOL_VERSION`; commands missing
+/// from its capabilities fail with `UnsupportedCommand`.
+///
+/// A blocking wrapper over `PipelinedSession`: each method sends one request
+/// and waits for its answer. Use `pipeline` to have several requests in
+/// flight at once, and `subscribe` / `take_events` for events the server
+/// pushes on its own.
+///
+/// Every command is bounded by a timeout. A server that times out is killed;
+/// a dead server is respawned on the next command (up to `max_restarts`
+/// times), reloading `restore_storage_state` and the last known URL.
 pub struct BrowserSession {
-    child: Child,
-    stdin: std::process::ChildStdin,
-    reader: BufReader<std::process::ChildStdout>,
+    process: Option<PipelinedSession>,
+    config: SessionConfig,
+    /// Effective context options (launch options plus any active overrides)
+    options: SessionOptions,
     current_url: Option<String>,
+    /// Installed route mocks, reinstalled after a respawn or context change
+    routes: Vec<ResolvedRoute>,
+    /// Whether user interactions are being recorded (resumed after a respawn)
+    recording: bool,
+    /// How JavaScript dialogs are answered (re-sent after a respawn)
+    dialog_policy: DialogPolicy,
+    /// Subscribed event kinds (re-sent after a respawn)
+    subscriptions: Vec<EventKind>,
+    /// Whether a trace is being recorded (restarted after a respawn)
+    tracing: bool,
+    /// Whether a video is being recorded (restarted after a respawn)
+    video: bool,
+    restarts: u32,
 }
 
 impl BrowserSession {
     /// Launch a new browser session by spawning browser_server.js.
     pub fn launch() -> Result<Self, AgentError> {
-        let mut child = Command::new("node")
-            .arg("../../node/dom-extraction/browser_server.js")
-            .stdin(Stdio::piped())
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()
-            .map_err(|e| AgentError::SubprocessSpawn {
-                script: "browser_server.js".into(),
-                source: e,
-            })?;
-
-        let stdin = child.stdin.take().ok_or_else(|| {
-            AgentError::SessionIO("Failed to capture stdin of browser_server.js".into())
-        })?;
-
-        let stdout = child.stdout.take().ok_or_else(|| {
-            AgentError::SessionIO("Failed to capture stdout of browser_server.js".into())
-        })?;
+        Self::launch_with(SessionConfig::default())
+    }
 
-        let mut reader = BufReader::new(stdout);
+    /// Launch a new browser session with explicit timeouts and restart policy.
+    pub fn launch_with(config: SessionConfig) -> Result<Self, AgentError> {
+        let process = PipelinedSession::launch(&config)?;
+        Ok(BrowserSession {
+            process: Some(process),
+            options: config.options.clone(),
+            config,
+            current_url: None,
+            routes: Vec::new(),
+            recording: false,
+            dialog_policy: DialogPolicy::default(),
+            subscriptions: Vec::new(),
+            tracing: false,
+            video: false,
+            restarts: 0,
+        })
+    }
 
-        // Wait for the ready signal
-        let mut line = String::new();
-        reader.read_line(&mut line).map_err(|e| {
-            AgentError::SessionIO(format!("Failed to read ready signal: {}", e))
-        })?;
+    /// The configuration this session was launched with.
+    pub fn config(&self) -> &SessionConfig {
+        &self.config
+    }
 
-        let response: BrowserResponse = serde_json::from_str(line.trim()).map_err(|e| {
-            AgentError::JsonParse {
-                context: "browser_server.js ready signal".into(),
-                source: e,
-            }
-        })?;
+    /// The browser context options currently in effect.
+    pub fn options(&self) -> &SessionOptions {
+        &self.options
+    }
 
-        if !response.ok || response.ready != Some(true) {
-            return Err(AgentError::SessionProtocol {
-                command: "launch".into(),
-                error: "Did not receive ready signal from browser_server.js".into(),
-            });
+    /// Apply context overrides on top of the launch options.
+    ///
+    /// The server recreates its browser context, so the current page is lost;
+    /// installed routes are carried over. Empty overrides restore the launch
+    /// options. No-op when nothing changes.
+    pub fn configure(&mut self, overrides: &SessionOptions) -> Result<(), AgentError> {
+        let options = self.config.options.merged(overrides);
+        if options == self.options {
+            return Ok(());
         }
+        let request = BrowserRequest::configure(&options);
+        self.send_ok(&request, "configure")?;
+        self.options = options;
+        self.current_url = None;
+        for route in self.routes.clone() {
+            self.send_ok(&BrowserRequest::route(&route), "route")?;
+        }
+        Ok(())
+    }
 
-        Ok(BrowserSession {
-            child,
-            stdin,
-            reader,
-            current_url: None,
-        })
+    /// Protocol version and capabilities announced by the running server
+    /// (`None` while it is down, until the next command respawns it).
+    pub fn handshake(&self) -> Option<&ServerHandshake> {
+        self.process.as_ref().map(PipelinedSession::handshake)
     }
 
-    /// Send a request and read the response.
-    fn send(&mut self, request: &BrowserRequest) -> Result<BrowserResponse, AgentError> {
-        let json = serde_json::to_string(request).map_err(|e| AgentError::JsonSerialize {
-            context: "BrowserRequest".into(),
-            source: e,
-        })?;
+    /// The running server, respawned first if it died, for sending
+    /// requests without waiting on each one (e.g. a screenshot while a DOM
+    /// extraction is in flight).
+    ///
+    /// Requests sent this way bypass the session's bookkeeping: a
+    /// `navigate` does not update `last_url`, routes are not reinstalled
+    /// after a respawn, and a timed-out request does not kill the server.
+    pub fn pipeline(&mut self) -> Result<&PipelinedSession, AgentError> {
+        self.ensure_alive(&BrowserRequest::current_url())?;
+        Ok(self.process.as_ref().expect("process is alive after ensure_alive"))
+    }
 
-        writeln!(self.stdin, "{}", json).map_err(|e| {
-            AgentError::SessionIO(format!("Failed to write to browser_server.js stdin: {}", e))
-        })?;
+    /// How many times the server has been respawned after a crash or timeout.
+    pub fn restart_count(&self) -> u32 {
+        self.restarts
+    }
 
-        self.stdin.flush().map_err(|e| {
-            AgentError::SessionIO(format!("Failed to flush browser_server.js stdin: {}", e))
-        })?;
+    /// Whether the server process is currently running.
+    pub fn is_alive(&mut self) -> bool {
+        self.process.as_ref().is_some_and(PipelinedSession::is_alive)
+    }
 
-        let mut line = String::new();
-        self.reader.read_line(&mut line).map_err(|e| {
-            AgentError::SessionIO(format!("Failed to read from browser_server.js stdout: {}", e))
-        })?;
+    fn timeout_for(&self, request: &BrowserRequest) -> Duration {
+        let ms = match request {
+            BrowserRequest::Navigate { .. } => self.config.navigate_timeout_ms,
+            BrowserRequest::Action { duration_ms: Some(wait), .. } => {
+                self.config.command_timeout_ms + wait
+            }
+            BrowserRequest::WaitForPopup { timeout_ms, .. } => {
+                self.config.command_timeout_ms + timeout_ms
+            }
+            BrowserRequest::Action {
+                value: Some(text),
+                args: ActionArgs { delay_ms: Some(delay), .. },
+                ..
+            } => self.config.command_timeout_ms + delay * text.chars().count() as u64,
+            _ => self.config.command_timeout_ms,
+        };
+        Duration::from_millis(ms)
+    }
 
-        if line.trim().is_empty() {
-            return Err(AgentError::SessionIO(
-                "Empty response from browser_server.js (process may have died)".into(),
-            ));
+    /// Respawn the server if it died, then restore storage state and URL.
+    ///
+    /// The URL is not restored when the pending command is itself a navigate.
+    fn ensure_alive(&mut self, request: &BrowserRequest) -> Result<(), AgentError> {
+        if self.process.is_some() {
+            return Ok(());
         }
+        if self.restarts >= self.config.max_restarts {
+            return Err(AgentError::SessionIO(format!(
+                "browser_server.js is not running and the restart limit ({}) was reached",
+                self.config.max_restarts
+            )));
+        }
+        self.restarts += 1;
+        let process = PipelinedSession::launch(&self.config)?;
 
-        let response: BrowserResponse =
-            serde_json::from_str(line.trim()).map_err(|e| AgentError::JsonParse {
-                context: "browser_server.js response".into(),
-                source: e,
-            })?;
+        if let Err(e) = self.restore(&process, request) {
+            process.kill();
+            return Err(e);
+        }
+        self.process = Some(process);
+        Ok(())
+    }
 
-        Ok(response)
+    /// Reload context options, storage state, routes, recording, the dialog
+    /// policy, event subscriptions, trace and video recording and the last
+    /// known URL into a fresh server.
+    fn restore(&self, process: &PipelinedSession, request: &BrowserRequest) -> Result<(), AgentError> {
+        let command_timeout = Duration::from_millis(self.config.command_timeout_ms);
+        if self.options != self.config.options && !matches!(request, BrowserRequest::Configure { .. }) {
+            let request = BrowserRequest::configure(&self.options);
+            let response = process.call(&request, "configure", command_timeout)?;
+            check_ok(response, "configure")?;
+        }
+        if let Some(path) = &self.config.restore_storage_state
+            && std::path::Path::new(path).exists()
+        {
+            let request = BrowserRequest::load_storage_state(path);
+            let response = process.call(&request, "load_storage_state", command_timeout)?;
+            check_ok(response, "load_storage_state")?;
+        }
+        for route in &self.routes {
+            let response = process.call(&BrowserRequest::route(route), "route", command_timeout)?;
+            check_ok(response, "route")?;
+        }
+        if self.recording {
+            let response = process.call(&BrowserRequest::record_start(), "record_start", command_timeout)?;
+            check_ok(response, "record_start")?;
+        }
+        if self.dialog_policy != DialogPolicy::default() {
+            let request = BrowserRequest::dialog_policy(&self.dialog_policy);
+            let response = process.call(&request, "dialog_policy", command_timeout)?;
+            check_ok(response, "dialog_policy")?;
+        }
+        if !self.subscriptions.is_empty() {
+            let request = BrowserRequest::subscribe(&self.subscriptions);
+            let response = process.call(&request, "subscribe", command_timeout)?;
+            check_ok(response, "subscribe")?;
+        }
+        if self.tracing {
+            let response = process.call(&BrowserRequest::trace_start(), "trace_start", command_timeout)?;
+            check_ok(response, "trace_start")?;
+        }
+        if self.video {
+            let response = process.call(&BrowserRequest::video_start(), "video_start", command_timeout)?;
+            check_ok(response, "video_start")?;
+        }
+
+        if !matches!(request, BrowserRequest::Navigate { .. } | BrowserRequest::Configure { .. })
+            && let Some(url) = &self.current_url
+        {
+            let timeout = Duration::from_millis(self.config.navigate_timeout_ms);
+            let response = process.call(&BrowserRequest::navigate(url), "navigate", timeout)?;
+            check_ok(response, "navigate")?;
+        }
+        Ok(())
+    }
+
+    /// Send a request and read the response.
+    ///
+    /// A timeout or broken pipe kills the server so the next call respawns it.
+    fn send(&mut self, request: &BrowserRequest, command_name: &str) -> Result<BrowserResponse, AgentError> {
+        self.ensure_alive(request)?;
+        let timeout = self.timeout_for(request);
+        let process = self.process.as_ref().expect("process is alive after ensure_alive");
+        let result = process.call(request, command_name, timeout);
+        if let Err(e) = &result
+            && !matches!(
+                e,
+                AgentError::JsonParse { .. } | AgentError::JsonSerialize { .. } | AgentError::UnsupportedCommand { .. }
+            )
+            && let Some(dead) = self.process.take()
+        {
+            dead.kill();
+        }
+        result
     }
 
     /// Send a request and verify it succeeded.
     fn send_ok(&mut self, request: &BrowserRequest, command_name: &str) -> Result<BrowserResponse, AgentError> {
-        let response = self.send(request)?;
-        if !response.ok {
-            return Err(AgentError::SessionProtocol {
-                command: command_name.into(),
-                error: response.error.unwrap_or_else(|| "Unknown error".into()),
-            });
+        let response = self.send(request, command_name)?;
+        if !response.ok
+            && let Some(not_found) = self.diagnose_missing_element(request, command_name, response.error.as_deref())
+        {
+            return Err(not_found);
         }
-        Ok(response)
+        check_ok(response, command_name)
+    }
+
+    /// After a failed element action, check the page for the element: when
+    /// its selector (or a drag target) matches nothing, report
+    /// `ElementNotFound` with the closest elements (`ElementDisabled` when it
+    /// is only disabled), and `AmbiguousElement` when a form-less lookup hit
+    /// several (see `resolve_hint`), instead of Playwright's error. `None`
+    /// when the element is there, so the action failed for another reason,
+    /// or the page cannot be read.
+    fn diagnose_missing_element(
+        &mut self,
+        request: &BrowserRequest,
+        command_name: &str,
+        error: Option<&str>,
+    ) -> Option<AgentError> {
+        let BrowserRequest::Action { selector: Some(selector), args, .. } = request else {
+            return None;
+        };
+        let data = self.extract().ok()?;
+        let dom = data["dom"].as_array()?;
+        let context = || {
+            format!(
+                "{} on '{}' failed: {}",
+                command_name,
+                data["url"].as_str().unwrap_or(""),
+                error.unwrap_or("Unknown error")
+            )
+        };
+        std::iter::once(selector)
+            .chain(args.target.as_deref())
+            .find_map(|s| resolve_hint(dom, s, context).err())
     }
 
     /// Navigate to a URL.
@@ -337,6 +1032,36 @@ impl BrowserSession {
         Ok(())
     }
 
+    /// Start a Playwright trace with screenshots and DOM snapshots. A
+    /// respawned server starts a new trace; what the old one captured is lost.
+    pub fn start_trace(&mut self) -> Result<(), AgentError> {
+        self.send_ok(&BrowserRequest::trace_start(), "trace_start")?;
+        self.tracing = true;
+        Ok(())
+    }
+
+    /// Stop the trace and save it as a zip at `path`, or discard it when `None`.
+    pub fn stop_trace(&mut self, path: Option<&str>) -> Result<(), AgentError> {
+        self.tracing = false;
+        self.send_ok(&BrowserRequest::trace_stop(path), "trace_stop")?;
+        Ok(())
+    }
+
+    /// Start recording a video of the active page. A respawned server
+    /// starts a new video; what the old one captured is lost.
+    pub fn start_video(&mut self) -> Result<(), AgentError> {
+        self.send_ok(&BrowserRequest::video_start(), "video_start")?;
+        self.video = true;
+        Ok(())
+    }
+
+    /// Stop the video and save it at `path`, or discard it when `None`.
+    pub fn stop_video(&mut self, path: Option<&str>) -> Result<(), AgentError> {
+        self.video = false;
+        self.send_ok(&BrowserRequest::video_stop(path), "video_stop")?;
+        Ok(())
+    }
+
     /// Get the current URL from the browser.
     pub fn current_url(&mut self) -> Result<String, AgentError> {
         let request = BrowserRequest::current_url();
@@ -392,24 +1117,445 @@ impl BrowserSession {
         Ok(())
     }
 
+    /// Move the mouse over an element (opens hover menus and tooltips).
+    pub fn hover(&mut self, selector: &SelectorHint) -> Result<(), AgentError> {
+        let request = BrowserRequest::hover(selector);
+        self.send_ok(&request, "hover")?;
+        Ok(())
+    }
+
+    /// Press a key or chord ("Enter", "Control+A") on an element, or on
+    /// whatever has focus when `selector` is `None`.
+    pub fn press(&mut self, selector: Option<&SelectorHint>, key: &str) -> Result<(), AgentError> {
+        let request = BrowserRequest::press(selector, key);
+        self.send_ok(&request, "press")?;
+        Ok(())
+    }
+
+    /// Type text one key at a time, `delay_ms` apart, firing key events
+    /// for each character (autocomplete and masked inputs).
+    pub fn type_text(&mut self, selector: &SelectorHint, text: &str, delay_ms: u64) -> Result<(), AgentError> {
+        let request = BrowserRequest::type_text(selector, text, delay_ms);
+        self.send_ok(&request, "type")?;
+        Ok(())
+    }
+
+    /// Scroll an element into the viewport.
+    pub fn scroll_into_view(&mut self, selector: &SelectorHint) -> Result<(), AgentError> {
+        let request = BrowserRequest::scroll_into_view(selector);
+        self.send_ok(&request, "scroll_into_view")?;
+        Ok(())
+    }
+
+    /// Scroll the page by a pixel offset (mouse wheel).
+    pub fn scroll_by(&mut self, delta_x: i64, delta_y: i64) -> Result<(), AgentError> {
+        let request = BrowserRequest::scroll_by(delta_x, delta_y);
+        self.send_ok(&request, "scroll_by")?;
+        Ok(())
+    }
+
+    /// Drag one element and drop it onto another.
+    pub fn drag_to(&mut self, source: &SelectorHint, target: &SelectorHint) -> Result<(), AgentError> {
+        let request = BrowserRequest::drag(source, target);
+        self.send_ok(&request, "drag")?;
+        Ok(())
+    }
+
+    /// Set the files of an `<input type=file>` (an empty list clears it).
+    ///
+    /// Paths are resolved against the current directory before sending,
+    /// since the server runs with its own working directory.
+    pub fn set_input_files(&mut self, selector: &SelectorHint, files: &[String]) -> Result<(), AgentError> {
+        let files = files
+            .iter()
+            .map(|f| absolute_upload_path(f))
+            .collect::<Result<Vec<_>, _>>()?;
+        let request = BrowserRequest::set_input_files(selector, &files);
+        self.send_ok(&request, "set_input_files")?;
+        Ok(())
+    }
+
+    /// Answer requests matching `mock` with its canned response instead of
+    /// the network. Replaces an earlier route for the same URL and method.
+    pub fn route(&mut self, mock: &RouteMock) -> Result<(), AgentError> {
+        let route = mock.resolve()?;
+        let request = BrowserRequest::route(&route);
+        self.send_ok(&request, "route")?;
+        self.routes
+            .retain(|r| (r.url.as_str(), &r.method) != (route.url.as_str(), &route.method));
+        self.routes.push(route);
+        Ok(())
+    }
+
+    /// Remove the routes for the URL glob `url`, or every route when `None`.
+    pub fn unroute(&mut self, url: Option<&str>) -> Result<(), AgentError> {
+        let request = BrowserRequest::unroute(url);
+        self.send_ok(&request, "unroute")?;
+        self.routes.retain(|r| url.is_some_and(|u| u != r.url));
+        Ok(())
+    }
+
+    /// Save cookies and localStorage of the browser context to a JSON file.
+    pub fn save_storage_state(&mut self, path: &str) -> Result<(), AgentError> {
+        let request = BrowserRequest::save_storage_state(path);
+        self.send_ok(&request, "save_storage_state")?;
+        Ok(())
+    }
+
+    /// Load cookies and localStorage previously saved with `save_storage_state`.
+    pub fn load_storage_state(&mut self, path: &str) -> Result<(), AgentError> {
+        let request = BrowserRequest::load_storage_state(path);
+        self.send_ok(&request, "load_storage_state")?;
+        Ok(())
+    }
+
+    /// Return and clear the requests recorded since the last call.
+    pub fn take_network_log(&mut self) -> Result<Vec<NetworkEntry>, AgentError> {
+        let request = BrowserRequest::network();
+        let response = self.send_ok(&request, "network")?;
+        let data = response.data.unwrap_or(Value::Array(Vec::new()));
+        serde_json::from_value(data).map_err(|e| AgentError::JsonParse {
+            context: "browser_server.js network log".into(),
+            source: e,
+        })
+    }
+
+    /// Return and clear the console messages and uncaught page errors
+    /// buffered since the last call.
+    pub fn take_console_log(&mut self) -> Result<Vec<ConsoleEntry>, AgentError> {
+        let request = BrowserRequest::console();
+        let response = self.send_ok(&request, "console")?;
+        let data = response.data.unwrap_or(Value::Array(Vec::new()));
+        serde_json::from_value(data).map_err(|e| AgentError::JsonParse {
+            context: "browser_server.js console log".into(),
+            source: e,
+        })
+    }
+
+    /// Answer JavaScript dialogs in every tab according to `policy` (they
+    /// are dismissed until this is called). Each dialog's message and answer
+    /// is buffered for `take_dialogs`.
+    pub fn set_dialog_policy(&mut self, policy: &DialogPolicy) -> Result<(), AgentError> {
+        let request = BrowserRequest::dialog_policy(policy);
+        self.send_ok(&request, "dialog_policy")?;
+        self.dialog_policy = policy.clone();
+        Ok(())
+    }
+
+    /// The policy JavaScript dialogs are currently answered with.
+    pub fn dialog_policy(&self) -> &DialogPolicy {
+        &self.dialog_policy
+    }
+
+    /// Return and clear the dialogs opened since the last call.
+    pub fn take_dialogs(&mut self) -> Result<Vec<DialogEntry>, AgentError> {
+        let request = BrowserRequest::dialogs();
+        let response = self.send_ok(&request, "dialogs")?;
+        let data = response.data.unwrap_or(Value::Array(Vec::new()));
+        serde_json::from_value(data).map_err(|e| AgentError::JsonParse {
+            context: "browser_server.js dialogs".into(),
+            source: e,
+        })
+    }
+
+    /// Have the server push `events` as they happen (replacing earlier
+    /// subscriptions; empty unsubscribes). Read them with `take_events`.
+    pub fn subscribe(&mut self, events: &[EventKind]) -> Result<(), AgentError> {
+        let request = BrowserRequest::subscribe(events);
+        self.send_ok(&request, "subscribe")?;
+        self.subscriptions = events.to_vec();
+        Ok(())
+    }
+
+    /// Return and clear the events pushed since the last call. Events not
+    /// yet taken are lost if the server dies.
+    pub fn take_events(&mut self) -> Vec<ServerEvent> {
+        self.process.as_ref().map(PipelinedSession::take_events).unwrap_or_default()
+    }
+
+    /// Start reporting the user's clicks, fills, selects and navigations in
+    /// every tab. Events buffered by the server are read with
+    /// `take_recorded_events`; events not yet read are lost if the server dies.
+    pub fn start_recording(&mut self) -> Result<(), AgentError> {
+        let request = BrowserRequest::record_start();
+        self.send_ok(&request, "record_start")?;
+        self.recording = true;
+        Ok(())
+    }
+
+    /// Return and clear the interactions recorded since the last call.
+    pub fn take_recorded_events(&mut self) -> Result<Vec<RecordedEvent>, AgentError> {
+        let request = BrowserRequest::record_events();
+        let response = self.send_ok(&request, "record_events")?;
+        let data = response.data.unwrap_or(Value::Array(Vec::new()));
+        serde_json::from_value(data).map_err(|e| AgentError::JsonParse {
+            context: "browser_server.js recorded events".into(),
+            source: e,
+        })
+    }
+
+    /// List the open tabs in opening order.
+    pub fn tabs(&mut self) -> Result<Vec<TabInfo>, AgentError> {
+        let request = BrowserRequest::tabs();
+        let response = self.send_ok(&request, "tabs")?;
+        let data = response.data.unwrap_or(Value::Array(Vec::new()));
+        serde_json::from_value(data).map_err(|e| AgentError::JsonParse {
+            context: "browser_server.js tab list".into(),
+            source: e,
+        })
+    }
+
+    /// Make the tab matching `tab` the target of all further commands.
+    pub fn switch_tab(&mut self, tab: &TabSelector) -> Result<TabInfo, AgentError> {
+        let request = BrowserRequest::switch_tab(tab);
+        let response = self.send_ok(&request, "switch_tab")?;
+        self.active_tab_from(response, "switch_tab")
+    }
+
+    /// Close the tab matching `tab` (the active tab when empty). If the
+    /// active tab is closed, the server activates the tab that opened it.
+    pub fn close_tab(&mut self, tab: &TabSelector) -> Result<(), AgentError> {
+        let request = BrowserRequest::close_tab(tab);
+        let response = self.send_ok(&request, "close_tab")?;
+        if response.data.is_some() {
+            self.active_tab_from(response, "close_tab")?;
+        }
+        Ok(())
+    }
+
+    /// Wait up to `timeout_ms` for a tab opened since the last tab command
+    /// (returning at once if one already opened) and switch to it.
+    pub fn wait_for_popup(&mut self, timeout_ms: u64) -> Result<TabInfo, AgentError> {
+        let request = BrowserRequest::wait_for_popup(timeout_ms);
+        let response = self.send_ok(&request, "wait_for_popup")?;
+        self.active_tab_from(response, "wait_for_popup")
+    }
+
+    /// Parse the newly active tab from a response and remember its URL.
+    fn active_tab_from(&mut self, response: BrowserResponse, command: &str) -> Result<TabInfo, AgentError> {
+        let data = response.data.ok_or_else(|| AgentError::SessionProtocol {
+            command: command.into(),
+            error: "No tab in response".into(),
+        })?;
+        let tab: TabInfo = serde_json::from_value(data).map_err(|e| AgentError::JsonParse {
+            context: format!("browser_server.js {} response", command),
+            source: e,
+        })?;
+        self.current_url = Some(tab.url.clone());
+        Ok(tab)
+    }
+
     /// Get the last known URL (cached, no browser call).
     pub fn last_url(&self) -> Option<&str> {
         self.current_url.as_deref()
     }
 
     /// Quit the browser session.
+    ///
+    /// Never respawns a dead server.
     pub fn quit(&mut self) -> Result<(), AgentError> {
-        let request = BrowserRequest::quit();
         // Best-effort quit — don't fail hard if process is already gone
-        let _ = self.send(&request);
-        let _ = self.child.wait();
+        if let Some(process) = self.process.take() {
+            process.shutdown();
+        }
         Ok(())
     }
 }
 
+/// Turn an `ok: false` response into a `SessionProtocol` error.
+fn check_ok(response: BrowserResponse, command_name: &str) -> Result<BrowserResponse, AgentError> {
+    if !response.ok {
+        let error = response.error.unwrap_or_else(|| "Unknown error".into());
+        return Err(match response.code {
+            Some(ErrorCode::UnsupportedCommand) => AgentError::UnsupportedCommand {
+                command: command_name.into(),
+                error,
+            },
+            _ => AgentError::SessionProtocol {
+                command: command_name.into(),
+                error,
+            },
+        });
+    }
+    Ok(response)
+}
+
+/// Absolute form of a local file to upload; missing files are rejected here
+/// rather than by Playwright, whose error does not name the path.
+fn absolute_upload_path(path: &str) -> Result<String, AgentError> {
+    let path = std::path::Path::new(path);
+    if !path.is_file() {
+        return Err(AgentError::BrowserAction(format!(
+            "Upload file '{}' does not exist",
+            path.display()
+        )));
+    }
+    std::path::absolute(path)
+        .map(|p| p.to_string_lossy().to_string())
+        .map_err(|e| AgentError::BrowserAction(format!("Cannot resolve '{}': {}", path.display(), e)))
+}
+
 impl Drop for BrowserSession {
     fn drop(&mut self) {
         // Best-effort cleanup
         let _ = self.quit();
     }
 }
+
+impl BrowserBackend for BrowserSession {
+    fn configure(&mut self, overrides: &SessionOptions) -> Result<(), AgentError> {
+        BrowserSession::configure(self, overrides)
+    }
+
+    fn navigate(&mut self, url: &str) -> Result<(), AgentError> {
+        BrowserSession::navigate(self, url)
+    }
+
+    fn extract(&mut self) -> Result<Value, AgentError> {
+        BrowserSession::extract(self)
+    }
+
+    fn fill(&mut self, selector: &SelectorHint, value: &str) -> Result<(), AgentError> {
+        BrowserSession::fill(self, selector, value)
+    }
+
+    fn click(&mut self, selector: &SelectorHint) -> Result<(), AgentError> {
+        BrowserSession::click(self, selector)
+    }
+
+    fn select_option(&mut self, selector: &SelectorHint, value: &str) -> Result<(), AgentError> {
+        BrowserSession::select_option(self, selector, value)
+    }
+
+    fn check(&mut self, selector: &SelectorHint) -> Result<(), AgentError> {
+        BrowserSession::check(self, selector)
+    }
+
+    fn uncheck(&mut self, selector: &SelectorHint) -> Result<(), AgentError> {
+        BrowserSession::uncheck(self, selector)
+    }
+
+    fn hover(&mut self, selector: &SelectorHint) -> Result<(), AgentError> {
+        BrowserSession::hover(self, selector)
+    }
+
+    fn press(&mut self, selector: Option<&SelectorHint>, key: &str) -> Result<(), AgentError> {
+        BrowserSession::press(self, selector, key)
+    }
+
+    fn type_text(&mut self, selector: &SelectorHint, text: &str, delay_ms: u64) -> Result<(), AgentError> {
+        BrowserSession::type_text(self, selector, text, delay_ms)
+    }
+
+    fn scroll_into_view(&mut self, selector: &SelectorHint) -> Result<(), AgentError> {
+        BrowserSession::scroll_into_view(self, selector)
+    }
+
+    fn scroll_by(&mut self, delta_x: i64, delta_y: i64) -> Result<(), AgentError> {
+        BrowserSession::scroll_by(self, delta_x, delta_y)
+    }
+
+    fn drag_to(&mut self, source: &SelectorHint, target: &SelectorHint) -> Result<(), AgentError> {
+        BrowserSession::drag_to(self, source, target)
+    }
+
+    fn set_input_files(&mut self, selector: &SelectorHint, files: &[String]) -> Result<(), AgentError> {
+        BrowserSession::set_input_files(self, selector, files)
+    }
+
+    fn wait_idle(&mut self, ms: u64) -> Result<(), AgentError> {
+        BrowserSession::wait_idle(self, ms)
+    }
+
+    fn route(&mut self, mock: &RouteMock) -> Result<(), AgentError> {
+        BrowserSession::route(self, mock)
+    }
+
+    fn unroute(&mut self, url: Option<&str>) -> Result<(), AgentError> {
+        BrowserSession::unroute(self, url)
+    }
+
+    fn screenshot(&mut self, path: &str) -> Result<(), AgentError> {
+        BrowserSession::screenshot(self, path)
+    }
+
+    fn start_trace(&mut self) -> Result<(), AgentError> {
+        BrowserSession::start_trace(self)
+    }
+
+    fn stop_trace(&mut self, path: Option<&str>) -> Result<(), AgentError> {
+        BrowserSession::stop_trace(self, path)
+    }
+
+    fn start_video(&mut self) -> Result<(), AgentError> {
+        BrowserSession::start_video(self)
+    }
+
+    fn stop_video(&mut self, path: Option<&str>) -> Result<(), AgentError> {
+        BrowserSession::stop_video(self, path)
+    }
+
+    fn current_url(&mut self) -> Result<String, AgentError> {
+        BrowserSession::current_url(self)
+    }
+
+    fn query_text(&mut self, selector: &str) -> Result<Option<String>, AgentError> {
+        BrowserSession::query_text(self, selector)
+    }
+
+    fn query_visible(&mut self, selector: &str) -> Result<bool, AgentError> {
+        BrowserSession::query_visible(self, selector)
+    }
+
+    fn query_count(&mut self, selector: &str) -> Result<u32, AgentError> {
+        BrowserSession::query_count(self, selector)
+    }
+
+    fn take_network_log(&mut self) -> Result<Vec<NetworkEntry>, AgentError> {
+        BrowserSession::take_network_log(self)
+    }
+
+    fn take_console_log(&mut self) -> Result<Vec<ConsoleEntry>, AgentError> {
+        BrowserSession::take_console_log(self)
+    }
+
+    fn set_dialog_policy(&mut self, policy: &DialogPolicy) -> Result<(), AgentError> {
+        BrowserSession::set_dialog_policy(self, policy)
+    }
+
+    fn take_dialogs(&mut self) -> Result<Vec<DialogEntry>, AgentError> {
+        BrowserSession::take_dialogs(self)
+    }
+
+    fn tabs(&mut self) -> Result<Vec<TabInfo>, AgentError> {
+        BrowserSession::tabs(self)
+    }
+
+    fn switch_tab(&mut self, tab: &TabSelector) -> Result<TabInfo, AgentError> {
+        BrowserSession::switch_tab(self, tab)
+    }
+
+    fn close_tab(&mut self, tab: &TabSelector) -> Result<(), AgentError> {
+        BrowserSession::close_tab(self, tab)
+    }
+
+    fn wait_for_popup(&mut self, timeout_ms: u64) -> Result<TabInfo, AgentError> {
+        BrowserSession::wait_for_popup(self, timeout_ms)
+    }
+
+    fn start_recording(&mut self) -> Result<(), AgentError> {
+        BrowserSession::start_recording(self)
+    }
+
+    fn take_recorded_events(&mut self) -> Result<Vec<RecordedEvent>, AgentError> {
+        BrowserSession::take_recorded_events(self)
+    }
+
+    fn save_storage_state(&mut self, path: &str) -> Result<(), AgentError> {
+        BrowserSession::save_storage_state(self, path)
+    }
+
+    fn load_storage_state(&mut self, path: &str) -> Result<(), AgentError> {
+        BrowserSession::load_storage_state(self, path)
+    }
+}
@@ -0,0 +1,956 @@
+use std::collections::HashMap;
+
+use scraper::{ElementRef, Html, Selector};
+use serde_json::{Value, json};
+
+use crate::agent::error::AgentError;
+use crate::browser::backend::{BrowserBackend, resolve_hint};
+use crate::browser::console::ConsoleEntry;
+use crate::browser::dialogs::{DialogEntry, DialogPolicy};
+use crate::browser::network::NetworkEntry;
+use crate::browser::options::SessionOptions;
+use crate::browser::playwright::SelectorHint;
+use crate::browser::recording::RecordedEvent;
+use crate::browser::routes::RouteMock;
+use crate::browser::tabs::{TabInfo, TabSelector};
+use crate::browser::url::resolve_url;
+
+// ============================================================================
+// Static (pure-Rust) DOM extraction — no Node.js or Chromium required
+// ============================================================================
+
+/// Elements whose contents are never rendered as page text.
+const NON_RENDERED: &[&str] = &["script", "style", "noscript", "template", "head", "title", "meta", "link"];
+
+/// Elements that are structural landmarks (HTML5 tag or ARIA role).
+const LANDMARK_TAGS: &[&str] = &["header", "nav", "main", "aside", "footer", "section", "article", "form", "dialog"];
+
+/// Parse an HTML document and produce the same `{url, title, dom, structural_outline}`
+/// JSON that `extract.js` / `browser_server.js` return.
+///
+/// Scripts are not executed, so this reflects the server-rendered markup only.
+/// Like `extract.js`, every rendered element in `<body>` that is interactive
+/// or has a non-blank text node of its own is emitted, in document order;
+/// containers whose text all comes from their children are not. Tables and
+/// lists are emitted too, for their `table` field. The rich context fields
+/// (`formId`, `associated_label_text`, `fieldset_legend`,
+/// `section_heading`, …) are filled in from the static tree.
+///
+/// Open declarative shadow roots (`<template shadowrootmode="open">`) are
+/// traversed like the browser renders them; elements inside carry the chain
+/// of their shadow hosts in `shadow_path`. Closed shadow roots are skipped.
+pub fn extract_html(html: &str, url: &str) -> Value {
+    extract_document(&Html::parse_document(html), url)
+}
+
+/// `extract_html` over an already parsed document.
+fn extract_document(document: &Html, url: &str) -> Value {
+    let title = select_first(document, "title")
+        .map(|t| collapse_ws(&rendered_text(t)))
+        .unwrap_or_default();
+
+    let labels_by_id = collect_label_targets(document);
+    let text_by_id = collect_text_by_id(document);
+
+    let mut dom = Vec::new();
+    let mut headings = Vec::new();
+    let mut landmarks = Vec::new();
+    let mut last_heading: Option<String> = None;
+
+    let body = match select_first(document, "body") {
+        Some(b) => b,
+        None => {
+            return json!({ "url": url, "title": title, "dom": [], "structural_outline": {} });
+        }
+    };
+
+    let live_regions: Vec<_> = body
+        .descendants()
+        .filter_map(ElementRef::wrap)
+        .filter(|el| is_live_root(*el) && !el.ancestors().filter_map(ElementRef::wrap).any(is_live_root))
+        .map(|el| el.id())
+        .collect();
+
+    for el in body.descendants().filter_map(ElementRef::wrap) {
+        let tag = el.value().name().to_lowercase();
+        if is_non_rendered(el) || is_open_shadow_root(el) {
+            continue;
+        }
+
+        if let Some(level) = heading_level(&tag) {
+            let text = collapse_ws(&rendered_text(el));
+            if !text.is_empty() {
+                headings.push(json!({ "level": level, "text": text }));
+                last_heading = Some(text);
+            }
+        }
+
+        if let Some(landmark) = landmark_tag(el)
+            && landmark != "form"
+        {
+            let label = el
+                .value()
+                .attr("aria-label")
+                .map(|s| s.trim().to_string())
+                .unwrap_or_default();
+            landmarks.push(json!({ "tag": landmark, "label": label }));
+        }
+
+        let interactive = is_interactive(&tag, el);
+        let text = if interactive || owns_text(el) { element_text(el, &tag) } else { None };
+        if !interactive && text.is_none() && table_kind(el, &tag).is_none() {
+            continue;
+        }
+
+        let mut json = element_json(el, &tag, text, &labels_by_id, &text_by_id, last_heading.as_deref());
+        if json["aria_live"].is_string() {
+            json["live_region"] = json!(std::iter::once(el)
+                .chain(el.ancestors().filter_map(ElementRef::wrap))
+                .find_map(|a| live_regions.iter().position(|id| *id == a.id())));
+        }
+        dom.push(json);
+    }
+
+    json!({
+        "url": url,
+        "title": title,
+        "dom": dom,
+        "structural_outline": {
+            "headings": headings,
+            "landmarks": landmarks,
+        },
+    })
+}
+
+/// Load a page from a `file://` URL, an `http(s)://` URL or a plain file path
+/// and extract it with `extract_html`. Drop-in replacement for `extract_screen`
+/// when no browser is available.
+pub fn extract_static(url: &str) -> Result<Value, AgentError> {
+    let html = load_html(url)?;
+    Ok(extract_html(&html, url))
+}
+
+fn load_html(url: &str) -> Result<String, AgentError> {
+    if url.starts_with("http://") || url.starts_with("https://") {
+        let response = reqwest::blocking::get(url).map_err(|e| AgentError::PageLoad {
+            url: url.to_string(),
+            error: e.to_string(),
+        })?;
+        return response.text().map_err(|e| AgentError::PageLoad {
+            url: url.to_string(),
+            error: e.to_string(),
+        });
+    }
+
+    let path = url.strip_prefix("file://").unwrap_or(url);
+    std::fs::read_to_string(path).map_err(|e| AgentError::PageLoad {
+        url: url.to_string(),
+        error: e.to_string(),
+    })
+}
+
+// ============================================================================
+// StaticBrowser — BrowserBackend over static HTML
+// ============================================================================
+
+/// `BrowserBackend` that loads pages with `extract_static` instead of Chromium.
+///
+/// Supports navigation, extraction, full CSS `query_*` and following links on
+/// click. Fills, selects and checks are validated against the page but have no
+/// effect; clicks on anything other than a link fail because no scripts or
+/// form submissions can run.
+#[derive(Debug, Default)]
+pub struct StaticBrowser {
+    current_url: Option<String>,
+    /// Parsed DOM of the loaded page, reused by every `query_*`.
+    document: Option<Html>,
+    payload: Option<Value>,
+}
+
+impl StaticBrowser {
+    /// Create a static browser with no page loaded.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load a page from an HTML string (the URL is only recorded, not fetched).
+    pub fn load_html(&mut self, url: &str, html: &str) {
+        self.current_url = Some(url.to_string());
+        let document = Html::parse_document(html);
+        self.payload = Some(extract_document(&document, url));
+        self.document = Some(document);
+    }
+
+    /// Get the last known URL.
+    pub fn last_url(&self) -> Option<&str> {
+        self.current_url.as_deref()
+    }
+
+    fn payload(&self, command: &str) -> Result<&Value, AgentError> {
+        self.payload.as_ref().ok_or_else(|| no_page(command))
+    }
+
+    fn resolve(&self, selector: &SelectorHint, command: &str) -> Result<Value, AgentError> {
+        let payload = self.payload(command)?;
+        let dom = payload["dom"].as_array().map(Vec::as_slice).unwrap_or_default();
+        resolve_hint(dom, selector, || {
+            format!("static page '{}'", self.current_url.as_deref().unwrap_or(""))
+        })
+        .cloned()
+    }
+
+    fn query<T>(
+        &self,
+        selector: &str,
+        command: &str,
+        f: impl FnOnce(Vec<ElementRef>) -> T,
+    ) -> Result<T, AgentError> {
+        let document = self.document.as_ref().ok_or_else(|| no_page(command))?;
+        let css = Selector::parse(selector).map_err(|e| AgentError::SessionProtocol {
+            command: command.into(),
+            error: format!("Invalid CSS selector '{}': {}", selector, e),
+        })?;
+        Ok(f(document.select(&css).collect()))
+    }
+}
+
+fn no_page(command: &str) -> AgentError {
+    AgentError::SessionProtocol {
+        command: command.into(),
+        error: "No page loaded in StaticBrowser".into(),
+    }
+}
+
+impl BrowserBackend for StaticBrowser {
+    /// Context options do not affect static extraction; accepted and ignored.
+    fn configure(&mut self, _overrides: &SessionOptions) -> Result<(), AgentError> {
+        Ok(())
+    }
+
+    fn navigate(&mut self, url: &str) -> Result<(), AgentError> {
+        let html = load_html(url)?;
+        self.load_html(url, &html);
+        Ok(())
+    }
+
+    fn extract(&mut self) -> Result<Value, AgentError> {
+        self.payload("extract").cloned()
+    }
+
+    fn fill(&mut self, selector: &SelectorHint, _value: &str) -> Result<(), AgentError> {
+        self.resolve(selector, "fill").map(|_| ())
+    }
+
+    fn click(&mut self, selector: &SelectorHint) -> Result<(), AgentError> {
+        let el = self.resolve(selector, "click")?;
+        let base = self.current_url.clone().unwrap_or_default();
+        match el["href"].as_str().and_then(|href| resolve_url(&base, href)) {
+            Some(target) => self.navigate(&target),
+            None => Err(AgentError::BrowserAction(format!(
+                "StaticBrowser can only follow links; '{}' has no href",
+                selector.name.as_deref().unwrap_or("<unnamed>")
+            ))),
+        }
+    }
+
+    fn select_option(&mut self, selector: &SelectorHint, _value: &str) -> Result<(), AgentError> {
+        self.resolve(selector, "select_option").map(|_| ())
+    }
+
+    fn check(&mut self, selector: &SelectorHint) -> Result<(), AgentError> {
+        self.resolve(selector, "check").map(|_| ())
+    }
+
+    fn uncheck(&mut self, selector: &SelectorHint) -> Result<(), AgentError> {
+        self.resolve(selector, "uncheck").map(|_| ())
+    }
+
+    fn hover(&mut self, selector: &SelectorHint) -> Result<(), AgentError> {
+        self.resolve(selector, "hover").map(|_| ())
+    }
+
+    fn press(&mut self, selector: Option<&SelectorHint>, _key: &str) -> Result<(), AgentError> {
+        match selector {
+            Some(selector) => self.resolve(selector, "press").map(|_| ()),
+            None => Ok(()),
+        }
+    }
+
+    fn type_text(&mut self, selector: &SelectorHint, _text: &str, _delay_ms: u64) -> Result<(), AgentError> {
+        self.resolve(selector, "type").map(|_| ())
+    }
+
+    fn scroll_into_view(&mut self, selector: &SelectorHint) -> Result<(), AgentError> {
+        self.resolve(selector, "scroll_into_view").map(|_| ())
+    }
+
+    /// There is no viewport; scrolling is accepted and ignored.
+    fn scroll_by(&mut self, _delta_x: i64, _delta_y: i64) -> Result<(), AgentError> {
+        Ok(())
+    }
+
+    fn drag_to(&mut self, source: &SelectorHint, target: &SelectorHint) -> Result<(), AgentError> {
+        self.resolve(source, "drag")?;
+        self.resolve(target, "drag").map(|_| ())
+    }
+
+    fn set_input_files(&mut self, selector: &SelectorHint, _files: &[String]) -> Result<(), AgentError> {
+        self.resolve(selector, "set_input_files").map(|_| ())
+    }
+
+    fn wait_idle(&mut self, _ms: u64) -> Result<(), AgentError> {
+        // Static pages never change on their own
+        Ok(())
+    }
+
+    /// Scripts never run, so there are no API calls to intercept; routes
+    /// are validated and otherwise ignored.
+    fn route(&mut self, mock: &RouteMock) -> Result<(), AgentError> {
+        mock.resolve().map(|_| ())
+    }
+
+    fn unroute(&mut self, _url: Option<&str>) -> Result<(), AgentError> {
+        Ok(())
+    }
+
+    fn screenshot(&mut self, _path: &str) -> Result<(), AgentError> {
+        Err(AgentError::BrowserAction(
+            "StaticBrowser cannot take screenshots".into(),
+        ))
+    }
+
+    fn start_trace(&mut self) -> Result<(), AgentError> {
+        Err(AgentError::BrowserAction(
+            "StaticBrowser cannot record traces".into(),
+        ))
+    }
+
+    fn stop_trace(&mut self, _path: Option<&str>) -> Result<(), AgentError> {
+        Err(AgentError::BrowserAction(
+            "StaticBrowser cannot record traces".into(),
+        ))
+    }
+
+    fn start_video(&mut self) -> Result<(), AgentError> {
+        Err(AgentError::BrowserAction(
+            "StaticBrowser cannot record video".into(),
+        ))
+    }
+
+    fn stop_video(&mut self, _path: Option<&str>) -> Result<(), AgentError> {
+        Err(AgentError::BrowserAction(
+            "StaticBrowser cannot record video".into(),
+        ))
+    }
+
+    fn current_url(&mut self) -> Result<String, AgentError> {
+        self.current_url.clone().ok_or_else(|| no_page("current_url"))
+    }
+
+    fn query_text(&mut self, selector: &str) -> Result<Option<String>, AgentError> {
+        self.query(selector, "query_text", |els| {
+            els.first().map(|el| collapse_ws(&rendered_text(*el)))
+        })
+    }
+
+    fn query_visible(&mut self, selector: &str) -> Result<bool, AgentError> {
+        self.query(selector, "query_visible", |els| {
+            els.first().map(|el| is_visible(*el)).unwrap_or(false)
+        })
+    }
+
+    fn query_count(&mut self, selector: &str) -> Result<u32, AgentError> {
+        self.query(selector, "query_count", |els| els.len() as u32)
+    }
+
+    /// Only the document itself is fetched, so there is no traffic to report.
+    fn take_network_log(&mut self) -> Result<Vec<NetworkEntry>, AgentError> {
+        Ok(Vec::new())
+    }
+
+    /// Scripts never run, so nothing is ever logged.
+    fn take_console_log(&mut self) -> Result<Vec<ConsoleEntry>, AgentError> {
+        Ok(Vec::new())
+    }
+
+    /// Scripts never run, so no dialog can open; any policy is accepted.
+    fn set_dialog_policy(&mut self, _policy: &DialogPolicy) -> Result<(), AgentError> {
+        Ok(())
+    }
+
+    fn take_dialogs(&mut self) -> Result<Vec<DialogEntry>, AgentError> {
+        Ok(Vec::new())
+    }
+
+    fn save_storage_state(&mut self, _path: &str) -> Result<(), AgentError> {
+        Err(AgentError::BrowserAction(
+            "StaticBrowser has no cookies or localStorage to save".into(),
+        ))
+    }
+
+    fn load_storage_state(&mut self, _path: &str) -> Result<(), AgentError> {
+        Err(AgentError::BrowserAction(
+            "StaticBrowser cannot load cookies or localStorage".into(),
+        ))
+    }
+
+    /// The loaded page is the only tab.
+    fn tabs(&mut self) -> Result<Vec<TabInfo>, AgentError> {
+        Ok(self
+            .current_url
+            .iter()
+            .map(|url| TabInfo {
+                index: 0,
+                url: url.clone(),
+                title: self
+                    .payload
+                    .as_ref()
+                    .and_then(|p| p["title"].as_str())
+                    .unwrap_or("")
+                    .to_string(),
+                active: true,
+            })
+            .collect())
+    }
+
+    fn switch_tab(&mut self, tab: &TabSelector) -> Result<TabInfo, AgentError> {
+        let tabs = self.tabs()?;
+        tab.pick(&tabs).cloned().ok_or_else(|| {
+            AgentError::BrowserAction(format!("StaticBrowser has no {}", tab.describe()))
+        })
+    }
+
+    fn close_tab(&mut self, _tab: &TabSelector) -> Result<(), AgentError> {
+        Err(AgentError::BrowserAction(
+            "StaticBrowser cannot close its only tab".into(),
+        ))
+    }
+
+    fn wait_for_popup(&mut self, _timeout_ms: u64) -> Result<TabInfo, AgentError> {
+        Err(AgentError::BrowserAction(
+            "StaticBrowser never opens popups".into(),
+        ))
+    }
+
+    fn start_recording(&mut self) -> Result<(), AgentError> {
+        Err(AgentError::BrowserAction(
+            "StaticBrowser has no user to record".into(),
+        ))
+    }
+
+    fn take_recorded_events(&mut self) -> Result<Vec<RecordedEvent>, AgentError> {
+        Ok(Vec::new())
+    }
+}
+
+// ============================================================================
+// Per-element field extraction
+// ============================================================================
+
+fn element_json(
+    el: ElementRef,
+    tag: &str,
+    text: Option<String>,
+    labels_by_id: &HashMap<String, String>,
+    text_by_id: &HashMap<String, String>,
+    section_heading: Option<&str>,
+) -> Value {
+    let attr = |name: &str| el.value().attr(name).map(|s| s.to_string());
+    let flag = |name: &str| el.value().attr(name).is_some();
+    let form = owning_form(el, tag);
+    let form_attr = |name: &str| form.and_then(|f| f.value().attr(name)).map(|s| s.to_string());
+
+    let aria_label = attr("aria-label")
+        .filter(|s| !s.trim().is_empty())
+        .or_else(|| resolve_id_list(el.value().attr("aria-labelledby"), text_by_id));
+
+    let associated_label_text = el
+        .value()
+        .id()
+        .and_then(|id| labels_by_id.get(id).cloned())
+        .or_else(|| {
+            el.ancestors()
+                .filter_map(ElementRef::wrap)
+                .find(|a| a.value().name() == "label")
+                .map(|label| collapse_ws(&label_text(label)))
+        })
+        .filter(|s| !s.is_empty());
+
+    let (options, value) = if tag == "select" {
+        let options: Vec<Value> = el
+            .descendants()
+            .filter_map(ElementRef::wrap)
+            .filter(|o| o.value().name() == "option")
+            .map(|o| {
+                let text = collapse_ws(&rendered_text(o));
+                let value = o.value().attr("value").map(|v| v.to_string()).unwrap_or_else(|| text.clone());
+                json!({ "value": value, "text": text })
+            })
+            .collect();
+        let selected = el
+            .descendants()
+            .filter_map(ElementRef::wrap)
+            .filter(|o| o.value().name() == "option")
+            .find(|o| o.value().attr("selected").is_some())
+            .and_then(|o| o.value().attr("value").map(|v| v.to_string()))
+            .or_else(|| options.first().and_then(|o| o["value"].as_str().map(|s| s.to_string())));
+        (Some(options), selected)
+    } else {
+        (None, attr("value"))
+    };
+
+    json!({
+        "tag": tag,
+        "text": text,
+        "role": attr("role").or_else(|| implicit_role(tag, el).map(|r| r.to_string())),
+        "type": attr("type").map(|t| t.to_lowercase()),
+        "ariaLabel": aria_label,
+        "disabled": flag("disabled") || in_disabled_fieldset(el),
+        "required": flag("required"),
+        "formId": form.and_then(form_identifier),
+        "id": attr("id"),
+        "name": attr("name"),
+        "placeholder": attr("placeholder"),
+        "href": attr("href"),
+        "value": value,
+        "options": options,
+        "pattern": attr("pattern"),
+        "minlength": attr("minlength").and_then(|v| v.trim().parse::<u32>().ok()),
+        "maxlength": attr("maxlength").and_then(|v| v.trim().parse::<u32>().ok()),
+        "min": attr("min"),
+        "max": attr("max"),
+        "readonly": flag("readonly"),
+        "heading_level": heading_level(tag),
+        "autocomplete": attr("autocomplete"),
+        "inputmode": attr("inputmode"),
+        "title_attr": attr("title"),
+        "form_action": form_attr("action"),
+        "form_method": form_attr("method").map(|m| m.to_lowercase()),
+        "aria_describedby_text": resolve_id_list(el.value().attr("aria-describedby"), text_by_id),
+        "aria_invalid": el.value().attr("aria-invalid") == Some("true"),
+        "aria_required": el.value().attr("aria-required") == Some("true"),
+        "associated_label_text": associated_label_text,
+        "fieldset_legend": fieldset_legend(el),
+        "section_heading": section_heading,
+        "nearby_help_text": nearby_help_text(el),
+        "semantic_section": el.ancestors().filter_map(ElementRef::wrap).find_map(landmark_tag),
+        "aria_live": live_politeness(el),
+        "in_dialog": in_dialog(el),
+        "table": table_data(el, tag),
+        "visible": is_visible(el),
+        "shadow_path": shadow_path(el),
+    })
+}
+
+/// Whether the element has a non-blank text node as a direct child.
+fn owns_text(el: ElementRef) -> bool {
+    el.children()
+        .filter_map(|node| node.value().as_text())
+        .any(|text| !text.trim().is_empty())
+}
+
+/// Text for an element, or None if it carries no rendered text.
+///
+/// Form controls report no text (their label/value are separate fields).
+fn element_text(el: ElementRef, tag: &str) -> Option<String> {
+    if matches!(tag, "input" | "select" | "textarea") {
+        return None;
+    }
+    let text = collapse_ws(&rendered_text(el));
+    if text.is_empty() { None } else { Some(text) }
+}
+
+fn is_interactive(tag: &str, el: ElementRef) -> bool {
+    matches!(tag, "input" | "select" | "textarea" | "button")
+        || (tag == "a" && el.value().attr("href").is_some())
+        || el.value().attr("role") == Some("button")
+}
+
+fn implicit_role(tag: &str, el: ElementRef) -> Option<&'static str> {
+    match tag {
+        "button" => Some("button"),
+        "a" if el.value().attr("href").is_some() => Some("link"),
+        "select" => Some("combobox"),
+        "textarea" => Some("textbox"),
+        "input" => match el.value().attr("type").map(|t| t.to_lowercase()).as_deref() {
+            None | Some("text") | Some("email") | Some("tel") | Some("url") | Some("password") => Some("textbox"),
+            Some("search") => Some("searchbox"),
+            Some("number") => Some("spinbutton"),
+            Some("checkbox") => Some("checkbox"),
+            Some("radio") => Some("radio"),
+            Some("range") => Some("slider"),
+            Some("submit") | Some("button") | Some("reset") | Some("image") => Some("button"),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Rows and columns of a `<table>` or ARIA table / grid, or the items of a
+/// list, for the `table` field (see `TableData`); None for other elements.
+///
+/// The header row is the first row made only of `<th>` / `columnheader`
+/// cells; rows of nested tables and hidden rows are left out. Tables with a
+/// role other than table / grid (e.g. `presentation`) are layout, not data.
+fn table_data(el: ElementRef, tag: &str) -> Option<Value> {
+    let kind = table_kind(el, tag)?;
+    let text = |e: ElementRef| collapse_ws(&rendered_text(e));
+
+    // Data tables this one sits in (layout tables are not counted)
+    let depth = el
+        .ancestors()
+        .filter_map(ElementRef::wrap)
+        .filter(|a| table_kind(*a, &a.value().name().to_lowercase()).is_some_and(|k| k != "list"))
+        .count();
+
+    if kind == "list" {
+        let rows: Vec<Value> = el
+            .children()
+            .filter_map(ElementRef::wrap)
+            .filter(|c| c.value().name() == "li" || c.value().attr("role") == Some("listitem"))
+            .filter(|c| is_visible(*c))
+            .map(|c| json!([text(c)]))
+            .collect();
+        return Some(json!({ "kind": kind, "headers": [], "rows": rows, "depth": depth }));
+    }
+
+    let caption = el
+        .children()
+        .filter_map(ElementRef::wrap)
+        .find(|c| c.value().name() == "caption")
+        .map(text)
+        .filter(|t| !t.is_empty());
+
+    let mut headers: Vec<String> = Vec::new();
+    let mut rows: Vec<Vec<String>> = Vec::new();
+    let own_rows = el.descendants().filter_map(ElementRef::wrap).filter(|r| {
+        (r.value().name() == "tr" || r.value().attr("role") == Some("row"))
+            && r.ancestors().filter_map(ElementRef::wrap).find(|a| is_table_like(*a)).map(|a| a.id()) == Some(el.id())
+            && is_visible(*r)
+    });
+    for row in own_rows {
+        let cells: Vec<ElementRef> = row
+            .children()
+            .filter_map(ElementRef::wrap)
+            .filter(|c| {
+                matches!(c.value().name(), "td" | "th")
+                    || matches!(c.value().attr("role"), Some("cell" | "gridcell" | "columnheader" | "rowheader"))
+            })
+            .collect();
+        if cells.is_empty() {
+            continue;
+        }
+        let header_row = cells
+            .iter()
+            .all(|c| c.value().name() == "th" || c.value().attr("role") == Some("columnheader"))
+            && headers.is_empty()
+            && rows.is_empty();
+        let texts = cells.into_iter().map(text).collect();
+        if header_row {
+            headers = texts;
+        } else {
+            rows.push(texts);
+        }
+    }
+
+    Some(json!({ "kind": kind, "caption": caption, "headers": headers, "rows": rows, "depth": depth }))
+}
+
+/// `TableKind` name of a table, grid or list element.
+fn table_kind(el: ElementRef, tag: &str) -> Option<&'static str> {
+    match (tag, el.value().attr("role")) {
+        (_, Some("grid")) | (_, Some("treegrid")) => Some("grid"),
+        (_, Some("table")) | ("table", None) => Some("table"),
+        (_, Some("list")) | ("ul", None) | ("ol", None) => Some("list"),
+        _ => None,
+    }
+}
+
+fn is_table_like(el: ElementRef) -> bool {
+    el.value().name() == "table" || matches!(el.value().attr("role"), Some("table" | "grid" | "treegrid"))
+}
+
+fn heading_level(tag: &str) -> Option<u8> {
+    match tag {
+        "h1" => Some(1),
+        "h2" => Some(2),
+        "h3" => Some(3),
+        "h4" => Some(4),
+        "h5" => Some(5),
+        "h6" => Some(6),
+        _ => None,
+    }
+}
+
+/// Politeness of the nearest live region around (or at) `el`: its `aria-live`
+/// value, or the implicit one of the `alert` / `status` / `log` roles.
+/// `aria-live="off"` ends the search with no live region.
+fn live_politeness(el: ElementRef) -> Option<String> {
+    std::iter::once(el)
+        .chain(el.ancestors().filter_map(ElementRef::wrap))
+        .find_map(|a| match a.value().attr("aria-live").map(|v| v.trim().to_lowercase()) {
+            Some(value) => Some(Some(value).filter(|v| v != "off")),
+            None => match a.value().attr("role") {
+                Some("alert") => Some(Some("assertive".to_string())),
+                Some("status") | Some("log") => Some(Some("polite".to_string())),
+                _ => None,
+            },
+        })
+        .flatten()
+}
+
+/// Whether `el` starts a live region (`aria-live="off"` does not).
+fn is_live_root(el: ElementRef) -> bool {
+    match el.value().attr("aria-live") {
+        Some(value) => !value.trim().eq_ignore_ascii_case("off"),
+        None => matches!(el.value().attr("role"), Some("alert" | "status" | "log")),
+    }
+}
+
+/// Whether the element is, or sits inside, a dialog: a `<dialog>`, a
+/// `dialog` / `alertdialog` role or an `aria-modal="true"` container.
+fn in_dialog(el: ElementRef) -> bool {
+    std::iter::once(el).chain(el.ancestors().filter_map(ElementRef::wrap)).any(|a| {
+        a.value().name() == "dialog"
+            || matches!(a.value().attr("role"), Some("dialog") | Some("alertdialog"))
+            || a.value().attr("aria-modal").is_some_and(|v| v.trim().eq_ignore_ascii_case("true"))
+    })
+}
+
+/// Landmark name for an element: its HTML5 tag, or the tag equivalent of its ARIA role.
+fn landmark_tag(el: ElementRef) -> Option<String> {
+    let by_role = match el.value().attr("role") {
+        Some("banner") => Some("header"),
+        Some("navigation") => Some("nav"),
+        Some("main") => Some("main"),
+        Some("complementary") => Some("aside"),
+        Some("contentinfo") => Some("footer"),
+        Some("region") => Some("section"),
+        Some("form") | Some("search") => Some("form"),
+        Some("dialog") | Some("alertdialog") => Some("dialog"),
+        _ => None,
+    };
+    if let Some(tag) = by_role {
+        return Some(tag.to_string());
+    }
+    let tag = el.value().name();
+    LANDMARK_TAGS.contains(&tag).then(|| tag.to_string())
+}
+
+/// The form an element belongs to: its `form` attribute, or the nearest ancestor `<form>`.
+fn owning_form<'a>(el: ElementRef<'a>, tag: &str) -> Option<ElementRef<'a>> {
+    if !matches!(tag, "input" | "select" | "textarea" | "button" | "output" | "fieldset") {
+        return None;
+    }
+    associated_form(el)
+}
+
+/// Form association that stops at shadow boundaries: a control inside a
+/// shadow tree with no `<form>` of its own belongs to its host's form, which
+/// is how form-associated custom elements (`ElementInternals.form`) behave.
+fn associated_form(el: ElementRef) -> Option<ElementRef> {
+    if let Some(form_ref) = el.value().attr("form") {
+        let root = el.ancestors().filter_map(ElementRef::wrap).last()?;
+        return root
+            .descendants()
+            .filter_map(ElementRef::wrap)
+            .find(|f| f.value().name() == "form" && f.value().id() == Some(form_ref));
+    }
+    for ancestor in el.ancestors().filter_map(ElementRef::wrap) {
+        if ancestor.value().name() == "form" {
+            return Some(ancestor);
+        }
+        if is_open_shadow_root(ancestor) {
+            let host = ancestor.parent().and_then(ElementRef::wrap)?;
+            return associated_form(host);
+        }
+    }
+    None
+}
+
+/// Whether `el` is an open declarative shadow root (`<template shadowrootmode="open">`).
+fn is_open_shadow_root(el: ElementRef) -> bool {
+    el.value().name() == "template"
+        && el
+            .value()
+            .attr("shadowrootmode")
+            .or_else(|| el.value().attr("shadowroot"))
+            .is_some_and(|mode| mode.eq_ignore_ascii_case("open"))
+}
+
+/// Selectors of the shadow hosts enclosing `el`, outermost first: the host's
+/// tag plus `#id` when it has one (e.g. `["app-checkout", "ds-input#email"]`).
+fn shadow_path(el: ElementRef) -> Vec<String> {
+    let mut hosts: Vec<String> = el
+        .ancestors()
+        .filter_map(ElementRef::wrap)
+        .filter(|a| is_open_shadow_root(*a))
+        .filter_map(|root| root.parent().and_then(ElementRef::wrap))
+        .map(|host| match host.value().id() {
+            Some(id) => format!("{}#{}", host.value().name(), id),
+            None => host.value().name().to_string(),
+        })
+        .collect();
+    hosts.reverse();
+    hosts
+}
+
+fn form_identifier(form: ElementRef) -> Option<String> {
+    form.value()
+        .id()
+        .or_else(|| form.value().attr("name"))
+        .map(|s| s.to_string())
+        .filter(|s| !s.is_empty())
+}
+
+fn in_disabled_fieldset(el: ElementRef) -> bool {
+    el.ancestors()
+        .filter_map(ElementRef::wrap)
+        .any(|a| a.value().name() == "fieldset" && a.value().attr("disabled").is_some())
+}
+
+fn fieldset_legend(el: ElementRef) -> Option<String> {
+    let fieldset = el
+        .ancestors()
+        .filter_map(ElementRef::wrap)
+        .find(|a| a.value().name() == "fieldset")?;
+    fieldset
+        .children()
+        .filter_map(ElementRef::wrap)
+        .find(|c| c.value().name() == "legend")
+        .map(|legend| collapse_ws(&rendered_text(legend)))
+        .filter(|s| !s.is_empty())
+}
+
+/// Short help text rendered right after a control (e.g. `<small>` or `.hint`).
+fn nearby_help_text(el: ElementRef) -> Option<String> {
+    let sibling = el.next_siblings().filter_map(ElementRef::wrap).next()?;
+    let class = sibling.value().attr("class").unwrap_or("").to_lowercase();
+    let looks_like_help = sibling.value().name() == "small"
+        || class.contains("help")
+        || class.contains("hint")
+        || class.contains("description");
+    if !looks_like_help {
+        return None;
+    }
+    let text = collapse_ws(&rendered_text(sibling));
+    if text.is_empty() || text.len() > 200 { None } else { Some(text) }
+}
+
+fn is_visible(el: ElementRef) -> bool {
+    std::iter::once(el)
+        .chain(el.ancestors().filter_map(ElementRef::wrap))
+        .all(|e| {
+            let v = e.value();
+            let style = v.attr("style").unwrap_or("").replace(' ', "").to_lowercase();
+            v.attr("hidden").is_none()
+                && v.attr("aria-hidden") != Some("true")
+                && !(v.name() == "input" && v.attr("type").map(|t| t.eq_ignore_ascii_case("hidden")) == Some(true))
+                && !style.contains("display:none")
+                && !style.contains("visibility:hidden")
+        })
+}
+
+fn is_non_rendered(el: ElementRef) -> bool {
+    std::iter::once(el)
+        .chain(el.ancestors().filter_map(ElementRef::wrap))
+        .any(hides_contents)
+}
+
+/// Whether nothing under `el` is rendered (open shadow roots are rendered).
+fn hides_contents(el: ElementRef) -> bool {
+    NON_RENDERED.contains(&el.value().name()) && !is_open_shadow_root(el)
+}
+
+// ============================================================================
+// Document-level lookups and text helpers
+// ============================================================================
+
+/// Map of element id → text of every `<label for="id">`.
+fn collect_label_targets(document: &Html) -> HashMap<String, String> {
+    let mut map = HashMap::new();
+    if let Ok(sel) = Selector::parse("label[for]") {
+        for label in document.select(&sel) {
+            if let Some(target) = label.value().attr("for") {
+                let text = collapse_ws(&label_text(label));
+                if !text.is_empty() {
+                    map.entry(target.to_string()).or_insert(text);
+                }
+            }
+        }
+    }
+    map
+}
+
+/// Map of element id → rendered text, for aria-labelledby / aria-describedby.
+fn collect_text_by_id(document: &Html) -> HashMap<String, String> {
+    let mut map = HashMap::new();
+    if let Ok(sel) = Selector::parse("[id]") {
+        for el in document.select(&sel) {
+            if let Some(id) = el.value().id() {
+                let text = collapse_ws(&rendered_text(el));
+                if !text.is_empty() {
+                    map.insert(id.to_string(), text);
+                }
+            }
+        }
+    }
+    map
+}
+
+fn resolve_id_list(ids: Option<&str>, text_by_id: &HashMap<String, String>) -> Option<String> {
+    let joined = ids?
+        .split_whitespace()
+        .filter_map(|id| text_by_id.get(id).cloned())
+        .collect::<Vec<_>>()
+        .join(" ");
+    if joined.is_empty() { None } else { Some(joined) }
+}
+
+fn select_first<'a>(document: &'a Html, css: &str) -> Option<ElementRef<'a>> {
+    let sel = Selector::parse(css).ok()?;
+    document.select(&sel).next()
+}
+
+/// All rendered text under an element (skips scripts, styles and templates
+/// other than open shadow roots).
+fn rendered_text(el: ElementRef) -> String {
+    let mut out = String::new();
+    for node in el.descendants() {
+        if let Some(text) = node.value().as_text() {
+            let hidden = node
+                .ancestors()
+                .filter_map(ElementRef::wrap)
+                .take_while(|a| a.id() != el.id())
+                .any(hides_contents);
+            if !hidden {
+                out.push_str(text);
+                out.push(' ');
+            }
+        }
+    }
+    out
+}
+
+/// Label text without the text of any control nested inside the label.
+fn label_text(label: ElementRef) -> String {
+    let mut out = String::new();
+    for node in label.descendants() {
+        if let Some(text) = node.value().as_text() {
+            let inside_control = node
+                .ancestors()
+                .filter_map(ElementRef::wrap)
+                .take_while(|a| a.id() != label.id())
+                .any(|a| matches!(a.value().name(), "select" | "textarea" | "button" | "script" | "style"));
+            if !inside_control {
+                out.push_str(text);
+                out.push(' ');
+            }
+        }
+    }
+    out
+}
+
+fn collapse_ws(s: &str) -> String {
+    s.split_whitespace().collect::<Vec<_>>().join(" ")
+}
@@ -0,0 +1,99 @@
+use serde::{Deserialize, Serialize};
+
+// ============================================================================
+// Tabs — pages open in the browser context
+// ============================================================================
+
+/// One open tab (Playwright page) as reported by `browser_server.js`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TabInfo {
+    /// Position in opening order (0 = the first tab)
+    pub index: usize,
+
+    pub url: String,
+
+    #[serde(default)]
+    pub title: String,
+
+    /// Whether commands currently go to this tab
+    #[serde(default)]
+    pub active: bool,
+}
+
+/// Which tab to switch to or close.
+///
+/// Every field that is set must match. An empty selector means the most
+/// recently opened tab for `switch_tab` and the active tab for `close_tab`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct TabSelector {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub index: Option<usize>,
+
+    /// Substring of the tab's URL
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url_contains: Option<String>,
+
+    /// Substring of the tab's title
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title_contains: Option<String>,
+}
+
+impl TabSelector {
+    /// Select the tab at `index`.
+    pub fn index(index: usize) -> Self {
+        TabSelector {
+            index: Some(index),
+            ..TabSelector::default()
+        }
+    }
+
+    /// Select the newest tab whose URL contains `fragment`.
+    pub fn url_contains(fragment: &str) -> Self {
+        TabSelector {
+            url_contains: Some(fragment.to_string()),
+            ..TabSelector::default()
+        }
+    }
+
+    /// Whether no criterion is set.
+    pub fn is_empty(&self) -> bool {
+        *self == TabSelector::default()
+    }
+
+    /// Whether `tab` satisfies every criterion that is set.
+    pub fn matches(&self, tab: &TabInfo) -> bool {
+        self.index.is_none_or(|i| i == tab.index)
+            && self
+                .url_contains
+                .as_ref()
+                .is_none_or(|u| tab.url.contains(u.as_str()))
+            && self
+                .title_contains
+                .as_ref()
+                .is_none_or(|t| tab.title.contains(t.as_str()))
+    }
+
+    /// The newest tab in `tabs` that matches, if any.
+    pub fn pick<'a>(&self, tabs: &'a [TabInfo]) -> Option<&'a TabInfo> {
+        tabs.iter().rev().find(|t| self.matches(t))
+    }
+
+    /// Human-readable description for error messages.
+    pub fn describe(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(index) = self.index {
+            parts.push(format!("index {}", index));
+        }
+        if let Some(url) = &self.url_contains {
+            parts.push(format!("URL containing '{}'", url));
+        }
+        if let Some(title) = &self.title_contains {
+            parts.push(format!("title containing '{}'", title));
+        }
+        if parts.is_empty() {
+            "newest tab".to_string()
+        } else {
+            format!("tab with {}", parts.join(", "))
+        }
+    }
+}
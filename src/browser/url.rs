@@ -0,0 +1,57 @@
+//! URL helpers shared by the browser backends and the explorer.
+
+/// Check if two URLs share the same origin (scheme + host).
+pub fn is_same_origin(base: &str, candidate: &str) -> bool {
+    match (extract_origin(base), extract_origin(candidate)) {
+        (Some(a), Some(b)) => a == b,
+        _ => false,
+    }
+}
+
+/// Extract the origin (scheme + host) from a URL.
+///
+/// Example: `"https://example.com/path/page"` → `"https://example.com"`
+pub fn extract_origin(url: &str) -> Option<String> {
+    let after_scheme = url.find("://").map(|i| i + 3)?;
+    let end = url[after_scheme..]
+        .find('/')
+        .map(|i| after_scheme + i)
+        .unwrap_or(url.len());
+    Some(url[..end].to_string())
+}
+
+/// Resolve a navigation candidate against a base URL.
+///
+/// Handles absolute URLs, protocol-relative URLs, root-relative paths,
+/// and relative paths. Filters out `javascript:`, `mailto:`, `tel:`,
+/// empty strings, and bare `#` fragments.
+pub fn resolve_url(base: &str, candidate: &str) -> Option<String> {
+    let trimmed = candidate.trim();
+    if trimmed.is_empty()
+        || trimmed == "#"
+        || trimmed.starts_with("javascript:")
+        || trimmed.starts_with("mailto:")
+        || trimmed.starts_with("tel:")
+    {
+        return None;
+    }
+    // Absolute URL — pass through
+    if trimmed.starts_with("http://") || trimmed.starts_with("https://") {
+        return Some(trimmed.to_string());
+    }
+    // Protocol-relative — inherit scheme from base
+    if trimmed.starts_with("//") {
+        let scheme = base.split("://").next().unwrap_or("https");
+        return Some(format!("{}:{}", scheme, trimmed));
+    }
+    // Relative: resolve against base origin
+    let origin = extract_origin(base)?;
+    if trimmed.starts_with('/') {
+        // Root-relative: /path
+        Some(format!("{}{}", origin, trimmed))
+    } else {
+        // Relative path: resolve against base directory
+        let base_dir = base.rfind('/').map(|i| &base[..i + 1]).unwrap_or(base);
+        Some(format!("{}{}", base_dir, trimmed))
+    }
+}
@@ -1,7 +1,7 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
 use crate::{
-    screen::screen_model::{ElementKind, FormIntent, ScreenElement},
+    screen::screen_model::{DataTable, ElementKind, FormIntent, OutputRegion, Overlay, ScreenElement},
     state::{diff::StateDiff, state_model::ScreenState},
 };
 
@@ -21,6 +21,13 @@ pub struct CanonicalScreenState {
 
     /// Outputs visible on screen
     pub outputs: Vec<String>, // element IDs
+
+    /// Tables, grids and repeated lists indexed by table id
+    pub tables: BTreeMap<String, DataTable>,
+
+    /// Open dialogs, each named by the heading its elements sit under
+    /// (see `dialog_identity`)
+    pub dialogs: BTreeSet<String>,
 }
 
 impl CanonicalScreenState {
@@ -31,7 +38,9 @@ impl CanonicalScreenState {
             forms: Default::default(),
             standalone_actions: vec![],
             outputs: vec![],
+            tables: BTreeMap::new(),
             elements: BTreeMap::new(),
+            dialogs: BTreeSet::new(),
         }
     }
 }
@@ -53,6 +62,8 @@ pub struct CanonicalElement {
     pub kind: ElementKind,
     pub label: Option<String>,
     pub scope: String, // "screen" | "form:<id>"
+    pub region: OutputRegion,
+    pub overlay: Option<Overlay>,
 }
 
 pub fn canonicalize(
@@ -71,6 +82,8 @@ pub fn canonicalize(
                 kind: identified.element.kind.clone(),
                 label: identified.element.label.clone(),
                 scope: identified.scope.clone(),
+                region: identified.region.clone(),
+                overlay: identified.element.overlay,
             },
         );
     }
@@ -130,9 +143,29 @@ pub fn canonicalize(
         forms,
         standalone_actions,
         outputs,
+        tables: state.tables.iter().map(|t| (t.id.clone(), t.clone())).collect(),
+        dialogs: state
+            .identities
+            .values()
+            .map(|ident| &ident.element)
+            .filter(|el| el.overlay == Some(Overlay::Modal))
+            .map(dialog_identity)
+            .collect(),
     }
 }
 
+/// Which dialog a modal element belongs to: its section heading (the
+/// dialog's title), case-folded; empty for dialogs without any heading.
+///
+/// Element ids come from labels, so two dialogs sharing a "Close" button
+/// would look alike; their titles tell them apart.
+fn dialog_identity(el: &ScreenElement) -> String {
+    el.section_heading
+        .as_deref()
+        .map(|h| h.trim().to_lowercase())
+        .unwrap_or_default()
+}
+
 fn resolve_id(state: &ScreenState, el: &ScreenElement) -> Option<String> {
     state
         .identities
@@ -1,6 +1,9 @@
 use std::collections::BTreeSet;
 
-use crate::{canonical::canonical_model::CanonicalScreenState, screen::screen_model::ElementKind};
+use crate::{
+    canonical::canonical_model::CanonicalScreenState,
+    screen::screen_model::{ElementKind, OutputRegion, Overlay},
+};
 
 #[derive(Debug, Clone)]
 pub struct SemanticStateDiff {
@@ -57,6 +60,14 @@ pub enum SemanticSignal {
     FormSubmitted { form_id: String },
     ResultsAppeared,
     ErrorAppeared,
+    /// A dialog opened (a cookie banner, a confirmation), possibly in place
+    /// of another; also reported on the initial observation when the page
+    /// loads with one
+    ModalOpened,
+    /// A dialog open before is gone (also when another one replaced it)
+    ModalClosed,
+    /// A live-region message appeared outside any dialog ("Saved")
+    ToastAppeared { text: String },
     NoOp, // action produced no visible change
 }
 
@@ -135,7 +146,7 @@ fn derive_signals(
     forms: &FormDiff,
     _actions: &ActionDiff,
     outputs: &OutputDiff,
-    _before: &CanonicalScreenState,
+    before: &CanonicalScreenState,
     after: &CanonicalScreenState,
     is_initial: bool,
 ) -> Vec<SemanticSignal> {
@@ -144,19 +155,35 @@ fn derive_signals(
     // ---- Initial observation ----
     if is_initial {
         signals.push(SemanticSignal::ScreenLoaded);
+        if !after.dialogs.is_empty() {
+            signals.push(SemanticSignal::ModalOpened);
+        }
         return signals;
     }
 
+    let toasts: Vec<String> = outputs
+        .added
+        .iter()
+        .filter_map(|id| after.elements.get(id))
+        .filter(|el| el.overlay == Some(Overlay::Toast))
+        .filter_map(|el| el.label.clone())
+        .collect();
+    // Dialogs are compared by title, so one replacing another is both
+    let modal_opened = !after.dialogs.is_subset(&before.dialogs);
+    let modal_closed = !before.dialogs.is_subset(&after.dialogs);
+
     let form_disappeared = !forms.removed.is_empty();
-    let outputs_appeared = !outputs.added.is_empty();
+    let outputs_appeared = content_appeared(outputs, after);
+    let feedback_appeared = outputs_appeared || !toasts.is_empty();
 
     // ---- Navigation ----
-    if form_disappeared && !outputs_appeared {
+    // (a form leaving with its dialog was closed, not navigated away from)
+    if form_disappeared && !feedback_appeared && !modal_closed {
         signals.push(SemanticSignal::NavigationOccurred);
     }
 
     // ---- Form submission ----
-    if form_disappeared && outputs_appeared {
+    if form_disappeared && feedback_appeared {
         for form_id in &forms.removed {
             signals.push(SemanticSignal::FormSubmitted {
                 form_id: form_id.clone(),
@@ -173,6 +200,18 @@ fn derive_signals(
         signals.push(SemanticSignal::ResultsAppeared);
     }
 
+    // ---- Overlays ----
+    // An opened modal goes last: it is what must be dealt with next
+    if modal_closed {
+        signals.push(SemanticSignal::ModalClosed);
+    }
+    for text in toasts {
+        signals.push(SemanticSignal::ToastAppeared { text });
+    }
+    if modal_opened {
+        signals.push(SemanticSignal::ModalOpened);
+    }
+
     // ---- No-op ----
     if signals.is_empty() {
         signals.push(SemanticSignal::NoOp);
@@ -181,6 +220,17 @@ fn derive_signals(
     signals
 }
 
+/// Whether page content (not just chrome or overlays) was added. Header
+/// and footer churn (a clock, a cart badge) is not content, nor are dialogs
+/// and toasts, which have signals of their own.
+fn content_appeared(outputs: &OutputDiff, after: &CanonicalScreenState) -> bool {
+    outputs
+        .added
+        .iter()
+        .filter_map(|id| after.elements.get(id))
+        .any(|el| el.overlay.is_none() && !matches!(el.region, OutputRegion::Header | OutputRegion::Footer))
+}
+
 fn output_is_error(id: &str, state: &CanonicalScreenState) -> bool {
     let el = match state.elements.get(id) {
         Some(e) => e,
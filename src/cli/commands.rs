@@ -1,47 +1,78 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
 use crate::agent::ai_model::OllamaBackend;
 use crate::agent::page_analyzer::{LlmPageAnalyzer, MockPageAnalyzer, PageAnalyzer};
-use crate::browser::session::BrowserSession;
-use crate::cli::config::build_explorer_config;
-use crate::explorer::explorer::explore_live;
+use crate::browser::doctor::run_checks;
+use crate::browser::routes::RouteMock;
+use crate::browser::session::{BrowserSession, SessionConfig};
+use crate::cli::config::{AppConfig, AuthConfig, build_explorer_config};
+use crate::explorer::app_map::AppMap;
+use crate::explorer::explorer::{authenticate, explore_live};
 use crate::explorer::flow_detector::detect_flows;
 use crate::explorer::test_generator::generate_test_plan;
 use crate::report::console::format_console_report;
 use crate::report::html::generate_html_report;
 use crate::report::junit::generate_junit_xml;
 use crate::report::report_model::TestSuiteReport;
-use crate::spec::runner::TestRunner;
-use crate::spec::runner_config::RunnerConfig;
+use crate::spec::pool::run_pool;
+use crate::spec::recorder::record_session;
 use crate::spec::spec_model::TestSpec;
 
 // ============================================================================
 // explore subcommand
 // ============================================================================
 
-pub fn cmd_explore(
-    url: &str,
-    max_pages: usize,
-    max_depth: usize,
-    explore_forms: bool,
-    max_forms_per_page: usize,
-    analyzer_name: &str,
-    verbose: u8,
-    ollama_endpoint: Option<&str>,
-    ollama_model: Option<&str>,
-) -> Result<(), Box<dyn std::error::Error>> {
-    let config = build_explorer_config(url, max_pages, max_depth, explore_forms, max_forms_per_page);
-    let analyzer = build_analyzer(analyzer_name, ollama_endpoint, ollama_model)?;
-    let mut session = BrowserSession::launch()?;
+/// Options of the `explore` and `generate` subcommands, resolved in
+/// `main.rs` from the command line and the config file.
+pub struct ExploreOptions<'a> {
+    pub url: &'a str,
+    pub max_pages: usize,
+    pub max_depth: usize,
+    pub explore_forms: bool,
+    pub max_forms_per_page: usize,
+    pub analyzer: &'a str,
+    pub verbose: u8,
+    pub ollama_endpoint: Option<&'a str>,
+    pub ollama_model: Option<&'a str>,
+    /// Browser launch settings (`session:` config section)
+    pub session: &'a SessionConfig,
+    /// Login performed before exploring (`auth:` config section)
+    pub auth: &'a AuthConfig,
+    /// API mocks installed while exploring (`explore.mocks`)
+    pub mocks: &'a [RouteMock],
+}
 
-    if verbose > 0 {
+impl ExploreOptions<'_> {
+    /// Explore the app as configured and return its map.
+    fn explore(&self) -> Result<AppMap, Box<dyn std::error::Error>> {
+        let mut config = build_explorer_config(
+            self.url,
+            self.max_pages,
+            self.max_depth,
+            self.explore_forms,
+            self.max_forms_per_page,
+        );
+        config.mocks = self.mocks.to_vec();
+        let analyzer = build_analyzer(self.analyzer, self.ollama_endpoint, self.ollama_model)?;
+        let mut session = BrowserSession::launch_with(self.session.clone())?;
+
+        let app_map = explore_live(&config, &mut session, analyzer.as_ref(), Some(self.auth), None, None)?;
+        session.quit()?;
+        Ok(app_map)
+    }
+}
+
+pub fn cmd_explore(opts: &ExploreOptions) -> Result<(), Box<dyn std::error::Error>> {
+    if opts.verbose > 0 {
         eprintln!(
             "Exploring {} (max_pages={}, max_depth={})...",
-            url, max_pages, max_depth
+            opts.url, opts.max_pages, opts.max_depth
         );
     }
 
-    let app_map = explore_live(&config, &mut session, analyzer.as_ref(), None, None, None)?;
-    session.quit()?;
-
+    let app_map = opts.explore()?;
     // Print summary
     println!(
         "Explored {} pages, {} transitions",
@@ -75,11 +106,17 @@ pub fn cmd_explore(
 // ============================================================================
 
 /// Run test specs and return whether all passed.
+///
+/// With `jobs > 1`, specs run concurrently on a pool of browser sessions;
+/// the report keeps the spec load order either way. Runner, session and
+/// auth settings come from the `run:`, `session:` and `auth:` config sections.
 pub fn cmd_run(
     spec_path: &str,
     format: &str,
     output: Option<&str>,
+    jobs: usize,
     verbose: u8,
+    config: &AppConfig,
 ) -> Result<bool, Box<dyn std::error::Error>> {
     let specs = load_specs(spec_path)?;
 
@@ -88,25 +125,37 @@ pub fn cmd_run(
         return Ok(true);
     }
 
+    let jobs = jobs.clamp(1, specs.len());
     if verbose > 0 {
-        eprintln!("Running {} test specs...", specs.len());
+        eprintln!("Running {} test specs on {} worker(s)...", specs.len(), jobs);
     }
 
-    let mut session = BrowserSession::launch()?;
     let start = std::time::Instant::now();
 
-    let runner_config = RunnerConfig::default();
-    let mut results = Vec::new();
-    for spec in &specs {
-        if verbose > 0 {
-            eprintln!("  Running: {}", spec.name);
+    let mut runner_config = config.run.runner_config();
+    let mut session_config = config.session.clone();
+    if specs.iter().any(|s| s.auth) {
+        let path = prepare_storage_state(&config.auth, &session_config, verbose)?;
+        // Crash recovery should come back logged in, too
+        session_config.restore_storage_state.get_or_insert_with(|| path.clone());
+        runner_config.storage_state = Some(path);
+    }
+
+    let results = run_pool(&specs, jobs, &runner_config, |worker| {
+        if verbose > 1 {
+            eprintln!("  Launching browser session for worker {}", worker);
+        }
+        BrowserSession::launch_with(session_config.clone())
+    })?;
+
+    if verbose > 0 {
+        for result in &results {
+            let status = if result.passed { "passed" } else { "FAILED" };
+            eprintln!("  {}: {}", result.spec_name, status);
         }
-        let result = TestRunner::run_with_config(spec, &mut session, &runner_config);
-        results.push(result);
     }
 
     let duration = start.elapsed().as_millis();
-    session.quit()?;
 
     let report = TestSuiteReport::from_results("CLI Run", results).with_duration(duration);
     let all_passed = report.all_passed();
@@ -127,7 +176,32 @@ pub fn cmd_run(
     Ok(all_passed)
 }
 
+/// Make sure `auth.storage_state` holds a valid logged-in state for `auth: true`
+/// specs, logging in once through the form if it is missing or expired.
+fn prepare_storage_state(
+    auth: &AuthConfig,
+    session_config: &SessionConfig,
+    verbose: u8,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let path = auth.storage_state.clone().ok_or(
+        "Specs declare `auth: true` but auth.storage_state is not set in the config file",
+    )?;
+    if verbose > 0 {
+        eprintln!("Authenticating (storage state: {})...", path);
+    }
+    let mut session = BrowserSession::launch_with(session_config.clone())?;
+    let logged_in = authenticate(&mut session, auth, &MockPageAnalyzer)?;
+    session.quit()?;
+    if !logged_in {
+        return Err("Specs declare `auth: true` but auth.login_url / auth.credentials are not set".into());
+    }
+    Ok(path)
+}
+
 /// Load test specs from a single YAML file or a directory of YAML files.
+///
+/// Relative mock `fixture` paths are resolved against the spec file's
+/// directory, so specs run the same from any working directory.
 pub fn load_specs(path: &str) -> Result<Vec<TestSpec>, Box<dyn std::error::Error>> {
     let metadata = std::fs::metadata(path)?;
     if metadata.is_dir() {
@@ -136,47 +210,38 @@ pub fn load_specs(path: &str) -> Result<Vec<TestSpec>, Box<dyn std::error::Error
             let entry = entry?;
             let p = entry.path();
             if p.extension().map_or(false, |e| e == "yaml" || e == "yml") {
-                let content = std::fs::read_to_string(&p)?;
-                let spec: TestSpec = serde_yaml::from_str(&content)?;
-                specs.push(spec);
+                specs.push(load_spec(&p)?);
             }
         }
         // Sort by name for deterministic order
         specs.sort_by(|a, b| a.name.cmp(&b.name));
         Ok(specs)
     } else {
-        let content = std::fs::read_to_string(path)?;
-        let spec: TestSpec = serde_yaml::from_str(&content)?;
-        Ok(vec![spec])
+        Ok(vec![load_spec(std::path::Path::new(path))?])
+    }
+}
+
+fn load_spec(path: &std::path::Path) -> Result<TestSpec, Box<dyn std::error::Error>> {
+    let content = std::fs::read_to_string(path)?;
+    let mut spec: TestSpec = serde_yaml::from_str(&content)?;
+    if let Some(dir) = path.parent() {
+        for mock in &mut spec.mocks {
+            mock.rebase_fixture(dir);
+        }
     }
+    Ok(spec)
 }
 
 // ============================================================================
 // generate subcommand
 // ============================================================================
 
-pub fn cmd_generate(
-    url: &str,
-    output_dir: &str,
-    max_pages: usize,
-    max_depth: usize,
-    explore_forms: bool,
-    max_forms_per_page: usize,
-    analyzer_name: &str,
-    verbose: u8,
-    ollama_endpoint: Option<&str>,
-    ollama_model: Option<&str>,
-) -> Result<(), Box<dyn std::error::Error>> {
-    let config = build_explorer_config(url, max_pages, max_depth, explore_forms, max_forms_per_page);
-    let analyzer = build_analyzer(analyzer_name, ollama_endpoint, ollama_model)?;
-    let mut session = BrowserSession::launch()?;
-
-    if verbose > 0 {
-        eprintln!("Exploring {} for test generation...", url);
+pub fn cmd_generate(opts: &ExploreOptions, output_dir: &str) -> Result<(), Box<dyn std::error::Error>> {
+    if opts.verbose > 0 {
+        eprintln!("Exploring {} for test generation...", opts.url);
     }
 
-    let app_map = explore_live(&config, &mut session, analyzer.as_ref(), None, None, None)?;
-    session.quit()?;
+    let app_map = opts.explore()?;
 
     let specs = generate_test_plan(&app_map, None, None);
 
@@ -189,7 +254,7 @@ pub fn cmd_generate(
         let path = std::path::Path::new(output_dir).join(&filename);
         let yaml = serde_yaml::to_string(spec)?;
         std::fs::write(&path, &yaml)?;
-        if verbose > 0 {
+        if opts.verbose > 0 {
             eprintln!("  Wrote: {}", path.display());
         }
     }
@@ -198,6 +263,73 @@ pub fn cmd_generate(
     Ok(())
 }
 
+// ============================================================================
+// record subcommand
+// ============================================================================
+
+/// Record the user's interactions in a headed browser until Enter is pressed
+/// in the terminal, then write them as a spec (to stdout without `output`).
+pub fn cmd_record(
+    url: &str,
+    output: Option<&str>,
+    name: Option<&str>,
+    verbose: u8,
+    session_config: &SessionConfig,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut session_config = session_config.clone();
+    session_config.options.headed = Some(true);
+    let mut session = BrowserSession::launch_with(session_config)?;
+
+    let stopped = Arc::new(AtomicBool::new(false));
+    let flag = Arc::clone(&stopped);
+    std::thread::spawn(move || {
+        let mut line = String::new();
+        let _ = std::io::stdin().read_line(&mut line);
+        flag.store(true, Ordering::SeqCst);
+    });
+
+    eprintln!("Recording {} — interact with the browser, then press Enter here to stop.", url);
+    let spec = record_session(
+        &mut session,
+        url,
+        name,
+        Duration::from_millis(250),
+        &mut || stopped.load(Ordering::SeqCst),
+    )?;
+    session.quit()?;
+
+    if verbose > 0 {
+        eprintln!("Recorded {} steps", spec.steps.len());
+    }
+
+    let yaml = serde_yaml::to_string(&spec)?;
+    match output {
+        Some(path) => {
+            std::fs::write(path, &yaml)?;
+            println!("Wrote recorded spec to {}", path);
+        }
+        None => print!("{}", yaml),
+    }
+    Ok(())
+}
+
+// ============================================================================
+// doctor subcommand
+// ============================================================================
+
+/// Print the environment checks and return whether all passed.
+pub fn cmd_doctor(session_config: &SessionConfig) -> bool {
+    let checks = run_checks(session_config);
+    for check in &checks {
+        let status = if check.ok { "ok" } else { "FAIL" };
+        println!("[{:>4}] {}: {}", status, check.name, check.detail);
+        if let Some(fix) = &check.fix {
+            println!("       fix: {}", fix);
+        }
+    }
+    checks.iter().all(|c| c.ok)
+}
+
 // ============================================================================
 // Helpers
 // ============================================================================
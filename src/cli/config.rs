@@ -3,6 +3,10 @@ use std::collections::HashMap;
 use clap::{Parser, Subcommand};
 use serde::{Deserialize, Serialize};
 
+use crate::browser::routes::RouteMock;
+use crate::browser::session::SessionConfig;
+use crate::spec::runner_config::ArtifactMode;
+
 // ============================================================================
 // CLI Argument Parsing (clap derive)
 // ============================================================================
@@ -76,6 +80,10 @@ pub enum Commands {
         /// Output file path (default: stdout for console, report.html / report.xml for others)
         #[arg(short, long)]
         output: Option<String>,
+
+        /// Number of specs to run in parallel, one browser session each (default: 1)
+        #[arg(short, long)]
+        jobs: Option<usize>,
     },
 
     /// Explore a site and generate test spec YAML files
@@ -108,6 +116,24 @@ pub enum Commands {
         #[arg(long, default_value = "mock")]
         analyzer: String,
     },
+
+    /// Record interactions in a headed browser as a test spec YAML file
+    Record {
+        /// URL to start recording from
+        #[arg(long)]
+        url: String,
+
+        /// Output file for the recorded spec (default: stdout)
+        #[arg(short, long)]
+        output: Option<String>,
+
+        /// Test name (default: "Recorded: <page title>")
+        #[arg(long)]
+        name: Option<String>,
+    },
+
+    /// Check that Node.js, the helper scripts, Playwright and Chromium are usable
+    Doctor,
 }
 
 // ============================================================================
@@ -144,6 +170,12 @@ pub struct AuthConfig {
     /// Text expected to appear on the page after successful login
     #[serde(default)]
     pub success_text: Option<String>,
+
+    /// Storage-state file (cookies + localStorage) reused across runs.
+    /// Loaded instead of filling the login form while it is still valid;
+    /// rewritten after every successful form login.
+    #[serde(default)]
+    pub storage_state: Option<String>,
 }
 
 impl AuthConfig {
@@ -278,6 +310,9 @@ pub struct AppConfig {
     /// URL skip patterns and force-include URLs.
     #[serde(default)]
     pub exclusions: ExclusionConfig,
+    /// Browser session timeouts and crash recovery.
+    #[serde(default)]
+    pub session: SessionConfig,
 }
 
 impl Default for AppConfig {
@@ -289,6 +324,7 @@ impl Default for AppConfig {
             auth: AuthConfig::default(),
             values: ValueConfig::default(),
             exclusions: ExclusionConfig::default(),
+            session: SessionConfig::default(),
         }
     }
 }
@@ -309,6 +345,10 @@ pub struct ExploreConfig {
 
     #[serde(default = "default_mock")]
     pub analyzer: String,
+
+    /// Canned responses for matching requests while exploring
+    #[serde(default)]
+    pub mocks: Vec<RouteMock>,
 }
 
 impl Default for ExploreConfig {
@@ -319,6 +359,7 @@ impl Default for ExploreConfig {
             explore_forms: true,
             max_forms_per_page: 3,
             analyzer: "mock".to_string(),
+            mocks: Vec::new(),
         }
     }
 }
@@ -345,6 +386,27 @@ pub struct RunConfig {
     /// Directory for screenshots (default: "screenshots")
     #[serde(default = "default_screenshot_dir")]
     pub screenshot_dir: String,
+
+    /// Parallel workers, each with its own browser session (default: 1)
+    #[serde(default = "default_one")]
+    pub jobs: usize,
+
+    /// Write a HAR file of each test's network traffic (default: false)
+    #[serde(default)]
+    pub record_har: bool,
+
+    /// Keep a Playwright trace zip per test: off, always, on_failure or
+    /// on_retry (default: off)
+    #[serde(default)]
+    pub record_trace: ArtifactMode,
+
+    /// Keep a video per test: off, always, on_failure or on_retry (default: off)
+    #[serde(default)]
+    pub record_video: ArtifactMode,
+
+    /// Fail a test when the page throws an uncaught exception (default: false)
+    #[serde(default)]
+    pub fail_on_page_error: bool,
 }
 
 impl Default for RunConfig {
@@ -356,6 +418,28 @@ impl Default for RunConfig {
             retry_delay_ms: 500,
             screenshot_on_failure: true,
             screenshot_dir: "screenshots".to_string(),
+            jobs: 1,
+            record_har: false,
+            record_trace: ArtifactMode::Off,
+            record_video: ArtifactMode::Off,
+            fail_on_page_error: false,
+        }
+    }
+}
+
+impl RunConfig {
+    /// Runner settings from the `run:` section of the config file.
+    pub fn runner_config(&self) -> crate::spec::runner_config::RunnerConfig {
+        crate::spec::runner_config::RunnerConfig {
+            max_assertion_retries: self.max_retries,
+            retry_delay_ms: self.retry_delay_ms,
+            screenshot_on_failure: self.screenshot_on_failure,
+            screenshot_dir: self.screenshot_dir.clone(),
+            record_har: self.record_har,
+            record_trace: self.record_trace,
+            record_video: self.record_video,
+            fail_on_page_error: self.fail_on_page_error,
+            storage_state: None,
         }
     }
 }
@@ -372,6 +456,7 @@ fn default_three() -> usize { 3 }
 fn default_true() -> bool { true }
 fn default_mock() -> String { "mock".to_string() }
 fn default_console() -> String { "console".to_string() }
+fn default_one() -> usize { 1 }
 fn default_two() -> usize { 2 }
 fn default_500() -> u64 { 500 }
 fn default_screenshot_dir() -> String { "screenshots".to_string() }
@@ -408,5 +493,6 @@ pub fn build_explorer_config(
         same_origin_only: true,
         explore_forms,
         max_forms_per_page,
+        mocks: Vec::new(),
     }
 }
@@ -3,6 +3,8 @@ use std::collections::HashMap;
 use serde::{Deserialize, Serialize};
 
 use crate::agent::page_model::PageModel;
+use crate::browser::dialogs::DialogEntry;
+use crate::browser::routes::RouteMock;
 
 // ============================================================================
 // Explorer configuration
@@ -33,6 +35,11 @@ pub struct ExplorerConfig {
     /// Maximum number of forms to submit per page (default 3)
     #[serde(default = "default_three")]
     pub max_forms_per_page: usize,
+
+    /// Canned responses installed before exploring, typically to stub
+    /// third-party calls (analytics, payment providers)
+    #[serde(default)]
+    pub mocks: Vec<RouteMock>,
 }
 
 fn default_true() -> bool {
@@ -52,6 +59,7 @@ impl Default for ExplorerConfig {
             same_origin_only: true,
             explore_forms: true,
             max_forms_per_page: 3,
+            mocks: Vec::new(),
         }
     }
 }
@@ -116,6 +124,10 @@ pub struct Transition {
     /// How this transition was triggered (defaults to Link for backward compat)
     #[serde(default)]
     pub kind: TransitionKind,
+
+    /// JavaScript dialogs opened on the way (dismissed by the explorer)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub dialogs: Vec<DialogEntry>,
 }
 
 /// A single step in a detected multi-page flow.
@@ -1,12 +1,14 @@
 use std::collections::{HashMap, VecDeque};
 
 use crate::agent::app_context::AppContext;
-use crate::agent::data_generator::DataGenerator;
+use crate::agent::data_generator::{DataGenerator, upload_files};
 use crate::agent::error::AgentError;
 use crate::agent::page_analyzer::{MockPageAnalyzer, PageAnalyzer};
 use crate::agent::page_model::{FieldAnalysis, FieldType, FormModel};
 use crate::browser::playwright::SelectorHint;
-use crate::browser::session::BrowserSession;
+use crate::browser::backend::BrowserBackend;
+use crate::browser::tabs::TabSelector;
+pub use crate::browser::url::{extract_origin, is_same_origin, resolve_url};
 use crate::cli::config::{AuthConfig, ExclusionConfig, ValueConfig};
 use crate::state::state_model::ScreenState;
 
@@ -49,33 +51,51 @@ pub fn explore(config: &ExplorerConfig, screen: &ScreenState) -> Result<AppMap,
 }
 
 // ============================================================================
-// Live multi-page BFS exploration (requires BrowserSession)
+// Live multi-page BFS exploration (requires a BrowserBackend)
 // ============================================================================
 
-/// Multi-page BFS exploration using a live `BrowserSession`.
+/// Multi-page BFS exploration using a live `BrowserBackend`.
 ///
 /// Starting from `config.start_url`, navigates to each page, extracts DOM,
 /// analyzes with `PageAnalyzer`, discovers links from `NavigationTargets`,
 /// and follows them up to `max_pages` / `max_depth`.
 ///
+/// Links are followed by URL, so `target=_blank` links become ordinary
+/// `Transition`s. A form submission that opens a new tab (popup) is followed
+/// into that tab, recorded as the submission's result, and the tab is then
+/// closed so exploration continues in the original one.
+///
+/// `config.mocks` are installed as routes before anything else, so stubbed
+/// third-party calls never reach the network (including during login).
+///
+/// JavaScript dialogs are left to the session's policy (dismissed by
+/// default, so a guarded delete is cancelled) and recorded on the
+/// `Transition` that opened them: a form submission, or the first link
+/// leading to a page that opens one while loading.
+///
 /// Optional parameters:
-/// - `auth`: if provided and has credentials, performs auto-login before BFS
+/// - `auth`: if provided and has credentials, authenticates before BFS
+///   (reusing `auth.storage_state` when still valid, see `authenticate`)
 /// - `exclusions`: URL patterns to skip + extra URLs to seed into the queue
 /// - `value_overrides`: custom field values overriding suggested test values
 ///
 /// Uses `snapshot_session()` from `crate` to extract and classify each page.
 pub fn explore_live(
     config: &ExplorerConfig,
-    session: &mut BrowserSession,
+    session: &mut dyn BrowserBackend,
     analyzer: &dyn PageAnalyzer,
     auth: Option<&AuthConfig>,
     exclusions: Option<&ExclusionConfig>,
     value_overrides: Option<&ValueConfig>,
 ) -> Result<AppMap, Box<dyn std::error::Error>> {
+    for mock in &config.mocks {
+        session.route(mock)?;
+    }
+
     // Auto-login before BFS if credentials are configured
     if let Some(auth_cfg) = auth {
         if auth_cfg.has_credentials() {
-            perform_login(session, auth_cfg, analyzer)?;
+            authenticate(session, auth_cfg, analyzer)?;
         }
     }
     let mut app_map = AppMap::new();
@@ -123,6 +143,14 @@ pub fn explore_live(
         // Navigate and snapshot
         session.navigate(&url)?;
         let (screen_state, _canonical) = crate::snapshot_session(session)?;
+        let load_dialogs = session.take_dialogs().unwrap_or_default();
+        if let Some(link) = app_map
+            .transitions
+            .iter_mut()
+            .find(|t| t.to_url == url && t.kind == TransitionKind::Link)
+        {
+            link.dialogs.extend(load_dialogs);
+        }
 
         // Analyze page — pass accumulated context for domain-aware LLM prompts
         let model = analyzer.analyze_with_context(&screen_state, &app_context)?;
@@ -138,6 +166,7 @@ pub fn explore_live(
                     to_url: resolved.clone(),
                     label: target.label.clone(),
                     kind: TransitionKind::Link,
+                    dialogs: Vec::new(),
                 });
                 if !app_map.has_page(&resolved) {
                     queue.push_back((resolved, depth + 1));
@@ -162,6 +191,7 @@ pub fn explore_live(
 
                 // Navigate back (form submit may have changed the page)
                 session.navigate(&url)?;
+                let _ = session.take_dialogs();
 
                 // Build field_analyses lookup: lowercase_label → FieldAnalysis
                 let field_analyses: HashMap<String, &FieldAnalysis> = model
@@ -188,7 +218,10 @@ pub fn explore_live(
                     .collect();
 
                 // Fill and submit using the computed values — graceful: skip on failure
+                let tabs_before = session.tabs().map(|t| t.len()).unwrap_or(1);
                 if submit_form_in_session(session, form, &values).is_ok() {
+                    let dialogs = session.take_dialogs().unwrap_or_default();
+                    let in_popup = switch_to_new_tab(session, tabs_before);
                     let result_url = session
                         .current_url()
                         .unwrap_or_else(|_| url.clone());
@@ -214,6 +247,7 @@ pub fn explore_live(
                                 form_id: form.form_id.clone(),
                                 values,
                             },
+                            dialogs,
                         });
 
                         // Add result page if new — use context-aware analysis
@@ -229,6 +263,10 @@ pub fn explore_live(
                             queue.push_back((result_page_url, depth + 1));
                         }
                     }
+
+                    if in_popup {
+                        let _ = session.close_tab(&TabSelector::default());
+                    }
                 }
             }
         }
@@ -246,6 +284,17 @@ pub fn explore_live(
     Ok(app_map)
 }
 
+/// If an action opened a tab (the browser now has more than `tabs_before`),
+/// switch to the newest one. Returns whether a switch happened.
+fn switch_to_new_tab(session: &mut dyn BrowserBackend, tabs_before: usize) -> bool {
+    match session.tabs() {
+        Ok(tabs) if tabs.len() > tabs_before => {
+            session.switch_tab(&TabSelector::default()).is_ok()
+        }
+        _ => false,
+    }
+}
+
 // ============================================================================
 // Form submission helper
 // ============================================================================
@@ -268,6 +317,7 @@ pub fn build_selector_for_field(field: &crate::agent::page_model::FieldModel, fo
         FieldType::Date => (None, Some("input".into()), Some("date".into())),
         FieldType::Time => (None, Some("input".into()), Some("time".into())),
         FieldType::Url => (Some("textbox".into()), Some("input".into()), Some("url".into())),
+        FieldType::File => (None, Some("input".into()), Some("file".into())),
         _ => (Some("textbox".into()), Some("input".into()), None),
     };
 
@@ -277,10 +327,33 @@ pub fn build_selector_for_field(field: &crate::agent::page_model::FieldModel, fo
         tag,
         input_type,
         form_id: Some(form_id.to_string()),
+        frame_path: Vec::new(),
+        shadow_path: Vec::new(),
+    }
+}
+
+/// `build_selector_for_field` in the frame the form lives in.
+fn field_selector(field: &crate::agent::page_model::FieldModel, form: &FormModel) -> SelectorHint {
+    SelectorHint {
+        frame_path: form.frame_path.clone(),
+        ..build_selector_for_field(field, &form.form_id)
     }
 }
 
-/// Fill and submit a form via BrowserSession using pre-computed field values.
+/// SelectorHint for the button submitting `form`, looked up in the form's frame.
+fn submit_selector(label: &str, form: &FormModel) -> SelectorHint {
+    SelectorHint {
+        role: Some("button".into()),
+        name: Some(label.into()),
+        tag: None,
+        input_type: None,
+        form_id: None,
+        frame_path: form.frame_path.clone(),
+        shadow_path: Vec::new(),
+    }
+}
+
+/// Fill and submit a form via a BrowserBackend using pre-computed field values.
 ///
 /// `values` is a label→value map produced by `DataGenerator` (or value_overrides).
 /// Falls back to `field.suggested_test_value` when a field's label is not in `values`.
@@ -288,7 +361,7 @@ pub fn build_selector_for_field(field: &crate::agent::page_model::FieldModel, fo
 /// the submit button. Skips hidden fields. Returns Ok(()) on success, Err on
 /// browser action failure.
 fn submit_form_in_session(
-    session: &mut BrowserSession,
+    session: &mut dyn BrowserBackend,
     form: &FormModel,
     values: &HashMap<String, String>,
 ) -> Result<(), AgentError> {
@@ -304,23 +377,17 @@ fn submit_form_in_session(
             .map(|v| v.as_str())
             .unwrap_or(&field.suggested_test_value);
 
-        let selector = build_selector_for_field(field, &form.form_id);
+        let selector = field_selector(field, form);
         match field.field_type {
             FieldType::Select => session.select_option(&selector, value)?,
             FieldType::Checkbox | FieldType::Radio => session.check(&selector)?,
+            FieldType::File => session.set_input_files(&selector, &upload_files(value)?)?,
             _ => session.fill(&selector, value)?,
         }
     }
 
     if let Some(label) = &form.submit_label {
-        let selector = SelectorHint {
-            role: Some("button".into()),
-            name: Some(label.clone()),
-            tag: None,
-            input_type: None,
-            form_id: None,
-        };
-        session.click(&selector)?;
+        session.click(&submit_selector(label, form))?;
     }
 
     session.wait_idle(500)?;
@@ -339,7 +406,7 @@ fn submit_form_in_session(
 /// Returns `Ok(true)` if login was performed, `Ok(false)` if skipped
 /// (no credentials or no login URL), `Err` on failure.
 pub fn perform_login(
-    session: &mut BrowserSession,
+    session: &mut dyn BrowserBackend,
     auth: &AuthConfig,
     analyzer: &dyn PageAnalyzer,
 ) -> Result<bool, Box<dyn std::error::Error>> {
@@ -366,7 +433,7 @@ pub fn perform_login(
             continue;
         }
         if let Some(cred_value) = auth.credentials.get(&field.label) {
-            let selector = build_selector_for_field(field, &form.form_id);
+            let selector = field_selector(field, form);
             session.fill(&selector, cred_value).map_err(|e| {
                 AgentError::BrowserAction(format!("Login fill '{}' failed: {}", field.label, e))
             })?;
@@ -379,15 +446,8 @@ pub fn perform_login(
         .as_deref()
         .or_else(|| form.submit_label.as_deref());
     if let Some(label) = submit_label {
-        let selector = SelectorHint {
-            role: Some("button".into()),
-            name: Some(label.into()),
-            tag: None,
-            input_type: None,
-            form_id: None,
-        };
         session
-            .click(&selector)
+            .click(&submit_selector(label, form))
             .map_err(|e| AgentError::BrowserAction(format!("Login submit failed: {}", e)))?;
     }
 
@@ -411,62 +471,64 @@ pub fn perform_login(
     Ok(true)
 }
 
-// ============================================================================
-// URL utilities
-// ============================================================================
+/// Authenticate, preferring a saved storage state over the login form.
+///
+/// If `auth.storage_state` names an existing file, it is loaded and the login
+/// page is revisited: a session that is still valid passes `is_logged_in` and
+/// no form is filled. Otherwise (no file, or the state expired) this falls back
+/// to `perform_login` and, on success, saves the fresh state to that path.
+///
+/// Returns `Ok(true)` if the session is authenticated, `Ok(false)` if skipped.
+pub fn authenticate(
+    session: &mut dyn BrowserBackend,
+    auth: &AuthConfig,
+    analyzer: &dyn PageAnalyzer,
+) -> Result<bool, Box<dyn std::error::Error>> {
+    if let Some(path) = &auth.storage_state
+        && std::path::Path::new(path).exists()
+    {
+        session.load_storage_state(path)?;
+        if is_logged_in(session, auth)? {
+            return Ok(true);
+        }
+    }
 
-/// Check if two URLs share the same origin (scheme + host).
-pub fn is_same_origin(base: &str, candidate: &str) -> bool {
-    match (extract_origin(base), extract_origin(candidate)) {
-        (Some(a), Some(b)) => a == b,
-        _ => false,
+    let logged_in = perform_login(session, auth, analyzer)?;
+    if logged_in && let Some(path) = &auth.storage_state {
+        if let Some(dir) = std::path::Path::new(path).parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        session.save_storage_state(path)?;
     }
+    Ok(logged_in)
 }
 
-/// Extract the origin (scheme + host) from a URL.
+/// Check whether the session is already authenticated by visiting the login page.
 ///
-/// Example: `"https://example.com/path/page"` → `"https://example.com"`
-pub fn extract_origin(url: &str) -> Option<String> {
-    let after_scheme = url.find("://").map(|i| i + 3)?;
-    let end = url[after_scheme..]
-        .find('/')
-        .map(|i| after_scheme + i)
-        .unwrap_or(url.len());
-    Some(url[..end].to_string())
-}
+/// Uses `success_url_contains` (logged-in users are usually redirected away
+/// from the login page) or `success_text` when configured; otherwise treats
+/// the absence of a password field as logged in.
+pub fn is_logged_in(
+    session: &mut dyn BrowserBackend,
+    auth: &AuthConfig,
+) -> Result<bool, AgentError> {
+    let Some(login_url) = &auth.login_url else {
+        return Ok(false);
+    };
+    session.navigate(login_url)?;
 
-/// Resolve a navigation candidate against a base URL.
-///
-/// Handles absolute URLs, protocol-relative URLs, root-relative paths,
-/// and relative paths. Filters out `javascript:`, `mailto:`, `tel:`,
-/// empty strings, and bare `#` fragments.
-pub fn resolve_url(base: &str, candidate: &str) -> Option<String> {
-    let trimmed = candidate.trim();
-    if trimmed.is_empty()
-        || trimmed == "#"
-        || trimmed.starts_with("javascript:")
-        || trimmed.starts_with("mailto:")
-        || trimmed.starts_with("tel:")
-    {
-        return None;
-    }
-    // Absolute URL — pass through
-    if trimmed.starts_with("http://") || trimmed.starts_with("https://") {
-        return Some(trimmed.to_string());
-    }
-    // Protocol-relative — inherit scheme from base
-    if trimmed.starts_with("//") {
-        let scheme = base.split("://").next().unwrap_or("https");
-        return Some(format!("{}:{}", scheme, trimmed));
+    if let Some(expected) = &auth.success_url_contains {
+        return Ok(session.current_url()?.contains(expected.as_str()));
     }
-    // Relative: resolve against base origin
-    let origin = extract_origin(base)?;
-    if trimmed.starts_with('/') {
-        // Root-relative: /path
-        Some(format!("{}{}", origin, trimmed))
-    } else {
-        // Relative path: resolve against base directory
-        let base_dir = base.rfind('/').map(|i| &base[..i + 1]).unwrap_or(base);
-        Some(format!("{}{}", base_dir, trimmed))
+    let data = session.extract()?;
+    let dom = data["dom"].as_array().cloned().unwrap_or_default();
+    if let Some(expected) = &auth.success_text {
+        let expected = expected.to_lowercase();
+        return Ok(dom.iter().any(|el| {
+            el["text"]
+                .as_str()
+                .is_some_and(|t| t.to_lowercase().contains(&expected))
+        }));
     }
+    Ok(!dom.iter().any(|el| el["type"].as_str() == Some("password")))
 }
@@ -84,6 +84,9 @@ pub fn generate_smoke_test(url: &str, model: &PageModel) -> TestSpec {
         name: format!("Smoke: {}", model.purpose),
         start_url: url.to_string(),
         steps,
+        options: None,
+        auth: false,
+        mocks: Vec::new(),
     }
 }
 
@@ -145,6 +148,7 @@ fn build_form_test_spec(url: &str, form: &FormModel, model: &PageModel, values:
         form: form.form_id.clone(),
         values,
         submit_label: form.submit_label.clone(),
+        frame_path: form.frame_path.clone(),
     };
 
     let mut steps = vec![fill_step, TestStep::Wait { duration_ms: 1000 }];
@@ -176,11 +180,14 @@ fn build_form_test_spec(url: &str, form: &FormModel, model: &PageModel, values:
         name: format!("Form: {} on {}", form.purpose, model.purpose),
         start_url: url.to_string(),
         steps,
+        options: None,
+        auth: false,
+        mocks: Vec::new(),
     }
 }
 
 /// Extract the path component from a URL (e.g. `"/login"` from `"https://example.com/login"`).
-fn extract_url_path(url: &str) -> Option<String> {
+pub(crate) fn extract_url_path(url: &str) -> Option<String> {
     let after_scheme = url.find("://").map(|i| i + 3)?;
     let slash_pos = url[after_scheme..].find('/').map(|i| after_scheme + i)?;
     let path = &url[slash_pos..];
@@ -233,6 +240,7 @@ pub fn generate_flow_tests(flows: &[Flow]) -> Vec<TestSpec> {
                         form: form_id.clone(),
                         values: values.clone(),
                         submit_label: submit_label.clone(),
+                        frame_path: Vec::new(),
                     },
                 })
                 .collect();
@@ -241,6 +249,9 @@ pub fn generate_flow_tests(flows: &[Flow]) -> Vec<TestSpec> {
                 name: flow.name.clone(),
                 start_url,
                 steps,
+                options: None,
+                auth: false,
+                mocks: Vec::new(),
             }
         })
         .collect()
@@ -4,6 +4,7 @@ use crate::{
         agent_model::AgentState,
     },
     browser::{
+        backend::BrowserBackend,
         playwright::extract_screen,
         session::BrowserSession,
     },
@@ -12,7 +13,7 @@ use crate::{
         diff::semantic_diff,
     },
     screen::{classifier::classify, screen_model::{DomElement, StructuralOutline}},
-    state::{diff::diff, state_builder::build_state},
+    state::{diff::diff, state_builder::build_state_with_outline},
     trace::logger::TraceLogger,
 };
 
@@ -95,8 +96,13 @@ fn snapshot(url: &str) -> Result<(crate::state::state_model::ScreenState, Canoni
         .ok_or("DOM extraction returned no 'dom' array")?;
     let elements: Vec<DomElement> = serde_json::from_value(dom.clone().into())?;
 
+    let structural_outline: StructuralOutline = raw.get("structural_outline")
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .unwrap_or_default();
+
     let semantics = classify(&elements);
-    let screen_state = build_state(Some(url), raw["title"].as_str().unwrap_or(""), semantics);
+    let screen_state =
+        build_state_with_outline(Some(url), raw["title"].as_str().unwrap_or(""), semantics, structural_outline);
     let identity_diff = diff(&screen_state, &screen_state); // self-diff for canonicalization
     let canonical = canonicalize(&screen_state, Some(&identity_diff));
 
@@ -107,9 +113,9 @@ fn snapshot(url: &str) -> Result<(crate::state::state_model::ScreenState, Canoni
 // Session-based variants (persistent browser, multi-page flows)
 // =========================================================================
 
-/// Take a snapshot using a persistent BrowserSession.
+/// Take a snapshot using a persistent browser (any `BrowserBackend`).
 pub fn snapshot_session(
-    session: &mut BrowserSession,
+    session: &mut dyn BrowserBackend,
 ) -> Result<(crate::state::state_model::ScreenState, CanonicalScreenState), Box<dyn std::error::Error>> {
     let raw = session.extract()?;
     let dom = raw["dom"]
@@ -125,8 +131,7 @@ pub fn snapshot_session(
         .unwrap_or_default();
 
     let semantics = classify(&elements);
-    let mut screen_state = build_state(Some(url_str), title_str, semantics);
-    screen_state.structural_outline = structural_outline;
+    let screen_state = build_state_with_outline(Some(url_str), title_str, semantics, structural_outline);
     let identity_diff = diff(&screen_state, &screen_state);
     let canonical = canonicalize(&screen_state, Some(&identity_diff));
 
@@ -1,5 +1,7 @@
 use clap::Parser;
-use screen_detection::cli::commands::{cmd_explore, cmd_generate, cmd_run};
+use screen_detection::cli::commands::{
+    ExploreOptions, cmd_doctor, cmd_explore, cmd_generate, cmd_record, cmd_run,
+};
 use screen_detection::cli::config::{Cli, Commands, load_config};
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -25,24 +27,37 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             max_forms_per_page,
             analyzer,
         } => {
-            cmd_explore(
-                &url,
+            cmd_explore(&ExploreOptions {
+                url: &url,
                 max_pages,
                 max_depth,
                 explore_forms,
                 max_forms_per_page,
-                &analyzer,
-                cli.verbose,
+                analyzer: &analyzer,
+                verbose: cli.verbose,
                 ollama_endpoint,
                 ollama_model,
-            )?;
+                session: &config.session,
+                auth: &config.auth,
+                mocks: &config.explore.mocks,
+            })?;
         }
         Commands::Run {
             spec,
             format,
             output,
+            jobs,
         } => {
-            let all_passed = cmd_run(&spec, &format, output.as_deref(), cli.verbose)?;
+            // Resolve worker count: CLI > config
+            let jobs = jobs.unwrap_or(config.run.jobs);
+            let all_passed = cmd_run(
+                &spec,
+                &format,
+                output.as_deref(),
+                jobs,
+                cli.verbose,
+                &config,
+            )?;
             if !all_passed {
                 std::process::exit(1);
             }
@@ -56,19 +71,36 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             max_forms_per_page,
             analyzer,
         } => {
-            cmd_generate(
-                &url,
-                &output_dir,
+            let opts = ExploreOptions {
+                url: &url,
                 max_pages,
                 max_depth,
                 explore_forms,
                 max_forms_per_page,
-                &analyzer,
-                cli.verbose,
+                analyzer: &analyzer,
+                verbose: cli.verbose,
                 ollama_endpoint,
                 ollama_model,
+                session: &config.session,
+                auth: &config.auth,
+                mocks: &config.explore.mocks,
+            };
+            cmd_generate(&opts, &output_dir)?;
+        }
+        Commands::Record { url, output, name } => {
+            cmd_record(
+                &url,
+                output.as_deref(),
+                name.as_deref(),
+                cli.verbose,
+                &config.session,
             )?;
         }
+        Commands::Doctor => {
+            if !cmd_doctor(&config.session) {
+                std::process::exit(1);
+            }
+        }
     }
 
     Ok(())
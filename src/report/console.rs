@@ -14,6 +14,9 @@ use crate::spec::spec_model::AssertionSpec;
 /// ✓ PASS  Test name 1 (3 steps, 2 assertions)
 /// ✗ FAIL  Test name 2 (5 steps, 4 assertions)
 ///     [FAIL] Step 2: TitleContains — expected "Login", actual "Home"
+/// ✗ FAIL  Test name 3 (2 steps, 0 assertions)
+///     [ERROR] Step 1 failed: Element 'Sign in' not found: ...
+///     [HINT] did you mean 'Sign-in'?
 ///
 /// === Results: 1 passed, 1 failed (2 total) ===
 /// ```
@@ -44,6 +47,9 @@ pub fn format_console_report(report: &TestSuiteReport) -> String {
         if let Some(ref error) = result.error {
             out.push_str(&format!("    [ERROR] {}\n", error));
         }
+        if let Some(hint) = result.did_you_mean() {
+            out.push_str(&format!("    [HINT] {}\n", hint));
+        }
 
         // Show retry info
         if result.retry_attempts > 0 {
@@ -99,5 +105,13 @@ fn format_assertion_type(spec: &AssertionSpec) -> &'static str {
         AssertionSpec::ElementVisible { .. } => "ElementVisible",
         AssertionSpec::ElementCount { .. } => "ElementCount",
         AssertionSpec::UrlNotContains { .. } => "UrlNotContains",
+        AssertionSpec::RequestMade { .. } => "RequestMade",
+        AssertionSpec::NoFailedRequests { .. } => "NoFailedRequests",
+        AssertionSpec::DialogShown { .. } => "DialogShown",
+        AssertionSpec::TableRows { .. } => "TableRows",
+        AssertionSpec::TableColumnContains { .. } => "TableColumnContains",
+        AssertionSpec::TableSorted { .. } => "TableSorted",
+        AssertionSpec::ModalOpen { .. } => "ModalOpen",
+        AssertionSpec::ToastShown { .. } => "ToastShown",
     }
 }
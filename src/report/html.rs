@@ -12,6 +12,7 @@ use crate::report::report_model::TestSuiteReport;
 /// - Each test case in its own section with per-test duration
 /// - Failed assertions highlighted in red
 /// - Screenshots on failure embedded as base64 data URIs
+/// - Links to the HAR, Playwright trace and video of each test, when kept
 /// - Inline CSS (no external dependencies)
 pub fn generate_html_report(report: &TestSuiteReport) -> String {
     let header_color = if report.all_passed() {
@@ -75,6 +76,9 @@ pub fn generate_html_report(report: &TestSuiteReport) -> String {
                 escape_html(error)
             ));
         }
+        if let Some(hint) = result.did_you_mean() {
+            test_cases.push_str(&format!("<p class=\"hint\">Hint: {}</p>\n", escape_html(&hint)));
+        }
 
         // Show failed assertions
         let failed: Vec<_> = result
@@ -106,6 +110,47 @@ pub fn generate_html_report(report: &TestSuiteReport) -> String {
             }
         }
 
+        // Link the network log (HAR) when one was recorded
+        if let Some(har_path) = &result.har_path {
+            test_cases.push_str(&format!(
+                "<p class=\"artifact\">Network log: <a href=\"{0}\">{0}</a></p>\n",
+                escape_html(har_path)
+            ));
+        }
+
+        // Link the Playwright trace and video when they were kept
+        if let Some(trace_path) = &result.trace_path {
+            test_cases.push_str(&format!(
+                "<p class=\"artifact\">Trace: <a href=\"{0}\">{0}</a> (open with <code>npx playwright show-trace</code>)</p>\n",
+                escape_html(trace_path)
+            ));
+        }
+        if let Some(video_path) = &result.video_path {
+            test_cases.push_str(&format!(
+                "<p class=\"artifact\">Video: <a href=\"{0}\">{0}</a></p>\n",
+                escape_html(video_path)
+            ));
+        }
+
+        // Console output, collapsed unless the page logged errors
+        if !result.console.is_empty() {
+            let errors = result.console.iter().filter(|e| e.is_error()).count();
+            test_cases.push_str(&format!(
+                "<details class=\"console\"{}><summary>Console: {} message(s), {} error(s)</summary>\n<ul>\n",
+                if errors > 0 { " open" } else { "" },
+                result.console.len(),
+                errors
+            ));
+            for entry in &result.console {
+                test_cases.push_str(&format!(
+                    "<li class=\"{}\">{}</li>\n",
+                    if entry.is_error() { "console-error" } else { "console-log" },
+                    escape_html(&entry.summary())
+                ));
+            }
+            test_cases.push_str("</ul>\n</details>\n");
+        }
+
         test_cases.push_str("</div>\n");
     }
 
@@ -128,12 +173,17 @@ body {{ font-family: -apple-system, BlinkMacSystemFont, "Segoe UI", Roboto, sans
 .test-case h3 {{ margin: 0 0 8px 0; font-size: 16px; }}
 .test-case p {{ margin: 4px 0; color: #666; font-size: 14px; }}
 .test-case .error {{ color: #f44336; font-weight: bold; }}
+.test-case .hint {{ color: #555; font-style: italic; }}
 .test-case .timing {{ color: #888; font-size: 13px; }}
 .failures {{ margin: 8px 0 0 0; padding-left: 20px; }}
 .failures li {{ color: #c62828; font-size: 13px; margin-bottom: 4px; }}
 .screenshot {{ margin: 12px 0; }}
 .screenshot img {{ max-width: 100%; border: 1px solid #ddd; border-radius: 4px; }}
 .screenshot p {{ font-size: 12px; color: #888; margin: 4px 0; }}
+.artifact {{ font-size: 12px; color: #888; margin: 4px 0; }}
+.console {{ margin: 8px 0 0 0; font-size: 13px; color: #666; }}
+.console ul {{ margin: 4px 0; padding-left: 20px; font-family: monospace; }}
+.console .console-error {{ color: #c62828; }}
 </style>
 </head>
 <body>
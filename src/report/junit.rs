@@ -1,4 +1,5 @@
 use crate::report::report_model::TestSuiteReport;
+use crate::spec::spec_model::TestResult;
 
 // ============================================================================
 // JUnit XML reporter — standard CI integration format
@@ -15,6 +16,7 @@ use crate::report::report_model::TestSuiteReport;
 ///     <failure message="1 assertion(s) failed" type="AssertionFailure">
 ///       Step 1: Title does not contain 'Login'
 ///     </failure>
+///     <system-err>[step 0] uncaught exception: x is undefined</system-err>
 ///   </testcase>
 /// </testsuite>
 /// ```
@@ -31,13 +33,22 @@ pub fn generate_junit_xml(report: &TestSuiteReport) -> String {
             .map(|ms| format!(" time=\"{:.3}\"", ms as f64 / 1000.0))
             .unwrap_or_default();
 
-        if result.passed {
+        let name = escape_xml(&result.spec_name);
+        let console = console_elements(result);
+
+        if result.passed && console.is_empty() {
             cases.push_str(&format!(
                 "  <testcase name=\"{}\" classname=\"screen-detection\"{} />\n",
-                escape_xml(&result.spec_name),
-                case_time
+                name, case_time
             ));
-        } else {
+            continue;
+        }
+
+        cases.push_str(&format!(
+            "  <testcase name=\"{}\" classname=\"screen-detection\"{}>\n",
+            name, case_time
+        ));
+        if !result.passed {
             // Collect failure details
             let failed_assertions: Vec<String> = result
                 .assertion_results
@@ -69,13 +80,13 @@ pub fn generate_junit_xml(report: &TestSuiteReport) -> String {
             };
 
             cases.push_str(&format!(
-                "  <testcase name=\"{name}\" classname=\"screen-detection\"{time}>\n    <failure message=\"{message}\" type=\"AssertionFailure\">{body}</failure>\n  </testcase>\n",
-                name = escape_xml(&result.spec_name),
-                time = case_time,
+                "    <failure message=\"{message}\" type=\"AssertionFailure\">{body}</failure>\n",
                 message = escape_xml(&failure_message),
                 body = escape_xml(&failure_body),
             ));
         }
+        cases.push_str(&console);
+        cases.push_str("  </testcase>\n");
     }
 
     format!(
@@ -88,6 +99,31 @@ pub fn generate_junit_xml(report: &TestSuiteReport) -> String {
     )
 }
 
+/// Console output of a test case: errors and uncaught exceptions go to
+/// `<system-err>`, everything else to `<system-out>`. Empty when nothing was logged.
+fn console_elements(result: &TestResult) -> String {
+    let (errors, output): (Vec<_>, Vec<_>) = result.console.iter().partition(|e| e.is_error());
+    let mut elements = String::new();
+    for (tag, entries) in [("system-out", output), ("system-err", errors)] {
+        if entries.is_empty() {
+            continue;
+        }
+        let lines: Vec<String> = entries
+            .iter()
+            .map(|e| match &e.stack {
+                Some(stack) => format!("{}\n{}", e.summary(), stack),
+                None => e.summary(),
+            })
+            .collect();
+        elements.push_str(&format!(
+            "    <{tag}>{}</{tag}>\n",
+            escape_xml(&lines.join("\n")),
+            tag = tag
+        ));
+    }
+    elements
+}
+
 /// Escape XML special characters.
 pub fn escape_xml(s: &str) -> String {
     s.replace('&', "&amp;")
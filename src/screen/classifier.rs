@@ -1,23 +1,67 @@
+use crate::browser::playwright::VIRTUAL_FORM_PREFIX;
 use crate::screen::intent::infer_form_intent;
+use crate::screen::overlay::overlay_of;
+use crate::screen::table::PageTables;
 use crate::screen::screen_model::{
-    DomElement, ElementKind, Form, FormIntent, ScreenElement, ScreenSemantics,
+    DomElement, ElementKind, Form, FormIntent, Overlay, ScreenElement, ScreenSemantics,
 };
 
 use std::collections::HashMap;
 
+/// Group extracted elements into forms, standalone actions and outputs.
+///
+/// Forms are keyed by frame as well as form id, so identically named forms in
+/// different iframes stay apart. Inputs and actions inside an iframe but
+/// outside any `<form>` (typical of embedded payment widgets) form one
+/// implicit form per frame, named after the frame (its last `frame_path` entry).
+///
+/// Inputs of the main frame outside any `<form>` (typical of SPAs) are
+/// grouped into virtual forms by landmark, section heading and fieldset
+/// legend. Buttons in the same place join them (by section when the button
+/// sits outside the fieldset); links and buttons elsewhere stay standalone.
+/// A virtual form's id is built from that context (`virtual:main-newsletter`,
+/// `virtual:page` without any), so it is stable across page loads.
+///
+/// Elements carrying table data (see `PageTables`) are also reported as
+/// tables, in page order. Elements of dialogs and live regions are tagged
+/// with their overlay (see `overlay_of`); the texts of one live region
+/// make up a single toast.
 pub fn classify(elements: &[DomElement]) -> ScreenSemantics {
-    let mut forms: HashMap<String, Form> = HashMap::new();
+    let mut forms: HashMap<(Vec<String>, String), Form> = HashMap::new();
     let mut standalone_actions = Vec::new();
     let mut outputs = Vec::new();
+    let mut tables = PageTables::default();
+    // Live region index -> position of its toast in `outputs`
+    let mut toasts: HashMap<usize, usize> = HashMap::new();
+
+    let virtual_forms = VirtualForms::from_orphan_inputs(elements);
 
     for el in elements {
+        tables.add(el);
         if is_output(el) {
-            outputs.push(to_output(el));
+            let output = to_output(el);
+            match el.live_region.filter(|_| output.overlay == Some(Overlay::Toast)) {
+                Some(region) if toasts.contains_key(&region) => {
+                    let toast: &mut ScreenElement = &mut outputs[toasts[&region]];
+                    toast.label = join_text(toast.label.take(), output.label);
+                }
+                Some(region) => {
+                    toasts.insert(region, outputs.len());
+                    outputs.push(output);
+                }
+                None => outputs.push(output),
+            }
             continue;
         }
 
-        if let Some(form_id) = &el.form_id {
-            let form = forms.entry(form_id.clone()).or_insert(Form {
+        let form_id = el
+            .form_id
+            .clone()
+            .or_else(|| frame_form_id(el))
+            .or_else(|| virtual_forms.form_for(el));
+        if let Some(form_id) = form_id {
+            let key = (el.frame_path.clone(), form_id.clone());
+            let form = forms.entry(key).or_insert(Form {
                 id: form_id.clone(),
                 inputs: vec![],
                 actions: vec![],
@@ -27,6 +71,7 @@ pub fn classify(elements: &[DomElement]) -> ScreenSemantics {
                     confidence: 0.0,
                     signals: vec![],
                 }),
+                frame_path: el.frame_path.clone(),
             });
 
             if is_input(el) {
@@ -57,10 +102,124 @@ pub fn classify(elements: &[DomElement]) -> ScreenSemantics {
         forms: forms.into_values().collect(),
         standalone_actions,
         outputs,
+        tables: tables.into_vec(),
         primary_action,
     }
 }
 
+/// Implicit form id for a form-less input or action inside an iframe.
+fn frame_form_id(el: &DomElement) -> Option<String> {
+    if is_input(el) || is_action(el) {
+        el.frame_path.last().cloned()
+    } else {
+        None
+    }
+}
+
+/// Where an element sits on the page: landmark, section heading, fieldset legend.
+#[derive(Debug, Clone, PartialEq)]
+struct Placement {
+    landmark: Option<String>,
+    heading: Option<String>,
+    legend: Option<String>,
+}
+
+impl Placement {
+    fn of(el: &DomElement) -> Self {
+        let clean = |s: &Option<String>| {
+            s.as_deref()
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(String::from)
+        };
+        Placement {
+            landmark: clean(&el.semantic_section),
+            heading: clean(&el.section_heading),
+            legend: clean(&el.fieldset_legend),
+        }
+    }
+
+    fn same_section(&self, other: &Placement) -> bool {
+        self.landmark == other.landmark && self.heading == other.heading
+    }
+
+    /// `virtual:` plus the slugged landmark, heading and legend.
+    fn form_id(&self) -> String {
+        let parts: Vec<String> = [&self.landmark, &self.heading, &self.legend]
+            .into_iter()
+            .flatten()
+            .map(|part| slug(part))
+            .filter(|part| !part.is_empty())
+            .collect();
+        if parts.is_empty() {
+            format!("{}page", VIRTUAL_FORM_PREFIX)
+        } else {
+            format!("{}{}", VIRTUAL_FORM_PREFIX, parts.join("-"))
+        }
+    }
+}
+
+/// Lowercase alphanumeric words joined by dashes ("Sign up!" -> "sign-up").
+pub(crate) fn slug(text: &str) -> String {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Placements of the main-frame inputs that have no `<form>`, in page order.
+struct VirtualForms {
+    placements: Vec<Placement>,
+}
+
+impl VirtualForms {
+    fn from_orphan_inputs(elements: &[DomElement]) -> Self {
+        let mut placements: Vec<Placement> = Vec::new();
+        for el in elements.iter().filter(|el| is_orphan(el) && is_input(el)) {
+            let placement = Placement::of(el);
+            if !placements.contains(&placement) {
+                placements.push(placement);
+            }
+        }
+        VirtualForms { placements }
+    }
+
+    /// Virtual form id for an orphan input, or for an orphan button placed
+    /// like one (same fieldset, else the last group of its section).
+    fn form_for(&self, el: &DomElement) -> Option<String> {
+        if !is_orphan(el) {
+            return None;
+        }
+        let placement = Placement::of(el);
+        if is_input(el) {
+            return Some(placement.form_id());
+        }
+        if !is_button(el) {
+            return None;
+        }
+        self.placements
+            .iter()
+            .find(|p| **p == placement)
+            .or_else(|| {
+                placement.legend.is_none().then(|| {
+                    self.placements.iter().rev().find(|p| p.same_section(&placement))
+                })?
+            })
+            .map(Placement::form_id)
+    }
+}
+
+/// Not in a `<form>` and not in an iframe (those get `frame_form_id`).
+fn is_orphan(el: &DomElement) -> bool {
+    el.form_id.is_none() && el.frame_path.is_empty()
+}
+
+/// An action that submits rather than navigates (links are left standalone).
+fn is_button(el: &DomElement) -> bool {
+    is_action(el) && el.tag != "a"
+}
+
 fn detect_primary_action(actions: &[ScreenElement]) -> Option<ScreenElement> {
     let keywords = ["submit", "save", "sign", "login", "continue", "next"];
 
@@ -107,15 +266,17 @@ fn is_input(el: &DomElement) -> bool {
 
         // Choice inputs
         | Some("radio")
-        | Some("checkbox") => true,
+        | Some("checkbox")
+
+        // Uploads
+        | Some("file") => true,
 
         // Explicit non-inputs
         Some("submit")
         | Some("button")
         | Some("reset")
         | Some("image")
-        | Some("hidden")
-        | Some("file") => false,
+        | Some("hidden") => false,
 
         // Unknown → be conservative
         _ => false,
@@ -151,7 +312,7 @@ fn is_output(el: &DomElement) -> bool {
     }
 }
 
-fn label_for(el: &DomElement) -> Option<String> {
+pub(crate) fn label_for(el: &DomElement) -> Option<String> {
     el.associated_label_text
         .clone()
         .or_else(|| el.aria_label.clone())
@@ -183,6 +344,18 @@ fn to_input(el: &DomElement) -> ScreenElement {
         nearby_help_text: el.nearby_help_text.clone(),
         autocomplete: el.autocomplete.clone(),
         aria_describedby_text: el.aria_describedby_text.clone(),
+        semantic_section: el.semantic_section.clone(),
+        aria_live: el.aria_live.clone(),
+        overlay: overlay_of(el),
+        frame_path: el.frame_path.clone(),
+    }
+}
+
+/// `a` and `b` separated by a space, either one alone when the other is missing.
+fn join_text(a: Option<String>, b: Option<String>) -> Option<String> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(format!("{} {}", a, b)),
+        (a, b) => a.or(b),
     }
 }
 
@@ -208,6 +381,10 @@ fn to_action(el: &DomElement) -> ScreenElement {
         nearby_help_text: None,
         autocomplete: None,
         aria_describedby_text: None,
+        semantic_section: el.semantic_section.clone(),
+        aria_live: el.aria_live.clone(),
+        overlay: overlay_of(el),
+        frame_path: el.frame_path.clone(),
     }
 }
 
@@ -229,9 +406,13 @@ fn to_output(el: &DomElement) -> ScreenElement {
         minlength: None,
         readonly: false,
         fieldset_legend: None,
-        section_heading: None,
+        section_heading: el.section_heading.clone(),
         nearby_help_text: None,
         autocomplete: None,
         aria_describedby_text: None,
+        semantic_section: el.semantic_section.clone(),
+        aria_live: el.aria_live.clone(),
+        overlay: overlay_of(el),
+        frame_path: el.frame_path.clone(),
     }
 }
@@ -1,50 +1,304 @@
-use crate::screen::screen_model::{Form, FormIntent, IntentSignal};
+use crate::screen::screen_model::{Form, FormIntent, IntentSignal, ScreenElement};
 
-pub fn infer_form_intent(form: &Form) -> FormIntent {
-    let mut signals = Vec::new();
-    let mut score: f32 = 0.0;
+/// A form is labelled with its best intent only when that intent scores above this.
+const MIN_SCORE: f32 = 0.4;
 
-    let mut text = String::new();
+const INPUT_TYPE_WEIGHT: f32 = 0.4;
+const AUTOCOMPLETE_WEIGHT: f32 = 0.35;
+const ACTION_WEIGHT: f32 = 0.4;
+const KEYWORD_WEIGHT: f32 = 0.2;
+/// Keywords are weak on their own: at most this many count per intent
+const MAX_KEYWORDS: usize = 2;
+const STRUCTURE_WEIGHT: f32 = 0.3;
 
-    for input in &form.inputs {
-        if let Some(label) = &input.label {
-            let l = label.to_lowercase();
-            text.push_str(&l);
-            text.push(' ');
-
-            if l.contains("password") {
-                score += 0.4;
-                signals.push(IntentSignal::InputType("password".into()));
+/// Evidence that points a form towards one intent.
+///
+/// Keywords are looked up in input labels, names, placeholders and the
+/// fieldset legends and section headings around the inputs; action phrases
+/// in button labels. All matching is on whole words, case-insensitive.
+struct IntentRule {
+    label: &'static str,
+    keywords: &'static [&'static str],
+    input_types: &'static [&'static str],
+    autocomplete: &'static [&'static str],
+    actions: &'static [&'static str],
+    /// Shape of the form typical of the intent. Only corroborates: it counts
+    /// when at least one other signal matched.
+    structure: fn(&FormShape) -> Option<&'static str>,
+}
+
+/// Ties go to the earlier rule.
+const RULES: &[IntentRule] = &[
+    IntentRule {
+        label: "Authentication",
+        keywords: &["log in", "login", "sign in", "signin", "username", "remember me"],
+        input_types: &["password"],
+        autocomplete: &["current-password", "username"],
+        actions: &["sign in", "signin", "log in", "login"],
+        structure: |s| {
+            (s.passwords == 1 && (2..=3).contains(&s.inputs)).then_some("one password field with an identifier")
+        },
+    },
+    IntentRule {
+        label: "Registration",
+        keywords: &["sign up", "register", "create account", "confirm password", "repeat password", "first name", "last name", "terms"],
+        input_types: &[],
+        autocomplete: &["new-password", "given-name", "family-name"],
+        actions: &["sign up", "register", "create account", "join", "get started"],
+        structure: |s| (s.passwords >= 1 && s.inputs >= 4).then_some("password among four or more fields"),
+    },
+    IntentRule {
+        label: "Password Reset",
+        keywords: &["forgot", "reset", "recover", "new password"],
+        input_types: &[],
+        autocomplete: &["new-password"],
+        actions: &["reset", "recover", "send link", "send reset link", "change password", "update password"],
+        structure: |s| {
+            (s.passwords >= 2 && s.passwords == s.inputs).then_some("only password fields")
+                .or_else(|| (s.inputs == 1 && s.emails == 1).then_some("single email field"))
+        },
+    },
+    IntentRule {
+        label: "Search",
+        keywords: &["search", "find", "query", "keywords"],
+        input_types: &["search"],
+        autocomplete: &[],
+        actions: &["search", "find", "go"],
+        structure: |s| (s.inputs == 1 && s.text_like == 1).then_some("single text field"),
+    },
+    IntentRule {
+        label: "Checkout",
+        keywords: &["card number", "credit card", "cvv", "cvc", "expiry", "expiration", "billing", "payment", "checkout"],
+        input_types: &[],
+        autocomplete: &["cc-number", "cc-exp", "cc-exp-month", "cc-exp-year", "cc-csc", "cc-name"],
+        actions: &["pay", "place order", "checkout", "check out", "buy", "purchase", "complete order"],
+        structure: |_| None,
+    },
+    IntentRule {
+        label: "Contact",
+        keywords: &["message", "subject", "contact", "inquiry", "enquiry", "comment", "question"],
+        input_types: &[],
+        autocomplete: &[],
+        actions: &["send", "send message", "contact"],
+        structure: |s| (s.textareas >= 1 && s.emails >= 1).then_some("message box with an email field"),
+    },
+    IntentRule {
+        label: "Newsletter",
+        keywords: &["newsletter", "subscribe", "mailing list", "updates"],
+        input_types: &[],
+        autocomplete: &[],
+        actions: &["subscribe", "sign up", "join", "notify me"],
+        structure: |s| (s.inputs == 1 && s.emails == 1).then_some("single email field"),
+    },
+    IntentRule {
+        label: "Filter",
+        keywords: &["filter", "filters", "sort", "sort by", "order by", "price range", "category", "refine"],
+        input_types: &["range"],
+        autocomplete: &[],
+        actions: &["apply", "filter", "sort", "refine", "clear filters", "reset filters"],
+        structure: |s| {
+            (s.inputs >= 1 && s.choices == s.inputs).then_some("only selects, checkboxes and radios")
+        },
+    },
+    IntentRule {
+        label: "Address",
+        keywords: &["address", "street", "city", "zip", "postal", "postcode", "province", "country", "shipping"],
+        input_types: &[],
+        autocomplete: &[
+            "street-address", "address-line1", "address-line2", "address-level1", "address-level2",
+            "postal-code", "country", "country-name",
+        ],
+        actions: &["save address", "use this address", "continue to shipping"],
+        structure: |_| None,
+    },
+    IntentRule {
+        label: "Profile Edit",
+        keywords: &["profile", "display name", "bio", "avatar", "about me", "account settings"],
+        input_types: &[],
+        autocomplete: &["nickname", "photo"],
+        actions: &["save changes", "update profile", "save profile"],
+        structure: |s| (s.prefilled >= 2).then_some("prefilled fields"),
+    },
+];
+
+/// Field counts of a form, for the structure signals.
+#[derive(Default)]
+struct FormShape {
+    inputs: usize,
+    passwords: usize,
+    emails: usize,
+    textareas: usize,
+    /// Single-line free-text inputs (text, search, no type)
+    text_like: usize,
+    /// Selects, checkboxes, radios and ranges
+    choices: usize,
+    /// Non-password inputs that already hold a value
+    prefilled: usize,
+}
+
+impl FormShape {
+    fn of(form: &Form) -> Self {
+        let mut shape = FormShape { inputs: form.inputs.len(), ..Default::default() };
+        for input in &form.inputs {
+            let ty = input_type(input);
+            match ty.as_str() {
+                "password" => shape.passwords += 1,
+                "email" => shape.emails += 1,
+                "textarea" => shape.textareas += 1,
+                "text" | "search" => shape.text_like += 1,
+                "select" | "checkbox" | "radio" | "range" => shape.choices += 1,
+                _ => {}
+            }
+            if ty != "password" && input.value.as_deref().is_some_and(|v| !v.trim().is_empty()) {
+                shape.prefilled += 1;
             }
         }
+        shape
     }
+}
 
-    for action in &form.actions {
-        if let Some(label) = &action.label {
-            let l = label.to_lowercase();
-            text.push_str(&l);
-            text.push(' ');
+/// Effective type of an input: `select` / `textarea` by tag, `search` for
+/// searchbox roles, else its `type` attribute (`text` when absent).
+fn input_type(input: &ScreenElement) -> String {
+    match input.tag.as_deref() {
+        Some("select") => return "select".into(),
+        Some("textarea") => return "textarea".into(),
+        _ => {}
+    }
+    if input.role.as_deref() == Some("searchbox") {
+        return "search".into();
+    }
+    input.input_type.as_deref().unwrap_or("text").to_lowercase()
+}
 
-            if l.contains("sign") || l.contains("login") {
-                score += 0.4;
-                signals.push(IntentSignal::ActionLabel(label.clone()));
-            }
+/// Lowercase `text` with every non-alphanumeric run turned into one space
+/// and a space at both ends, so phrases can be matched as whole words.
+fn words(text: &str) -> String {
+    let mut out = String::from(" ");
+    for c in text.to_lowercase().chars() {
+        if c.is_alphanumeric() {
+            out.push(c);
+        } else if !out.ends_with(' ') {
+            out.push(' ');
+        }
+    }
+    if !out.ends_with(' ') {
+        out.push(' ');
+    }
+    out
+}
+
+fn has_phrase(words: &str, phrase: &str) -> bool {
+    words.contains(&format!(" {} ", phrase))
+}
+
+/// Score one rule against the form; returns the score and its signals.
+fn score_rule(
+    rule: &IntentRule,
+    field_text: &str,
+    types: &[String],
+    autocomplete: &[String],
+    actions: &[(String, String)],
+    shape: &FormShape,
+) -> (f32, Vec<IntentSignal>) {
+    let mut score = 0.0;
+    let mut signals = Vec::new();
+
+    for ty in rule.input_types {
+        if types.iter().any(|t| t == ty) {
+            score += INPUT_TYPE_WEIGHT;
+            signals.push(IntentSignal::InputType((*ty).into()));
+        }
+    }
+
+    for token in rule.autocomplete {
+        if autocomplete.iter().any(|a| a == token) {
+            score += AUTOCOMPLETE_WEIGHT;
+            signals.push(IntentSignal::Autocomplete((*token).into()));
         }
     }
 
-    let label = if score > 0.7 {
-        "Authentication"
-    } else if score > 0.4 {
-        "User Input"
-    } else {
-        "Unknown"
-    };
+    if let Some((label, _)) = actions
+        .iter()
+        .find(|(_, words)| rule.actions.iter().any(|p| has_phrase(words, p)))
+    {
+        score += ACTION_WEIGHT;
+        signals.push(IntentSignal::ActionLabel(label.clone()));
+    }
 
-    let confidence = score.clamp(0.0, 1.0);
+    for keyword in rule.keywords.iter().filter(|k| has_phrase(field_text, k)).take(MAX_KEYWORDS) {
+        score += KEYWORD_WEIGHT;
+        signals.push(IntentSignal::Keyword((*keyword).into()));
+    }
+
+    if !signals.is_empty()
+        && let Some(structure) = (rule.structure)(shape)
+    {
+        score += STRUCTURE_WEIGHT;
+        signals.push(IntentSignal::Structure(structure.into()));
+    }
+
+    (score, signals)
+}
 
-    FormIntent {
-        label: label.to_string(),
-        confidence,
-        signals,
+/// Infer what a form is for from its inputs, actions and surrounding headings.
+///
+/// Every known intent (see `RULES`) is scored from weighted signals — input
+/// types, autocomplete tokens, action labels, keywords in field text and
+/// headings, and the form's shape — and the best one wins if it scores above
+/// 0.4; otherwise the label is "Unknown". The confidence is the winning
+/// score capped at 1.0, and the signals are the ones that produced it.
+pub fn infer_form_intent(form: &Form) -> FormIntent {
+    let mut field_text = String::new();
+    let mut headings: Vec<&str> = Vec::new();
+    for input in &form.inputs {
+        for text in [&input.label, &input.name, &input.placeholder, &input.id].into_iter().flatten() {
+            field_text.push_str(text);
+            field_text.push(' ');
+        }
+        for heading in [&input.fieldset_legend, &input.section_heading].into_iter().flatten() {
+            if !headings.contains(&heading.as_str()) {
+                headings.push(heading);
+            }
+        }
+    }
+    field_text.push_str(&headings.join(" "));
+    let field_text = words(&field_text);
+
+    let types: Vec<String> = form.inputs.iter().map(input_type).collect();
+    let autocomplete: Vec<String> = form
+        .inputs
+        .iter()
+        .filter_map(|i| i.autocomplete.as_deref())
+        .flat_map(str::split_whitespace)
+        .map(str::to_lowercase)
+        .collect();
+    let actions: Vec<(String, String)> = form
+        .actions
+        .iter()
+        .filter_map(|a| a.label.as_ref())
+        .map(|label| (label.clone(), words(label)))
+        .collect();
+    let shape = FormShape::of(form);
+
+    let mut best: Option<(&str, f32, Vec<IntentSignal>)> = None;
+    for rule in RULES {
+        let (score, signals) = score_rule(rule, &field_text, &types, &autocomplete, &actions, &shape);
+        if best.as_ref().is_none_or(|(_, s, _)| score > *s) {
+            best = Some((rule.label, score, signals));
+        }
+    }
+    match best {
+        Some((label, score, signals)) if score > MIN_SCORE => FormIntent {
+            label: label.to_string(),
+            confidence: score.clamp(0.0, 1.0),
+            signals,
+        },
+        // Nothing matched well enough: no confidence, nothing to explain
+        _ => FormIntent {
+            label: "Unknown".to_string(),
+            confidence: 0.0,
+            signals: Vec::new(),
+        },
     }
 }
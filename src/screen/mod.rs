@@ -1,3 +1,5 @@
 pub mod classifier;
 pub mod intent;
+pub mod overlay;
 pub mod screen_model;
+pub mod table;
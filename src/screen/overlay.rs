@@ -0,0 +1,85 @@
+use crate::screen::screen_model::{DomElement, ElementKind, Overlay, ScreenElement, ScreenSemantics};
+
+/// Words of buttons that close a dialog without committing to anything,
+/// most preferred first.
+const DISMISS_WORDS: &[&str] = &[
+    "close", "dismiss", "×", "✕", "x", "no thanks", "not now", "maybe later", "later", "cancel",
+    "reject all", "reject", "decline", "got it", "ok", "okay", "accept all", "accept", "agree",
+    "allow all", "continue",
+];
+
+/// The overlay `el` belongs to, if any.
+///
+/// Dialogs (`role=dialog` / `alertdialog`, `<dialog>`, anything inside one
+/// and anything under an `aria-modal="true"` container) are `Modal`. Text in
+/// a live region (`aria-live`, `role=status` / `alert`) outside a dialog is a
+/// `Toast`; a live region inside a dialog is part of the dialog.
+pub fn overlay_of(el: &DomElement) -> Option<Overlay> {
+    let in_dialog = el.in_dialog
+        || el.tag == "dialog"
+        || matches!(el.role.as_deref(), Some("dialog") | Some("alertdialog"))
+        || el.semantic_section.as_deref() == Some("dialog");
+    if in_dialog {
+        Some(Overlay::Modal)
+    } else if el.aria_live.is_some() {
+        Some(Overlay::Toast)
+    } else {
+        None
+    }
+}
+
+/// The action that most likely closes an open modal: the first of
+/// `actions` in the modal whose label best matches `DISMISS_WORDS`.
+pub fn dismiss_action<'a, I>(actions: I) -> Option<&'a ScreenElement>
+where
+    I: IntoIterator<Item = &'a ScreenElement>,
+{
+    actions
+        .into_iter()
+        .filter(|a| a.kind == ElementKind::Action && a.overlay == Some(Overlay::Modal))
+        .filter_map(|a| dismiss_rank(a).map(|rank| (rank, a)))
+        .min_by_key(|(rank, _)| *rank)
+        .map(|(_, a)| a)
+}
+
+/// Position in `DISMISS_WORDS` of the word the action's label (or, for
+/// icon buttons, its id) consists of or starts with.
+fn dismiss_rank(action: &ScreenElement) -> Option<usize> {
+    let label = action
+        .label
+        .as_deref()
+        .or(action.id.as_deref())?
+        .trim()
+        .to_lowercase();
+    DISMISS_WORDS.iter().position(|word| {
+        label == *word
+            || label
+                .strip_prefix(word)
+                .is_some_and(|rest| rest.starts_with(|c: char| !c.is_alphanumeric()))
+    })
+}
+
+impl ScreenSemantics {
+    /// Inputs, actions and outputs of the whole screen, forms first.
+    pub fn elements(&self) -> impl Iterator<Item = &ScreenElement> {
+        self.forms
+            .iter()
+            .flat_map(|f| f.inputs.iter().chain(&f.actions))
+            .chain(&self.standalone_actions)
+            .chain(&self.outputs)
+    }
+
+    /// Whether anything on the screen belongs to a modal.
+    pub fn modal_open(&self) -> bool {
+        self.elements().any(|el| el.overlay == Some(Overlay::Modal))
+    }
+
+    /// Texts of the toasts on the screen, one per live region.
+    pub fn toasts(&self) -> Vec<&str> {
+        self.outputs
+            .iter()
+            .filter(|el| el.overlay == Some(Overlay::Toast))
+            .filter_map(|el| el.label.as_deref())
+            .collect()
+    }
+}
@@ -72,8 +72,62 @@ pub struct DomElement {
     pub nearby_help_text: Option<String>,
     #[serde(default)]
     pub semantic_section: Option<String>,
+    /// Politeness of the live region around the element ("polite",
+    /// "assertive"), from `aria-live` or a live role like `status` / `alert`
+    #[serde(default)]
+    pub aria_live: Option<String>,
+    /// The element is, or sits inside, a dialog: `role="dialog"` /
+    /// `"alertdialog"`, a `<dialog>` or an `aria-modal="true"` container
+    #[serde(default)]
+    pub in_dialog: bool,
+    /// Index, in page order, of the outermost live region around the
+    /// element; the elements of one region make up one message
+    #[serde(default)]
+    pub live_region: Option<usize>,
     #[serde(default)]
     pub visible: bool,
+    /// Selectors of the iframes containing this element, outermost first
+    /// (e.g. `["iframe#checkout", "iframe[name=\"card\"]"]`); empty for the main frame
+    #[serde(default)]
+    pub frame_path: Vec<String>,
+    /// Shadow hosts enclosing this element, outermost first (e.g.
+    /// `["app-checkout", "ds-input#email"]`); empty outside shadow trees
+    #[serde(default)]
+    pub shadow_path: Vec<String>,
+    /// Rows and columns, on `<table>`s, ARIA grids and lists of repeated items
+    #[serde(default)]
+    pub table: Option<TableData>,
+}
+
+/// What kind of element a table was read from.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TableKind {
+    /// `<table>` or `role="table"`
+    #[default]
+    Table,
+    /// `role="grid"` / `role="treegrid"`
+    Grid,
+    /// `<ul>` / `<ol>` / `role="list"`: one cell per item, no headers
+    List,
+}
+
+/// Table contents as extracted, attached to the table's own element.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TableData {
+    #[serde(default)]
+    pub kind: TableKind,
+    /// `<caption>` text
+    #[serde(default)]
+    pub caption: Option<String>,
+    #[serde(default)]
+    pub headers: Vec<String>,
+    /// Cell texts, row by row (header row excluded)
+    #[serde(default)]
+    pub rows: Vec<Vec<String>>,
+    /// Number of tables and grids the element sits in
+    #[serde(default)]
+    pub depth: usize,
 }
 
 #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
@@ -101,9 +155,25 @@ pub struct ScreenSemantics {
     pub forms: Vec<Form>,
     pub standalone_actions: Vec<ScreenElement>,
     pub outputs: Vec<ScreenElement>,
+    pub tables: Vec<DataTable>,
     pub primary_action: Option<ScreenElement>,
 }
 
+/// A table, grid or list of repeated items on the page, row by row.
+///
+/// Its cells are still reported as outputs too; this keeps their layout.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DataTable {
+    /// `table:` plus the element id, else the slugged label, else the position
+    /// among the page's tables (`table:orders`, `table:3`)
+    pub id: String,
+    /// Caption, `aria-label` or the heading above the table
+    pub label: Option<String>,
+    pub kind: TableKind,
+    pub headers: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct ScreenElement {
     pub label: Option<String>,
@@ -127,6 +197,22 @@ pub struct ScreenElement {
     pub nearby_help_text: Option<String>,
     pub autocomplete: Option<String>,
     pub aria_describedby_text: Option<String>,
+    /// Landmark the element sits in (see `DomElement::semantic_section`)
+    pub semantic_section: Option<String>,
+    pub aria_live: Option<String>,
+    /// Transient layer the element belongs to (see `overlay_of`)
+    pub overlay: Option<Overlay>,
+    /// Frame the element lives in (see `DomElement::frame_path`); empty for the main frame
+    pub frame_path: Vec<String>,
+}
+
+/// Layer shown over the page for a while rather than part of it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Overlay {
+    /// Dialog content: cookie banners, confirmations, popups
+    Modal,
+    /// Live-region message outside any dialog ("Saved", "3 items in cart")
+    Toast,
 }
 
 #[derive(Debug, Clone)]
@@ -136,6 +222,8 @@ pub struct Form {
     pub actions: Vec<ScreenElement>,
     pub primary_action: Option<ScreenElement>,
     pub intent: Option<FormIntent>,
+    /// Frame the form lives in (see `DomElement::frame_path`); empty for the main frame
+    pub frame_path: Vec<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -154,9 +242,13 @@ pub struct FormIntent {
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum IntentSignal {
+    /// Word in field labels, names, placeholders or nearby headings
     Keyword(String),
     InputType(String),
+    /// Token of an input's `autocomplete` attribute (e.g. "cc-number")
+    Autocomplete(String),
     ActionLabel(String),
+    /// Shape of the form (e.g. "single email field")
     Structure(String),
 }
 
@@ -0,0 +1,170 @@
+use crate::screen::classifier::slug;
+use crate::screen::screen_model::{DataTable, DomElement, TableData, TableKind};
+
+/// Landmarks whose lists are menus rather than data.
+const CHROME_SECTIONS: &[&str] = &["header", "nav", "footer"];
+
+/// Tables of a page, collected from its elements in document order.
+///
+/// A table, grid or list inside a table or grid that was already reported
+/// is part of that table's cells, not a table of its own.
+#[derive(Debug, Default)]
+pub struct PageTables {
+    tables: Vec<DataTable>,
+    /// `depth` of each table or grid enclosing the current element, and
+    /// whether it was reported
+    enclosing: Vec<(usize, bool)>,
+}
+
+impl PageTables {
+    /// Report the table held by `el`, if any (see `to_table`).
+    pub fn add(&mut self, el: &DomElement) {
+        let Some(data) = &el.table else {
+            return;
+        };
+        while self.enclosing.last().is_some_and(|(depth, _)| *depth >= data.depth) {
+            self.enclosing.pop();
+        }
+        let nested = self.enclosing.iter().any(|(_, reported)| *reported);
+        let table = if nested { None } else { to_table(el, &self.tables) };
+
+        if data.kind != TableKind::List {
+            self.enclosing.push((data.depth, table.is_some()));
+        }
+        self.tables.extend(table);
+    }
+
+    pub fn into_vec(self) -> Vec<DataTable> {
+        self.tables
+    }
+}
+
+/// The table held by `el`, if any, following the page's `earlier` tables.
+///
+/// Tables and grids need a header row and more than one cell; without them
+/// they lay out the page rather than hold data. Lists need at least two
+/// items to count as repeated data, and lists in the header, navigation or
+/// footer are menus, not data. An id already taken by an earlier table (two
+/// tables under one heading) gets a `-2`, `-3`… suffix.
+pub fn to_table(el: &DomElement, earlier: &[DataTable]) -> Option<DataTable> {
+    let data: &TableData = el.table.as_ref()?;
+    let keep = match data.kind {
+        TableKind::List => {
+            data.rows.len() >= 2
+                && !el
+                    .semantic_section
+                    .as_deref()
+                    .is_some_and(|s| CHROME_SECTIONS.contains(&s))
+        }
+        _ => !data.headers.is_empty() && data.headers.len() + data.rows.iter().map(Vec::len).sum::<usize>() > 1,
+    };
+    if !keep {
+        return None;
+    }
+
+    let label = [&data.caption, &el.aria_label, &el.section_heading]
+        .into_iter()
+        .flatten()
+        .map(|s| s.trim())
+        .find(|s| !s.is_empty())
+        .map(String::from);
+    let key = el
+        .id
+        .as_deref()
+        .map(str::trim)
+        .filter(|id| !id.is_empty())
+        .map(String::from)
+        .or_else(|| label.as_deref().map(slug).filter(|s| !s.is_empty()))
+        .unwrap_or_else(|| earlier.len().to_string());
+    let mut id = format!("table:{}", key);
+    let mut n = 2;
+    while earlier.iter().any(|t| t.id == id) {
+        id = format!("table:{}-{}", key, n);
+        n += 1;
+    }
+
+    Some(DataTable {
+        id,
+        label,
+        kind: data.kind,
+        headers: data.headers.iter().map(|h| h.trim().to_string()).collect(),
+        rows: data
+            .rows
+            .iter()
+            .map(|row| row.iter().map(|c| c.trim().to_string()).collect())
+            .collect(),
+    })
+}
+
+/// The table named `name` (its id, or a label containing it, case-insensitive),
+/// or without a name the first table (having `column`, when given).
+pub fn find_table<'a>(tables: &'a [DataTable], name: Option<&str>, column: Option<&str>) -> Option<&'a DataTable> {
+    match name {
+        Some(name) => {
+            let wanted = name.trim().to_lowercase();
+            tables.iter().find(|t| {
+                t.id.to_lowercase() == wanted
+                    || t.id.to_lowercase() == format!("table:{}", wanted)
+                    || t.label.as_deref().is_some_and(|l| l.to_lowercase().contains(&wanted))
+            })
+        }
+        None => tables
+            .iter()
+            .find(|t| column.is_none_or(|c| t.column_index(c).is_some())),
+    }
+}
+
+impl DataTable {
+    pub fn row_count(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Position of the column headed `header` (trimmed, case-insensitive).
+    pub fn column_index(&self, header: &str) -> Option<usize> {
+        let wanted = header.trim().to_lowercase();
+        self.headers.iter().position(|h| h.to_lowercase() == wanted)
+    }
+
+    /// The cells of column `header`, top to bottom (rows too short to have
+    /// one are skipped). None when there is no such column.
+    pub fn column(&self, header: &str) -> Option<Vec<&str>> {
+        let index = self.column_index(header)?;
+        Some(
+            self.rows
+                .iter()
+                .filter_map(|row| row.get(index).map(String::as_str))
+                .collect(),
+        )
+    }
+
+    /// Whether column `header` is sorted, ascending or `descending`.
+    ///
+    /// Empty cells are ignored. When every other cell reads as a number
+    /// (currency signs, thousands separators and units aside) the column is
+    /// compared numerically, otherwise as case-insensitive text.
+    pub fn is_sorted_by(&self, header: &str, descending: bool) -> Option<bool> {
+        let cells: Vec<&str> = self.column(header)?.into_iter().filter(|c| !c.is_empty()).collect();
+        let numbers: Option<Vec<f64>> = cells.iter().map(|c| parse_number(c)).collect();
+        let in_order = |ord: std::cmp::Ordering| {
+            if descending { ord.is_ge() } else { ord.is_le() }
+        };
+        Some(match numbers {
+            Some(numbers) => numbers.windows(2).all(|w| in_order(w[0].total_cmp(&w[1]))),
+            None => cells
+                .windows(2)
+                .all(|w| in_order(w[0].to_lowercase().cmp(&w[1].to_lowercase()))),
+        })
+    }
+}
+
+/// `"$1,299.50"` -> 1299.5, `"-3 kg"` -> -3.0; None without any digit.
+fn parse_number(cell: &str) -> Option<f64> {
+    let kept: String = cell
+        .chars()
+        .filter(|c| c.is_ascii_digit() || matches!(c, '.' | '-'))
+        .collect();
+    if !kept.chars().any(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    kept.parse().ok()
+}
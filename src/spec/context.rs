@@ -1,3 +1,7 @@
+use crate::browser::console::ConsoleEntry;
+use crate::browser::dialogs::DialogEntry;
+use crate::browser::network::NetworkEntry;
+use std::time::Instant;
 use crate::spec::spec_model::AssertionResult;
 
 /// Tracks the execution state and results of a running test.
@@ -8,6 +12,28 @@ pub struct TestContext {
 
     /// All assertion results collected during execution
     pub assertion_results: Vec<AssertionResult>,
+
+    /// Network requests recorded so far in this test
+    pub network: Vec<NetworkEntry>,
+
+    /// Console messages and page errors recorded so far in this test
+    pub console: Vec<ConsoleEntry>,
+
+    /// JavaScript dialogs opened so far in this test
+    pub dialogs: Vec<DialogEntry>,
+
+    /// Message fragment (empty: any message) of the dialog the next step
+    /// must open, set by an `ExpectDialog` step
+    pub expected_dialog: Option<String>,
+
+    /// Screenshots captured on failure so far
+    pub screenshots: Vec<String>,
+
+    /// Assertion retry attempts used so far
+    pub retry_attempts: usize,
+
+    /// When the test started
+    pub started: Instant,
 }
 
 impl TestContext {
@@ -15,6 +41,13 @@ impl TestContext {
         TestContext {
             current_step: 0,
             assertion_results: Vec::new(),
+            network: Vec::new(),
+            console: Vec::new(),
+            dialogs: Vec::new(),
+            expected_dialog: None,
+            screenshots: Vec::new(),
+            retry_attempts: 0,
+            started: Instant::now(),
         }
     }
 
@@ -23,6 +56,22 @@ impl TestContext {
         self.assertion_results.extend(results);
     }
 
+    /// Append newly recorded network requests.
+    pub fn record_network(&mut self, entries: Vec<NetworkEntry>) {
+        self.network.extend(entries);
+    }
+
+    /// Append newly recorded console messages, tagged with the step they
+    /// were logged during (`None` while loading `start_url`).
+    pub fn record_console(&mut self, entries: Vec<ConsoleEntry>, step: Option<usize>) {
+        self.console.extend(entries.into_iter().map(|e| ConsoleEntry { step, ..e }));
+    }
+
+    /// Append newly opened dialogs.
+    pub fn record_dialogs(&mut self, entries: Vec<DialogEntry>) {
+        self.dialogs.extend(entries);
+    }
+
     /// Advance to the next step.
     pub fn advance(&mut self) {
         self.current_step += 1;
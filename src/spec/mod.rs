@@ -1,4 +1,6 @@
 pub mod context;
+pub mod pool;
+pub mod recorder;
 pub mod runner;
 pub mod runner_config;
 pub mod spec_model;
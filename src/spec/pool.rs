@@ -0,0 +1,87 @@
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::agent::error::AgentError;
+use crate::browser::backend::BrowserBackend;
+use crate::spec::runner::TestRunner;
+use crate::spec::runner_config::RunnerConfig;
+use crate::spec::spec_model::{TestResult, TestSpec};
+
+// ============================================================================
+// Parallel spec execution over a pool of browser backends
+// ============================================================================
+
+/// Run `specs` on up to `jobs` workers, each owning one backend from `launch`.
+///
+/// Workers pull the next unclaimed spec until none are left, so slow specs do
+/// not hold up a fixed partition. Results are returned in `specs` order
+/// regardless of completion order. `launch` receives the worker index and is
+/// called on the worker thread, so backends never cross threads.
+///
+/// With more than one worker, each worker writes screenshots to its own
+/// `worker-N` subdirectory of `config.screenshot_dir`.
+///
+/// A worker whose backend fails to launch simply takes no specs; an error is
+/// returned only if no worker could launch at all.
+pub fn run_pool<B, F>(
+    specs: &[TestSpec],
+    jobs: usize,
+    config: &RunnerConfig,
+    launch: F,
+) -> Result<Vec<TestResult>, AgentError>
+where
+    B: BrowserBackend,
+    F: Fn(usize) -> Result<B, AgentError> + Sync,
+{
+    if specs.is_empty() {
+        return Ok(Vec::new());
+    }
+    let jobs = jobs.clamp(1, specs.len());
+
+    let next = AtomicUsize::new(0);
+    let results: Mutex<Vec<Option<TestResult>>> = Mutex::new(vec![None; specs.len()]);
+    let launch_errors: Mutex<Vec<AgentError>> = Mutex::new(Vec::new());
+
+    std::thread::scope(|scope| {
+        for worker in 0..jobs {
+            let (next, results, launch_errors, launch) = (&next, &results, &launch_errors, &launch);
+            let worker_config = worker_config(config, worker, jobs);
+            scope.spawn(move || {
+                let mut backend = match launch(worker) {
+                    Ok(backend) => backend,
+                    Err(e) => {
+                        launch_errors.lock().unwrap().push(e);
+                        return;
+                    }
+                };
+                loop {
+                    let index = next.fetch_add(1, Ordering::SeqCst);
+                    let Some(spec) = specs.get(index) else { break };
+                    let result = TestRunner::run_with_config(spec, &mut backend, &worker_config);
+                    results.lock().unwrap()[index] = Some(result);
+                }
+            });
+        }
+    });
+
+    let results = results.into_inner().unwrap();
+    if results.iter().any(Option::is_none) {
+        // Only possible when every worker failed to launch.
+        let mut errors = launch_errors.into_inner().unwrap();
+        return Err(errors.remove(0));
+    }
+    Ok(results.into_iter().flatten().collect())
+}
+
+/// Runner config for one worker: unchanged for a single worker, otherwise
+/// screenshots go to `<screenshot_dir>/worker-N` so filenames cannot collide.
+pub fn worker_config(config: &RunnerConfig, worker: usize, jobs: usize) -> RunnerConfig {
+    let mut worker_config = config.clone();
+    if jobs > 1 {
+        worker_config.screenshot_dir = std::path::Path::new(&config.screenshot_dir)
+            .join(format!("worker-{}", worker))
+            .to_string_lossy()
+            .to_string();
+    }
+    worker_config
+}
@@ -0,0 +1,260 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use crate::browser::backend::BrowserBackend;
+use crate::browser::recording::RecordedEvent;
+use crate::canonical::canonical_model::CanonicalScreenState;
+use crate::canonical::diff::{SemanticSignal, SemanticStateDiff, semantic_diff};
+use crate::explorer::test_generator::extract_url_path;
+use crate::browser::playwright::is_virtual_form_id;
+use crate::screen::classifier::{classify, label_for};
+use crate::screen::screen_model::DomElement;
+use crate::spec::spec_model::{AssertionSpec, TestSpec, TestStep};
+
+// ============================================================================
+// Recorder — turn recorded user interactions into a TestSpec
+// ============================================================================
+
+/// Builds `TestStep`s from `RecordedEvent`s, naming elements the way
+/// `classify` does so the recorded spec replays through the normal runner.
+///
+/// - Consecutive fills and selects in one form collapse into a `FillForm`;
+///   a click on that form's action turns it into a `FillAndSubmit`.
+/// - Inputs outside any form become `Type` steps without a delay.
+/// - Clicks on text inputs only move focus and are dropped; clicks on
+///   checkboxes, radios, buttons and links become `Click` steps.
+/// - Navigations caused by a click are dropped (replaying the click gets
+///   there), as are redirects up to the next interaction; any other
+///   navigation becomes a `Navigate` step.
+#[derive(Debug, Clone)]
+pub struct Recorder {
+    start_url: String,
+    current_url: String,
+    steps: Vec<TestStep>,
+    /// A click that may navigate happened since the last interaction
+    expect_navigation: bool,
+}
+
+impl Recorder {
+    pub fn new(start_url: &str) -> Self {
+        Recorder {
+            start_url: start_url.to_string(),
+            current_url: start_url.to_string(),
+            steps: Vec::new(),
+            expect_navigation: false,
+        }
+    }
+
+    /// Steps recorded so far.
+    pub fn steps(&self) -> &[TestStep] {
+        &self.steps
+    }
+
+    /// Map one event onto the step list.
+    pub fn record(&mut self, event: &RecordedEvent) {
+        match event {
+            RecordedEvent::Navigate { url } => {
+                if url != &self.current_url && !self.expect_navigation {
+                    self.steps.push(TestStep::Navigate { url: url.clone() });
+                }
+                self.current_url = url.clone();
+            }
+            RecordedEvent::Fill { element, value } | RecordedEvent::Select { element, value } => {
+                self.expect_navigation = false;
+                self.record_fill(element, value);
+            }
+            RecordedEvent::Click { element } => {
+                self.expect_navigation = false;
+                self.record_click(element);
+            }
+        }
+    }
+
+    fn record_fill(&mut self, element: &DomElement, value: &str) {
+        if element.r#type.as_deref() == Some("file") {
+            // Browsers hide the real path of a chosen file
+            return;
+        }
+        let Some(label) = label_for(element) else {
+            return;
+        };
+        let semantics = classify(std::slice::from_ref(element));
+        // A lone element's virtual form says nothing about its neighbours
+        let Some(form) = semantics.forms.first().filter(|f| !is_virtual_form_id(&f.id)) else {
+            if let Some(TestStep::Type { label: last, text, form: None, .. }) = self.steps.last_mut()
+                && *last == label
+            {
+                *text = value.to_string();
+            } else {
+                self.steps.push(TestStep::Type { label, text: value.to_string(), form: None, delay_ms: 0 });
+            }
+            return;
+        };
+
+        if let Some(TestStep::FillForm { form: id, values, frame_path }) = self.steps.last_mut()
+            && *id == form.id
+            && *frame_path == form.frame_path
+        {
+            values.insert(label, value.to_string());
+            return;
+        }
+        self.steps.push(TestStep::FillForm {
+            form: form.id.clone(),
+            values: HashMap::from([(label, value.to_string())]),
+            frame_path: form.frame_path.clone(),
+        });
+    }
+
+    fn record_click(&mut self, element: &DomElement) {
+        if matches!(element.tag.as_str(), "input" | "textarea" | "select")
+            && !matches!(element.r#type.as_deref(), Some("submit" | "button" | "image" | "reset"))
+        {
+            let toggles = matches!(element.r#type.as_deref(), Some("checkbox" | "radio"));
+            if toggles && let Some(label) = label_for(element) {
+                self.steps.push(TestStep::Click { label, frame_path: element.frame_path.clone() });
+            }
+            return;
+        }
+
+        let semantics = classify(std::slice::from_ref(element));
+
+        let form_action = semantics
+            .forms
+            .first()
+            .and_then(|f| f.actions.first().map(|a| (Some(f.id.as_str()), a)));
+        let Some((form_id, action)) = form_action.or_else(|| semantics.standalone_actions.first().map(|a| (None, a)))
+        else {
+            return;
+        };
+        let Some(label) = action.label.clone() else {
+            return;
+        };
+        self.expect_navigation = true;
+
+        if let Some(TestStep::FillForm { form, values, frame_path }) = self.steps.last()
+            && Some(form.as_str()) == form_id
+            && *frame_path == element.frame_path
+        {
+            let submit = TestStep::FillAndSubmit {
+                form: form.clone(),
+                values: values.clone(),
+                submit_label: Some(label),
+                frame_path: frame_path.clone(),
+            };
+            *self.steps.last_mut().expect("last step exists") = submit;
+            return;
+        }
+        self.steps.push(TestStep::Click { label, frame_path: element.frame_path.clone() });
+    }
+
+    /// Finish the spec, closing it with assertions suggested by the last
+    /// interaction's diff (see `suggest_assertions`). Without a `name` the
+    /// spec is named after the final page title.
+    pub fn finish(self, name: Option<&str>, diff: &SemanticStateDiff, state: &CanonicalScreenState) -> TestSpec {
+        let mut steps = self.steps;
+        let assertions = suggest_assertions(diff, state, &self.start_url);
+        if !assertions.is_empty() {
+            steps.push(TestStep::Assert { assertions });
+        }
+        let name = match name {
+            Some(name) => name.to_string(),
+            None if !state.title.trim().is_empty() => format!("Recorded: {}", state.title.trim()),
+            None => format!("Recorded: {}", self.start_url),
+        };
+        TestSpec {
+            name,
+            start_url: self.start_url,
+            steps,
+            options: None,
+            auth: false,
+            mocks: Vec::new(),
+        }
+    }
+}
+
+/// Assertions describing where a recording ended: the URL path when it
+/// moved away from `start_url`, the first output that appeared with the
+/// last interaction (result or error message), and the page title.
+pub fn suggest_assertions(
+    diff: &SemanticStateDiff,
+    state: &CanonicalScreenState,
+    start_url: &str,
+) -> Vec<AssertionSpec> {
+    let mut assertions = Vec::new();
+
+    let path = extract_url_path(&state.url);
+    if path.is_some() && path != extract_url_path(start_url) {
+        assertions.push(AssertionSpec::UrlContains { expected: path.unwrap_or_default() });
+    }
+
+    let outputs_appeared = diff
+        .signals
+        .iter()
+        .any(|s| {
+            matches!(
+                s,
+                SemanticSignal::ResultsAppeared
+                    | SemanticSignal::ErrorAppeared
+                    | SemanticSignal::ToastAppeared { .. }
+            )
+        });
+    if outputs_appeared
+        && let Some(text) = diff
+            .outputs
+            .added
+            .iter()
+            .find_map(|id| state.elements.get(id).and_then(|e| e.label.clone()))
+    {
+        assertions.push(AssertionSpec::TextPresent { expected: text });
+    }
+
+    if !state.title.trim().is_empty() {
+        assertions.push(AssertionSpec::TitleContains { expected: state.title.trim().to_string() });
+    }
+    assertions
+}
+
+// ============================================================================
+// Recording loop
+// ============================================================================
+
+/// Open `url`, record the user's interactions until `stop` returns true and
+/// return them as a spec (see `Recorder::finish` for the default name).
+///
+/// Events are polled every `poll`; after each non-empty batch the page is
+/// snapshotted so the final assertions come from the diff across the last
+/// batch. Events still buffered when `stop` fires are included.
+pub fn record_session(
+    session: &mut dyn BrowserBackend,
+    url: &str,
+    name: Option<&str>,
+    poll: Duration,
+    stop: &mut dyn FnMut() -> bool,
+) -> Result<TestSpec, Box<dyn std::error::Error>> {
+    session.navigate(url)?;
+    session.start_recording()?;
+    let (_, mut state) = crate::snapshot_session(session)?;
+    let mut diff = semantic_diff(&CanonicalScreenState::empty(), &state, true);
+    let mut recorder = Recorder::new(url);
+
+    loop {
+        let stopping = stop();
+        let events = session.take_recorded_events()?;
+        if !events.is_empty() {
+            for event in &events {
+                recorder.record(event);
+            }
+            let (_, after) = crate::snapshot_session(session)?;
+            diff = semantic_diff(&state, &after, false);
+            state = after;
+        }
+        if stopping {
+            break;
+        }
+        if events.is_empty() {
+            std::thread::sleep(poll);
+        }
+    }
+
+    Ok(recorder.finish(name, &diff, &state))
+}
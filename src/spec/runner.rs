@@ -1,16 +1,23 @@
 use crate::agent::error::AgentError;
 use crate::browser::playwright::SelectorHint;
-use crate::browser::session::BrowserSession;
+use crate::browser::backend::BrowserBackend;
+use crate::browser::dialogs::DialogPolicy;
+use crate::browser::network::{NetworkEntry, write_har};
+use crate::browser::options::SessionOptions;
+use crate::screen::classifier::classify;
+use crate::screen::overlay::dismiss_action;
+use crate::screen::screen_model::{DataTable, DomElement, ScreenSemantics};
+use crate::screen::table::find_table;
 use crate::spec::context::TestContext;
-use crate::spec::runner_config::RunnerConfig;
+use crate::spec::runner_config::{ArtifactMode, RunnerConfig};
 use crate::spec::spec_model::{AssertionResult, AssertionSpec, TestResult, TestSpec, TestStep};
 
-/// Executes a TestSpec step-by-step using a BrowserSession.
+/// Executes a TestSpec step-by-step using any `BrowserBackend`.
 pub struct TestRunner;
 
 impl TestRunner {
     /// Run a complete test spec with default config (backward compatible).
-    pub fn run(spec: &TestSpec, session: &mut BrowserSession) -> TestResult {
+    pub fn run(spec: &TestSpec, session: &mut dyn BrowserBackend) -> TestResult {
         Self::run_with_config(spec, session, &RunnerConfig::default())
     }
 
@@ -20,86 +27,227 @@ impl TestRunner {
     /// - Per-test duration tracking
     /// - Assertion retry on failure (configurable retries + delay)
     /// - Screenshot capture on failure (configurable)
+    /// - Per-spec browser context options, reverted after the spec
+    /// - HAR export of the test's network traffic (configurable)
+    /// - Playwright trace and video per test, kept always, on failure or
+    ///   on retry (configurable)
+    /// - Console messages and uncaught page errors per step, optionally
+    ///   failing the test on an uncaught exception
+    /// - JavaScript dialogs, dismissed unless an `ExpectDialog` step says
+    ///   how to answer the next one
     pub fn run_with_config(
         spec: &TestSpec,
-        session: &mut BrowserSession,
+        session: &mut dyn BrowserBackend,
         config: &RunnerConfig,
     ) -> TestResult {
-        let test_start = std::time::Instant::now();
+        if let Some(options) = &spec.options
+            && let Err(e) = session.configure(options)
+        {
+            let error = format!("Failed to apply session options: {}", e);
+            return TestResult::failed(spec, 0, error, &mut TestContext::new());
+        }
+
+        // Start every test with empty network and console logs (older servers
+        // may not support them; network assertions will then report the error)
+        let _ = session.take_network_log();
+        let _ = session.take_console_log();
+        let _ = session.take_dialogs();
+
+        // Started after the spec's options so the video uses its viewport;
+        // a backend that cannot record just produces no artifact
+        let tracing = config.record_trace.is_enabled() && session.start_trace().is_ok();
+        let filming = config.record_video.is_enabled() && session.start_video().is_ok();
+
         let mut ctx = TestContext::new();
-        let mut screenshots = Vec::new();
-        let mut total_retry_attempts: usize = 0;
+        let mut result = Self::run_steps(spec, session, config, &mut ctx);
+        result.har_path = Self::maybe_write_har(session, config, &spec.name, &mut ctx);
+
+        // Whatever was logged during a failing step (or a failed start navigation)
+        Self::collect_console(session, &mut ctx, result.steps_run.checked_sub(1));
+        result.console = std::mem::take(&mut ctx.console);
+        if config.fail_on_page_error {
+            Self::fail_on_page_error(&mut result);
+        }
+        if tracing {
+            result.trace_path = Self::keep_artifact(config.record_trace, &result, config, "trace", "zip", |path| {
+                session.stop_trace(path)
+            });
+        }
+        if filming {
+            result.video_path = Self::keep_artifact(config.record_video, &result, config, "video", "webm", |path| {
+                session.stop_video(path)
+            });
+        }
+
+        if !spec.mocks.is_empty() {
+            let _ = session.unroute(None);
+        }
+        if ctx.expected_dialog.is_some() {
+            // The expected dialog never came (the test ended or failed first)
+            let _ = session.set_dialog_policy(&DialogPolicy::default());
+        }
+        if spec.options.is_some() {
+            // Restore launch options so later specs on this session are unaffected
+            let _ = session.configure(&SessionOptions::default());
+        }
+        result
+    }
+
+    /// Navigate to `start_url` and execute every step.
+    fn run_steps(
+        spec: &TestSpec,
+        session: &mut dyn BrowserBackend,
+        config: &RunnerConfig,
+        ctx: &mut TestContext,
+    ) -> TestResult {
+        // Restore the authenticated user before the first navigation
+        if spec.auth
+            && let Err(e) = Self::load_auth_state(session, config)
+        {
+            return TestResult::failed(spec, 0, format!("Failed to authenticate: {}", e), ctx);
+        }
+
+        // Install API mocks so they already answer the first page load
+        for mock in &spec.mocks {
+            if let Err(e) = session.route(mock) {
+                let error = format!("Failed to install mock for '{}': {}", mock.url, e);
+                return TestResult::failed(spec, 0, error, ctx);
+            }
+        }
 
         // Navigate to the start URL
         if let Err(e) = session.navigate(&spec.start_url) {
-            Self::maybe_screenshot(session, config, &spec.name, 0, &mut screenshots);
-            return TestResult {
-                spec_name: spec.name.clone(),
-                passed: false,
-                steps_run: 0,
-                assertion_results: ctx.assertion_results,
-                error: Some(format!("Failed to navigate to start_url: {}", e)),
-                duration_ms: Some(test_start.elapsed().as_millis()),
-                screenshots,
-                retry_attempts: 0,
-            };
+            Self::maybe_screenshot(session, config, &spec.name, 0, &mut ctx.screenshots);
+            return TestResult::failed(spec, 0, format!("Failed to navigate to start_url: {}", e), ctx);
         }
 
+        Self::collect_console(session, ctx, None);
+        Self::collect_dialogs(session, ctx);
+
         // Execute each step
         for (i, step) in spec.steps.iter().enumerate() {
             ctx.current_step = i;
 
-            match Self::execute_step_with_retry(step, i, session, &mut ctx, config) {
+            let outcome = Self::execute_step_with_retry(step, i, session, ctx, config)
+                .and_then(|retries| Self::check_expected_dialog(step, session, ctx).map(|_| retries));
+            match outcome {
                 Ok(retries) => {
-                    total_retry_attempts += retries;
+                    ctx.retry_attempts += retries;
+                    Self::collect_console(session, ctx, Some(i));
                 }
                 Err(e) => {
-                    Self::maybe_screenshot(session, config, &spec.name, i, &mut screenshots);
-                    return TestResult {
-                        spec_name: spec.name.clone(),
-                        passed: false,
-                        steps_run: i + 1,
-                        assertion_results: ctx.assertion_results,
-                        error: Some(format!("Step {} failed: {}", i, e)),
-                        duration_ms: Some(test_start.elapsed().as_millis()),
-                        screenshots,
-                        retry_attempts: total_retry_attempts,
-                    };
+                    Self::maybe_screenshot(session, config, &spec.name, i, &mut ctx.screenshots);
+                    let mut result = TestResult::failed(spec, i + 1, format!("Step {} failed: {}", i, e), ctx);
+                    if let AgentError::ElementNotFound { candidates, .. } = e {
+                        result.suggestions = candidates;
+                    }
+                    return result;
                 }
             }
         }
 
-        let passed = ctx.all_passed();
-
         // Screenshot on assertion failure (passed = false but no execution error)
-        if !passed {
+        if !ctx.all_passed() {
             Self::maybe_screenshot(
                 session,
                 config,
                 &spec.name,
                 spec.steps.len(),
-                &mut screenshots,
+                &mut ctx.screenshots,
             );
         }
 
-        TestResult {
-            spec_name: spec.name.clone(),
-            passed,
-            steps_run: spec.steps.len(),
-            assertion_results: ctx.assertion_results,
-            error: None,
-            duration_ms: Some(test_start.elapsed().as_millis()),
-            screenshots,
-            retry_attempts: total_retry_attempts,
+        TestResult::finish(spec, spec.steps.len(), None, ctx)
+    }
+
+    /// Pull newly logged console messages into `ctx`, tagged with `step`.
+    ///
+    /// Servers without console capture are tolerated: the log just stays empty.
+    fn collect_console(session: &mut dyn BrowserBackend, ctx: &mut TestContext, step: Option<usize>) {
+        if let Ok(entries) = session.take_console_log() {
+            ctx.record_console(entries, step);
+        }
+    }
+
+    /// Pull newly opened dialogs into `ctx` (servers without dialog
+    /// support leave the list empty).
+    fn collect_dialogs(session: &mut dyn BrowserBackend, ctx: &mut TestContext) {
+        if let Ok(entries) = session.take_dialogs() {
+            ctx.record_dialogs(entries);
+        }
+    }
+
+    /// After the step following an `ExpectDialog`, check that it opened the
+    /// expected dialog and go back to dismissing dialogs.
+    fn check_expected_dialog(
+        step: &TestStep,
+        session: &mut dyn BrowserBackend,
+        ctx: &mut TestContext,
+    ) -> Result<(), AgentError> {
+        if matches!(step, TestStep::ExpectDialog { .. }) {
+            return Ok(());
+        }
+        let seen_before = ctx.dialogs.len();
+        Self::collect_dialogs(session, ctx);
+        let Some(expected) = ctx.expected_dialog.take() else {
+            return Ok(());
+        };
+        session.set_dialog_policy(&DialogPolicy::default())?;
+
+        let opened = &ctx.dialogs[seen_before..];
+        if opened.iter().any(|d| d.mentions(&expected)) {
+            return Ok(());
+        }
+        let wanted = if expected.is_empty() {
+            "a dialog".to_string()
+        } else {
+            format!("a dialog containing '{}'", expected)
+        };
+        let seen = if opened.is_empty() {
+            "none opened".to_string()
+        } else {
+            opened.iter().map(|d| d.summary()).collect::<Vec<_>>().join("; ")
+        };
+        Err(AgentError::BrowserAction(format!("Expected {}, got: {}", wanted, seen)))
+    }
+
+    /// Mark `result` failed if the page threw an uncaught exception.
+    ///
+    /// An existing error is kept; the first exception is reported otherwise.
+    fn fail_on_page_error(result: &mut TestResult) {
+        let page_errors: Vec<_> = result.console.iter().filter(|e| e.is_page_error()).collect();
+        let Some(first) = page_errors.first() else {
+            return;
+        };
+        result.passed = false;
+        if result.error.is_none() {
+            result.error = Some(format!(
+                "{} uncaught page error(s); first: {}",
+                page_errors.len(),
+                first.summary()
+            ));
         }
     }
 
+    /// Load the configured storage state for an `auth: true` spec.
+    fn load_auth_state(
+        session: &mut dyn BrowserBackend,
+        config: &RunnerConfig,
+    ) -> Result<(), AgentError> {
+        let path = config.storage_state.as_deref().ok_or_else(|| {
+            AgentError::MissingState(
+                "spec requires auth but no storage state is configured (auth.storage_state)".into(),
+            )
+        })?;
+        session.load_storage_state(path)
+    }
+
     /// Execute a step. For Assert steps, retry if assertions fail.
     /// Returns Ok(retry_count) on success, Err on unrecoverable failure.
     fn execute_step_with_retry(
         step: &TestStep,
         step_index: usize,
-        session: &mut BrowserSession,
+        session: &mut dyn BrowserBackend,
         ctx: &mut TestContext,
         config: &RunnerConfig,
     ) -> Result<usize, AgentError> {
@@ -107,7 +255,7 @@ impl TestRunner {
         if let TestStep::Assert { assertions } = step {
             let mut retries_used = 0;
             for attempt in 0..=config.max_assertion_retries {
-                let results = Self::evaluate_assertions(assertions, step_index, session);
+                let results = Self::evaluate_assertions(assertions, step_index, session, ctx);
                 let all_passed = results.iter().all(|r| r.passed);
 
                 if all_passed || attempt == config.max_assertion_retries {
@@ -132,13 +280,14 @@ impl TestRunner {
     fn execute_step(
         step: &TestStep,
         step_index: usize,
-        session: &mut BrowserSession,
+        session: &mut dyn BrowserBackend,
         ctx: &mut TestContext,
     ) -> Result<(), AgentError> {
         match step {
-            TestStep::FillForm { form, values } => {
+            TestStep::FillForm { form, values, frame_path } => {
                 for (label, value) in values {
-                    let selector = Self::input_selector(label, Some(form));
+                    let mut selector = Self::input_selector(label, Some(form));
+                    selector.frame_path = frame_path.clone();
                     session.fill(&selector, value)?;
                 }
                 Ok(())
@@ -148,46 +297,150 @@ impl TestRunner {
                 form,
                 values,
                 submit_label,
+                frame_path,
             } => {
                 // Fill all inputs
                 for (label, value) in values {
-                    let selector = Self::input_selector(label, Some(form));
+                    let mut selector = Self::input_selector(label, Some(form));
+                    selector.frame_path = frame_path.clone();
                     session.fill(&selector, value)?;
                 }
                 // Click submit if provided
                 if let Some(label) = submit_label {
-                    let selector = Self::button_selector(label);
+                    let mut selector = Self::button_selector(label);
+                    selector.frame_path = frame_path.clone();
                     session.click(&selector)?;
                 }
                 Ok(())
             }
 
-            TestStep::Click { label } => {
-                let selector = Self::button_selector(label);
+            TestStep::Click { label, frame_path } => {
+                let mut selector = Self::button_selector(label);
+                selector.frame_path = frame_path.clone();
                 session.click(&selector)
             }
 
+            TestStep::Hover { label, frame_path } => {
+                let mut selector = Self::element_selector(label);
+                selector.frame_path = frame_path.clone();
+                session.hover(&selector)
+            }
+
+            TestStep::Press { key, label } => {
+                let selector = label.as_deref().map(Self::element_selector);
+                session.press(selector.as_ref(), key)
+            }
+
+            TestStep::Type {
+                label,
+                text,
+                form,
+                delay_ms,
+            } => {
+                let selector = Self::input_selector(label, form.as_deref());
+                session.type_text(&selector, text, *delay_ms)
+            }
+
+            TestStep::Scroll {
+                label,
+                delta_x,
+                delta_y,
+            } => match label {
+                Some(label) => session.scroll_into_view(&Self::element_selector(label)),
+                None => session.scroll_by(*delta_x, *delta_y),
+            },
+
+            TestStep::DragAndDrop { source, target } => {
+                session.drag_to(&Self::element_selector(source), &Self::element_selector(target))
+            }
+
+            TestStep::Upload { label, files, form } => {
+                let mut selector = Self::input_selector(label, form.as_deref());
+                selector.role = None;
+                selector.input_type = Some("file".into());
+                session.set_input_files(&selector, files)
+            }
+
             TestStep::Navigate { url } => session.navigate(url),
 
             TestStep::Wait { duration_ms } => session.wait_idle(*duration_ms),
 
             TestStep::Assert { assertions } => {
-                let results = Self::evaluate_assertions(assertions, step_index, session);
+                let results = Self::evaluate_assertions(assertions, step_index, session, ctx);
                 ctx.record_assertions(results);
                 Ok(())
             }
+
+            // "The newest tab" is only known once the popup has opened
+            TestStep::SwitchTab { tab, timeout_ms } if tab.is_empty() => {
+                session.wait_for_popup(*timeout_ms).map(|_| ())
+            }
+
+            TestStep::SwitchTab { tab, timeout_ms } => {
+                if session.switch_tab(tab).is_ok() {
+                    return Ok(());
+                }
+                // Not open yet: wait for the popup, then pick the requested tab
+                session.wait_for_popup(*timeout_ms)?;
+                session.switch_tab(tab).map(|_| ())
+            }
+
+            TestStep::CloseTab { tab } => session.close_tab(tab),
+
+            TestStep::ExpectDialog { contains, accept, prompt_text } => {
+                session.set_dialog_policy(&DialogPolicy::from_expectation(*accept, prompt_text.as_deref()))?;
+                ctx.expected_dialog = Some(contains.clone().unwrap_or_default());
+                Ok(())
+            }
+
+            TestStep::DismissModal => {
+                let semantics = Self::page_semantics(session)?;
+                match dismiss_action(semantics.elements()) {
+                    Some(button) => session.click(&SelectorHint {
+                        role: button.role.clone(),
+                        name: button.label.clone(),
+                        tag: button.tag.clone(),
+                        input_type: None,
+                        form_id: None,
+                        frame_path: Vec::new(),
+                        shadow_path: Vec::new(),
+                    }),
+                    None if semantics.modal_open() => session.press(None, "Escape"),
+                    None => Ok(()),
+                }
+            }
         }
     }
 
     /// Evaluate a list of assertions against the current page state.
+    ///
+    /// Network assertions first pull newly recorded requests into `ctx`.
     fn evaluate_assertions(
         assertions: &[AssertionSpec],
         step_index: usize,
-        session: &mut BrowserSession,
+        session: &mut dyn BrowserBackend,
+        ctx: &mut TestContext,
     ) -> Vec<AssertionResult> {
+        let mut network_error = None;
+        if assertions.iter().any(Self::is_network_assertion) {
+            match session.take_network_log() {
+                Ok(entries) => ctx.record_network(entries),
+                Err(e) => network_error = Some(e),
+            }
+        }
+
         assertions
             .iter()
-            .map(|spec| Self::evaluate_one(spec, step_index, session))
+            .map(|spec| match &network_error {
+                Some(e) if Self::is_network_assertion(spec) => AssertionResult {
+                    step_index,
+                    spec: spec.clone(),
+                    passed: false,
+                    actual: None,
+                    message: Some(format!("Failed to read network log: {}", e)),
+                },
+                _ => Self::evaluate_one(spec, step_index, session, ctx),
+            })
             .collect()
     }
 
@@ -195,8 +448,10 @@ impl TestRunner {
     fn evaluate_one(
         spec: &AssertionSpec,
         step_index: usize,
-        session: &mut BrowserSession,
+        session: &mut dyn BrowserBackend,
+        ctx: &TestContext,
     ) -> AssertionResult {
+        let network: &[NetworkEntry] = &ctx.network;
         match spec {
             AssertionSpec::UrlContains { expected } => {
                 match session.current_url() {
@@ -451,9 +706,221 @@ impl TestRunner {
                     },
                 }
             }
+
+            AssertionSpec::RequestMade {
+                url_contains,
+                method,
+            } => {
+                let matching = network
+                    .iter()
+                    .filter(|e| e.url.contains(url_contains.as_str()))
+                    .filter(|e| method.as_ref().is_none_or(|m| e.method.eq_ignore_ascii_case(m)))
+                    .count();
+                let passed = matching > 0;
+                AssertionResult {
+                    step_index,
+                    spec: spec.clone(),
+                    passed,
+                    actual: Some(format!("{} matching of {} requests", matching, network.len())),
+                    message: if passed {
+                        None
+                    } else {
+                        Some(format!(
+                            "No {}request to a URL containing '{}' was made",
+                            method.as_ref().map(|m| format!("{} ", m.to_uppercase())).unwrap_or_default(),
+                            url_contains
+                        ))
+                    },
+                }
+            }
+
+            AssertionSpec::NoFailedRequests { min_status } => {
+                let failed: Vec<String> = network
+                    .iter()
+                    .filter(|e| e.is_failed(*min_status))
+                    .map(|e| e.summary())
+                    .collect();
+                let passed = failed.is_empty();
+                AssertionResult {
+                    step_index,
+                    spec: spec.clone(),
+                    passed,
+                    actual: Some(format!("{} failed of {} requests", failed.len(), network.len())),
+                    message: if passed {
+                        None
+                    } else {
+                        Some(format!("Failed requests: {}", failed.join("; ")))
+                    },
+                }
+            }
+
+            AssertionSpec::DialogShown { contains } => {
+                let fragment = contains.as_deref().unwrap_or("");
+                let passed = ctx.dialogs.iter().any(|d| d.mentions(fragment));
+                AssertionResult {
+                    step_index,
+                    spec: spec.clone(),
+                    passed,
+                    actual: Some(format!("{} dialog(s) shown", ctx.dialogs.len())),
+                    message: if passed {
+                        None
+                    } else if fragment.is_empty() {
+                        Some("No dialog was shown".to_string())
+                    } else {
+                        Some(format!("No dialog containing '{}' was shown", fragment))
+                    },
+                }
+            }
+
+            AssertionSpec::ModalOpen { open } => {
+                let semantics = match Self::page_semantics(session) {
+                    Ok(semantics) => semantics,
+                    Err(e) => return Self::extract_failure(spec, step_index, e),
+                };
+                let actual = semantics.modal_open();
+                AssertionResult {
+                    step_index,
+                    spec: spec.clone(),
+                    passed: actual == *open,
+                    actual: Some(if actual { "modal open" } else { "no modal" }.to_string()),
+                    message: match (open, actual) {
+                        (true, false) => Some("No modal is open".to_string()),
+                        (false, true) => Some("A modal is open".to_string()),
+                        _ => None,
+                    },
+                }
+            }
+
+            AssertionSpec::ToastShown { contains } => {
+                let semantics = match Self::page_semantics(session) {
+                    Ok(semantics) => semantics,
+                    Err(e) => return Self::extract_failure(spec, step_index, e),
+                };
+                let toasts = semantics.toasts();
+                let fragment = contains.as_deref().unwrap_or("").to_lowercase();
+                let passed = toasts.iter().any(|t| t.to_lowercase().contains(&fragment));
+                AssertionResult {
+                    step_index,
+                    spec: spec.clone(),
+                    passed,
+                    actual: Some(toasts.join(" | ")),
+                    message: if passed {
+                        None
+                    } else if fragment.is_empty() {
+                        Some("No toast is shown".to_string())
+                    } else {
+                        Some(format!("No toast containing '{}' is shown", contains.as_deref().unwrap_or("")))
+                    },
+                }
+            }
+
+            AssertionSpec::TableRows { table, min } => {
+                Self::evaluate_table(spec, step_index, session, table.as_deref(), None, |t| {
+                    let rows = t.row_count();
+                    let passed = rows >= *min;
+                    let message = (!passed)
+                        .then(|| format!("Table '{}' has {} row(s), expected at least {}", t.id, rows, min));
+                    (passed, format!("{} rows", rows), message)
+                })
+            }
+
+            AssertionSpec::TableColumnContains { table, column, expected } => {
+                Self::evaluate_table(spec, step_index, session, table.as_deref(), Some(column), |t| {
+                    let wanted = expected.to_lowercase();
+                    let cells = t.column(column).unwrap_or_default();
+                    let passed = cells.iter().any(|c| c.to_lowercase().contains(&wanted));
+                    let message = (!passed).then(|| {
+                        format!("No cell of column '{}' in table '{}' contains '{}'", column, t.id, expected)
+                    });
+                    (passed, cells.join(" | "), message)
+                })
+            }
+
+            AssertionSpec::TableSorted { table, column, descending } => {
+                Self::evaluate_table(spec, step_index, session, table.as_deref(), Some(column), |t| {
+                    let passed = t.is_sorted_by(column, *descending).unwrap_or(false);
+                    let message = (!passed).then(|| {
+                        format!(
+                            "Column '{}' of table '{}' is not sorted {}",
+                            column,
+                            t.id,
+                            if *descending { "descending" } else { "ascending" }
+                        )
+                    });
+                    (passed, t.column(column).unwrap_or_default().join(" | "), message)
+                })
+            }
         }
     }
 
+    /// Evaluate a table assertion: find the table on the current page (see
+    /// `find_table`) and let `check` return (passed, actual, failure message).
+    fn evaluate_table(
+        spec: &AssertionSpec,
+        step_index: usize,
+        session: &mut dyn BrowserBackend,
+        table: Option<&str>,
+        column: Option<&str>,
+        check: impl Fn(&DataTable) -> (bool, String, Option<String>),
+    ) -> AssertionResult {
+        let tables = match Self::page_semantics(session) {
+            Ok(semantics) => semantics.tables,
+            Err(e) => return Self::extract_failure(spec, step_index, e),
+        };
+        match find_table(&tables, table, column) {
+            Some(found) => {
+                let (passed, actual, message) = check(found);
+                AssertionResult {
+                    step_index,
+                    spec: spec.clone(),
+                    passed,
+                    actual: Some(actual),
+                    message,
+                }
+            }
+            None => AssertionResult {
+                step_index,
+                spec: spec.clone(),
+                passed: false,
+                actual: Some(format!("{} table(s) on page", tables.len())),
+                message: Some(match (table, column) {
+                    (Some(name), _) => format!("No table '{}' on page", name),
+                    (None, Some(column)) => format!("No table with a '{}' column on page", column),
+                    (None, None) => "No table on page".to_string(),
+                }),
+            },
+        }
+    }
+
+    /// Classified elements, tables and overlays of the current page.
+    fn page_semantics(session: &mut dyn BrowserBackend) -> Result<ScreenSemantics, AgentError> {
+        let data = session.extract()?;
+        let elements: Vec<DomElement> =
+            serde_json::from_value(data["dom"].clone()).map_err(|e| AgentError::JsonParse {
+                context: "extracted DOM".into(),
+                source: e,
+            })?;
+        Ok(classify(&elements))
+    }
+
+    /// Failed assertion for a page whose DOM could not be read.
+    fn extract_failure(spec: &AssertionSpec, step_index: usize, error: AgentError) -> AssertionResult {
+        AssertionResult {
+            step_index,
+            spec: spec.clone(),
+            passed: false,
+            actual: None,
+            message: Some(format!("Failed to extract DOM: {}", error)),
+        }
+    }
+
+    fn is_network_assertion(spec: &AssertionSpec) -> bool {
+        matches!(
+            spec,
+            AssertionSpec::RequestMade { .. } | AssertionSpec::NoFailedRequests { .. }
+        )
+    }
+
     /// Collect all visible text from extracted DOM data (lowercased for matching).
     fn collect_text_from_dom(data: &serde_json::Value) -> String {
         let mut texts = Vec::new();
@@ -478,6 +945,8 @@ impl TestRunner {
             tag: Some("input".into()),
             input_type: None,
             form_id: form_id.map(|s| s.to_string()),
+            frame_path: Vec::new(),
+            shadow_path: Vec::new(),
         }
     }
 
@@ -489,12 +958,67 @@ impl TestRunner {
             tag: None,
             input_type: None,
             form_id: None,
+            frame_path: Vec::new(),
+            shadow_path: Vec::new(),
+        }
+    }
+
+    /// Build a SelectorHint for any interactive element by label.
+    fn element_selector(label: &str) -> SelectorHint {
+        SelectorHint {
+            role: None,
+            name: Some(label.to_string()),
+            tag: None,
+            input_type: None,
+            form_id: None,
+            frame_path: Vec::new(),
+            shadow_path: Vec::new(),
         }
     }
 
+    /// Write the test's network traffic as a HAR file if configured.
+    /// Returns the file path; silently ignores errors.
+    fn maybe_write_har(
+        session: &mut dyn BrowserBackend,
+        config: &RunnerConfig,
+        test_name: &str,
+        ctx: &mut TestContext,
+    ) -> Option<String> {
+        if !config.record_har {
+            return None;
+        }
+        ctx.record_network(session.take_network_log().ok()?);
+
+        let _ = std::fs::create_dir_all(&config.screenshot_dir);
+        let path = Self::artifact_path(config, test_name, "network", "har");
+        write_har(&path, &ctx.network, test_name).ok()?;
+        Some(path)
+    }
+
+    /// Stop a trace or video through `stop`, saving it in the screenshot
+    /// directory when `mode` keeps it for this result and discarding it
+    /// otherwise. Returns the saved path; a failed save is not fatal.
+    fn keep_artifact(
+        mode: ArtifactMode,
+        result: &TestResult,
+        config: &RunnerConfig,
+        suffix: &str,
+        extension: &str,
+        stop: impl FnOnce(Option<&str>) -> Result<(), AgentError>,
+    ) -> Option<String> {
+        if !mode.keeps(result.passed, result.retry_attempts) {
+            let _ = stop(None);
+            return None;
+        }
+        let _ = std::fs::create_dir_all(&config.screenshot_dir);
+        let path = Self::artifact_path(config, &result.spec_name, suffix, extension);
+        stop(Some(&path)).ok()?;
+        Some(path)
+    }
+
     /// Capture a screenshot if configured. Silently ignores errors.
     fn maybe_screenshot(
-        session: &mut BrowserSession,
+        session: &mut dyn BrowserBackend,
         config: &RunnerConfig,
         test_name: &str,
         step_index: usize,
@@ -507,7 +1031,18 @@ impl TestRunner {
         // Ensure directory exists
         let _ = std::fs::create_dir_all(&config.screenshot_dir);
 
-        // Build filename: sanitized test name + step index + timestamp
+        let suffix = format!("step{}_failure", step_index);
+        let path = Self::artifact_path(config, test_name, &suffix, "png");
+
+        match session.screenshot(&path) {
+            Ok(()) => screenshots.push(path),
+            Err(_) => {} // Screenshot failure is not fatal
+        }
+    }
+
+    /// Build an artifact path in the screenshot directory:
+    /// sanitized test name + suffix + timestamp.
+    fn artifact_path(config: &RunnerConfig, test_name: &str, suffix: &str, extension: &str) -> String {
         let safe_name: String = test_name
             .chars()
             .map(|c| {
@@ -525,15 +1060,10 @@ impl TestRunner {
             .map(|d| d.as_millis())
             .unwrap_or(0);
 
-        let filename = format!("{}_step{}_failure_{}.png", safe_name, step_index, timestamp);
-        let path = std::path::Path::new(&config.screenshot_dir)
+        let filename = format!("{}_{}_{}.{}", safe_name, suffix, timestamp, extension);
+        std::path::Path::new(&config.screenshot_dir)
             .join(&filename)
             .to_string_lossy()
-            .to_string();
-
-        match session.screenshot(&path) {
-            Ok(()) => screenshots.push(path),
-            Err(_) => {} // Screenshot failure is not fatal
-        }
+            .to_string()
     }
 }
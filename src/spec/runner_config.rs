@@ -3,7 +3,8 @@ use serde::{Deserialize, Serialize};
 /// Configuration for test runner resilience features.
 ///
 /// Controls assertion retry behavior, screenshot capture on failure,
-/// and screenshot storage directory. Used by `TestRunner::run_with_config()`.
+/// screenshot storage directory, HAR recording, trace and video recording,
+/// page-error handling, and the auth state for `auth: true` specs. Used by `TestRunner::run_with_config()`.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RunnerConfig {
     /// Max retries for Assert steps when assertions fail (default: 2)
@@ -21,6 +22,61 @@ pub struct RunnerConfig {
     /// Directory for screenshot files (default: "screenshots")
     #[serde(default = "default_screenshot_dir")]
     pub screenshot_dir: String,
+
+    /// Write a HAR file of each test's network traffic next to the screenshots (default: false)
+    #[serde(default)]
+    pub record_har: bool,
+
+    /// When to keep a Playwright trace zip of each test (default: off)
+    #[serde(default)]
+    pub record_trace: ArtifactMode,
+
+    /// When to keep a video of each test (default: off)
+    #[serde(default)]
+    pub record_video: ArtifactMode,
+
+    /// Fail a test when the page throws an uncaught exception (default: false)
+    #[serde(default)]
+    pub fail_on_page_error: bool,
+
+    /// Storage-state file loaded before specs with `auth: true` (default: none)
+    #[serde(default)]
+    pub storage_state: Option<String>,
+}
+
+/// When a per-test artifact (trace, video) is recorded and kept.
+///
+/// Recording runs for every test unless `Off`; the mode decides whether the
+/// file is saved next to the screenshots or discarded when the test ends.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ArtifactMode {
+    /// Do not record
+    #[default]
+    Off,
+    /// Keep for every test
+    Always,
+    /// Keep for failed tests
+    OnFailure,
+    /// Keep for tests that needed assertion retries (flaky or failing)
+    OnRetry,
+}
+
+impl ArtifactMode {
+    /// Whether recording is enabled at all.
+    pub fn is_enabled(self) -> bool {
+        self != ArtifactMode::Off
+    }
+
+    /// Whether the artifact of a test with this outcome is kept.
+    pub fn keeps(self, passed: bool, retry_attempts: usize) -> bool {
+        match self {
+            ArtifactMode::Off => false,
+            ArtifactMode::Always => true,
+            ArtifactMode::OnFailure => !passed,
+            ArtifactMode::OnRetry => retry_attempts > 0,
+        }
+    }
 }
 
 impl Default for RunnerConfig {
@@ -30,6 +86,11 @@ impl Default for RunnerConfig {
             retry_delay_ms: 500,
             screenshot_on_failure: true,
             screenshot_dir: "screenshots".to_string(),
+            record_har: false,
+            record_trace: ArtifactMode::Off,
+            record_video: ArtifactMode::Off,
+            fail_on_page_error: false,
+            storage_state: None,
         }
     }
 }
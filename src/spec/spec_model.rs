@@ -2,6 +2,13 @@ use std::collections::HashMap;
 
 use serde::{Deserialize, Serialize};
 
+use crate::browser::candidates::ElementCandidate;
+use crate::browser::console::ConsoleEntry;
+use crate::browser::options::SessionOptions;
+use crate::browser::routes::RouteMock;
+use crate::browser::tabs::TabSelector;
+use crate::spec::context::TestContext;
+
 /// A complete test specification. Built in-memory by AI (Phase 5) or
 /// deserialized from YAML for human review and execution.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -14,6 +21,20 @@ pub struct TestSpec {
 
     /// Ordered list of test steps to execute
     pub steps: Vec<TestStep>,
+
+    /// Browser context overrides for this spec (viewport, device, locale, ...)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub options: Option<SessionOptions>,
+
+    /// Requires an authenticated user: the runner loads the saved storage
+    /// state (`auth.storage_state`) before navigating to `start_url`
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub auth: bool,
+
+    /// Canned responses for matching requests, installed before `start_url`
+    /// loads and removed when the test ends
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub mocks: Vec<RouteMock>,
 }
 
 /// A single step in a test spec.
@@ -24,6 +45,9 @@ pub enum TestStep {
     FillForm {
         form: String,
         values: HashMap<String, String>,
+        /// Frame containing the form (see `DomElement::frame_path`); empty for the main frame
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        frame_path: Vec<String>,
     },
 
     /// Fill all fields and submit a form in one step
@@ -31,11 +55,68 @@ pub enum TestStep {
         form: String,
         values: HashMap<String, String>,
         submit_label: Option<String>,
+        /// Frame containing the form; empty for the main frame
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        frame_path: Vec<String>,
     },
 
     /// Click a button/link by label
     Click {
         label: String,
+        /// Frame containing the element; empty for the main frame
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        frame_path: Vec<String>,
+    },
+
+    /// Move the mouse over an element (hover menus, tooltips)
+    Hover {
+        label: String,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        frame_path: Vec<String>,
+    },
+
+    /// Press a key or chord ("Enter", "Control+A") on the element labelled
+    /// `label`, or on the focused element when no label is given
+    Press {
+        key: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        label: Option<String>,
+    },
+
+    /// Type into an input key by key, for widgets that react to each
+    /// keystroke (autocomplete, masked inputs)
+    Type {
+        label: String,
+        text: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        form: Option<String>,
+        #[serde(default = "default_type_delay")]
+        delay_ms: u64,
+    },
+
+    /// Scroll the element labelled `label` into view, or scroll the page by
+    /// `delta_x`/`delta_y` pixels when no label is given
+    Scroll {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        label: Option<String>,
+        #[serde(default)]
+        delta_x: i64,
+        #[serde(default)]
+        delta_y: i64,
+    },
+
+    /// Drag the element labelled `source` onto the element labelled `target`
+    DragAndDrop {
+        source: String,
+        target: String,
+    },
+
+    /// Set the files of a file input (paths relative to the working directory)
+    Upload {
+        label: String,
+        files: Vec<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        form: Option<String>,
     },
 
     /// Navigate to a different URL
@@ -52,6 +133,54 @@ pub enum TestStep {
     Assert {
         assertions: Vec<AssertionSpec>,
     },
+
+    /// Make another tab active, waiting up to `timeout_ms` for a popup to
+    /// open if none matches yet. Without criteria it waits for the next
+    /// popup and switches to it.
+    SwitchTab {
+        #[serde(flatten)]
+        tab: TabSelector,
+        #[serde(default = "default_tab_timeout")]
+        timeout_ms: u64,
+    },
+
+    /// Close a tab (the active one when no criterion is given)
+    CloseTab {
+        #[serde(flatten)]
+        tab: TabSelector,
+    },
+
+    /// Answer the JavaScript dialog opened by the next step (OK, or Cancel
+    /// when `accept` is false, typing `prompt_text` into a prompt) and fail
+    /// that step unless a dialog whose message contains `contains` opens
+    ExpectDialog {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        contains: Option<String>,
+        #[serde(default = "default_accept_dialog")]
+        accept: bool,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        prompt_text: Option<String>,
+    },
+
+    /// Close the open modal (cookie banner, popup) with its dismiss button,
+    /// or with Escape when it has none; does nothing when no modal is open
+    DismissModal,
+}
+
+fn default_accept_dialog() -> bool {
+    true
+}
+
+fn default_modal_open() -> bool {
+    true
+}
+
+fn default_tab_timeout() -> u64 {
+    5000
+}
+
+fn default_type_delay() -> u64 {
+    50
 }
 
 /// A single assertion to evaluate against the page.
@@ -90,6 +219,73 @@ pub enum AssertionSpec {
         selector: String,
         expected: u32,
     },
+
+    /// A request whose URL contains `url_contains` was made during the test
+    /// (optionally with the given HTTP method, case-insensitive)
+    RequestMade {
+        url_contains: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        method: Option<String>,
+    },
+
+    /// No request so far failed at the network level or returned a status
+    /// of at least `min_status` (default: 400)
+    NoFailedRequests {
+        #[serde(default = "default_min_failed_status")]
+        min_status: u16,
+    },
+
+    /// A JavaScript dialog was opened during the test (one whose message
+    /// contains `contains`, case-insensitive, when given)
+    DialogShown {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        contains: Option<String>,
+    },
+
+    /// A table has at least `min` rows. `table` picks it by id or label
+    /// (see `find_table`); without one, the first table on the page is used
+    TableRows {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        table: Option<String>,
+        min: usize,
+    },
+
+    /// Some cell of column `column` contains `expected` (case-insensitive).
+    /// Without `table`, the first table having that column is used
+    TableColumnContains {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        table: Option<String>,
+        column: String,
+        expected: String,
+    },
+
+    /// Column `column` is sorted, ascending unless `descending`
+    /// (numerically when all its cells are numbers)
+    TableSorted {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        table: Option<String>,
+        column: String,
+        #[serde(default)]
+        descending: bool,
+    },
+
+    /// A modal (`role=dialog`, `aria-modal`) is open on the page, or with
+    /// `open: false`, none is
+    ModalOpen {
+        #[serde(default = "default_modal_open")]
+        open: bool,
+    },
+
+    /// A live-region message outside any modal (a toast) is on the page
+    /// (one containing `contains`, case-insensitive, when given)
+    ToastShown {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        contains: Option<String>,
+    },
+}
+
+fn default_min_failed_status() -> u16 {
+    400
 }
 
 /// Result of evaluating a single assertion.
@@ -140,4 +336,59 @@ pub struct TestResult {
     /// Number of assertion retry attempts used (0 = no retries needed)
     #[serde(default)]
     pub retry_attempts: usize,
+
+    /// HAR file with the network traffic of this test (when recording is enabled)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub har_path: Option<String>,
+
+    /// Playwright trace zip of this test (when kept by `record_trace`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trace_path: Option<String>,
+
+    /// Video of this test (when kept by `record_video`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub video_path: Option<String>,
+
+    /// Console messages and uncaught page errors, tagged with their step
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub console: Vec<ConsoleEntry>,
+
+    /// Elements resembling the one a failed step could not find, best first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub suggestions: Vec<ElementCandidate>,
+}
+
+impl TestResult {
+    /// Result of `spec` after `steps_run` steps, failed with `error` if the
+    /// run stopped early. Assertions, screenshots, retries and duration are
+    /// taken from `ctx`; HAR, console and artifacts are added by the runner.
+    pub fn finish(spec: &TestSpec, steps_run: usize, error: Option<String>, ctx: &mut TestContext) -> Self {
+        TestResult {
+            spec_name: spec.name.clone(),
+            passed: error.is_none() && ctx.all_passed(),
+            steps_run,
+            assertion_results: std::mem::take(&mut ctx.assertion_results),
+            error,
+            duration_ms: Some(ctx.started.elapsed().as_millis()),
+            screenshots: std::mem::take(&mut ctx.screenshots),
+            retry_attempts: ctx.retry_attempts,
+            har_path: None,
+            trace_path: None,
+            video_path: None,
+            console: Vec::new(),
+            suggestions: Vec::new(),
+        }
+    }
+
+    /// `finish` for a run that stopped early with `error`.
+    pub fn failed(spec: &TestSpec, steps_run: usize, error: String, ctx: &mut TestContext) -> Self {
+        Self::finish(spec, steps_run, Some(error), ctx)
+    }
+
+    /// "did you mean 'Sign In'?" for the best suggestion, if any.
+    pub fn did_you_mean(&self) -> Option<String> {
+        self.suggestions
+            .first()
+            .map(|c| format!("did you mean '{}'?", c.name))
+    }
 }
@@ -1,4 +1,4 @@
-use crate::screen::screen_model::{OutputRegion, ScreenElement, Volatility};
+use crate::screen::screen_model::{Overlay, OutputRegion, ScreenElement, StructuralOutline, Volatility};
 
 pub fn normalize_output_text(raw: &str) -> Option<String> {
     let text = raw.trim();
@@ -41,7 +41,40 @@ pub fn normalize_output_text(raw: &str) -> Option<String> {
     Some(normalized)
 }
 
-pub fn infer_output_region(el: &ScreenElement) -> OutputRegion {
+/// Where on the page an output sits.
+///
+/// Structure decides first: dialogs (the `Modal` overlay, see `overlay_of`)
+/// are `Modal`; the header, navigation and footer landmarks (the element
+/// itself or the one it sits in) are `Header` and `Footer`, and a
+/// complementary `aside` is `Unknown`. Elsewhere, repeated
+/// items (list items, table rows and cells, articles, listbox options) and
+/// anything under a "results" heading are `Results`; the rest is `Main`.
+/// Live regions (`role=status`) are toasts, not results.
+///
+/// Only when neither the element nor the page (`outline`) has any landmark
+/// is the region guessed from words in the text, as before.
+pub fn infer_output_region(el: &ScreenElement, outline: &StructuralOutline) -> OutputRegion {
+    let role = el.role.as_deref().unwrap_or("");
+    let tag = el.tag.as_deref().unwrap_or("");
+    if el.overlay == Some(Overlay::Modal) {
+        return OutputRegion::Modal;
+    }
+    let section = own_landmark(tag, role).or(el.semantic_section.as_deref());
+
+    match section {
+        Some("dialog") => return OutputRegion::Modal,
+        Some("header") | Some("nav") => return OutputRegion::Header,
+        Some("footer") => return OutputRegion::Footer,
+        Some("aside") => return OutputRegion::Unknown,
+        _ => {}
+    }
+    if el.overlay != Some(Overlay::Toast) && is_result(el, role) {
+        return OutputRegion::Results;
+    }
+    if section.is_some() || !outline.landmarks.is_empty() {
+        return OutputRegion::Main;
+    }
+
     let text = el.label.as_deref().unwrap_or("").to_lowercase();
 
     if text.contains("footer") || text.contains("privacy") || text.contains("terms") {
@@ -54,6 +87,28 @@ pub fn infer_output_region(el: &ScreenElement) -> OutputRegion {
     }
 }
 
+/// Landmark an element is itself, by tag or ARIA role (named like `semantic_section`).
+fn own_landmark(tag: &str, role: &str) -> Option<&'static str> {
+    match (tag, role) {
+        (_, "banner") | ("header", _) => Some("header"),
+        (_, "navigation") | ("nav", _) => Some("nav"),
+        (_, "contentinfo") | ("footer", _) => Some("footer"),
+        (_, "complementary") | ("aside", _) => Some("aside"),
+        (_, "dialog") | (_, "alertdialog") | ("dialog", _) => Some("dialog"),
+        _ => None,
+    }
+}
+
+fn is_result(el: &ScreenElement, role: &str) -> bool {
+    let tag = el.tag.as_deref().unwrap_or("");
+    matches!(tag, "li" | "tr" | "td" | "article")
+        || matches!(role, "listitem" | "row" | "cell" | "gridcell" | "article" | "option" | "feed")
+        || el
+            .section_heading
+            .as_deref()
+            .is_some_and(|h| h.to_lowercase().contains("result"))
+}
+
 pub fn classify_volatility(text: &str) -> Volatility {
     if text.len() > 200 {
         Volatility::Volatile
@@ -6,7 +6,23 @@ use crate::screen::screen_model::{ElementKind, Form, OutputRegion, ScreenElement
 use crate::state::normalize::{infer_output_region, normalize_output_text, text_fingerprint};
 
 pub fn build_state(url: Option<&str>, title: &str, semantics: ScreenSemantics) -> ScreenState {
-    let identities = resolve_identities(&semantics.forms, &semantics.outputs);
+    build_state_with_outline(url, title, semantics, StructuralOutline::default())
+}
+
+/// `build_state` for a page whose landmarks and headings are known; outputs
+/// are then placed by structure rather than wording (see `infer_output_region`).
+pub fn build_state_with_outline(
+    url: Option<&str>,
+    title: &str,
+    semantics: ScreenSemantics,
+    structural_outline: StructuralOutline,
+) -> ScreenState {
+    let identities = resolve_identities(
+        &semantics.forms,
+        &semantics.standalone_actions,
+        &semantics.outputs,
+        &structural_outline,
+    );
 
     ScreenState {
         url: Some(url.unwrap_or("<unknown>").to_string()),
@@ -14,15 +30,18 @@ pub fn build_state(url: Option<&str>, title: &str, semantics: ScreenSemantics) -
         forms: semantics.forms,
         standalone_actions: semantics.standalone_actions,
         outputs: semantics.outputs,
+        tables: semantics.tables,
         identities: identities,
-        structural_outline: StructuralOutline::default(),
+        structural_outline,
     }
 }
 
 /// Public entry point
 pub fn resolve_identities(
     forms: &[Form],
+    standalone_actions: &[ScreenElement],
     outputs: &[ScreenElement],
+    outline: &StructuralOutline,
 ) -> HashMap<String, IdentifiedElement> {
     let mut map = HashMap::new();
     let mut region_counters: HashMap<OutputRegion, usize> = HashMap::new();
@@ -45,9 +64,23 @@ pub fn resolve_identities(
         }
     }
 
+    for el in standalone_actions {
+        let id = element_identity(el, "screen");
+        map.insert(
+            id.clone(),
+            IdentifiedElement {
+                id,
+                element: el.clone(),
+                scope: "screen".to_string(),
+                region: infer_output_region(el, outline),
+                volatility: Volatility::Stable,
+            },
+        );
+    }
+
     for el in outputs {
         let scope = "screen".to_string();
-        let region = infer_output_region(el);
+        let region = infer_output_region(el, outline);
 
         let id = match normalize_output_text(el.label.as_deref().unwrap_or("")) {
             Some(text) => {
@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 
 use crate::{
-    screen::screen_model::{Form, ScreenElement, StructuralOutline},
+    screen::screen_model::{DataTable, Form, ScreenElement, StructuralOutline},
     state::identity::IdentifiedElement,
 };
 
@@ -13,6 +13,8 @@ pub struct ScreenState {
     pub forms: Vec<Form>,
     pub standalone_actions: Vec<ScreenElement>,
     pub outputs: Vec<ScreenElement>,
+    /// Tables, grids and repeated lists (see `classify`), in page order
+    pub tables: Vec<DataTable>,
     pub identities: HashMap<String, IdentifiedElement>,
     pub structural_outline: StructuralOutline,
 }
@@ -9,7 +9,7 @@ use screen_detection::{
         budget::{BudgetDecision, check_budgets},
         error::AgentError,
     },
-    browser::playwright::extract_screen,
+    browser::{playwright::extract_screen, static_html::extract_static},
     canonical::{
         canonical_model::{CanonicalScreenState, canonicalize},
         diff::{ActionDiff, FormDiff, OutputDiff, SemanticSignal, SemanticStateDiff, semantic_diff},
@@ -28,7 +28,7 @@ use screen_detection::{
 use screen_detection::agent::agent_model::Policy;
 
 use crate::common::{
-    semantic_diff::diff_static,
+    semantic_diff::{diff_between_html_pages, diff_html_initial, diff_static},
     utils::page,
 };
 
@@ -80,6 +80,10 @@ fn mock_screen_with_form() -> ScreenState {
         nearby_help_text: None,
         autocomplete: None,
         aria_describedby_text: None,
+        semantic_section: None,
+        aria_live: None,
+        overlay: None,
+        frame_path: Vec::new(),
             }],
             actions: vec![ScreenElement {
                 label: Some("Sign In".into()),
@@ -102,12 +106,18 @@ fn mock_screen_with_form() -> ScreenState {
         nearby_help_text: None,
         autocomplete: None,
         aria_describedby_text: None,
+        semantic_section: None,
+        aria_live: None,
+        overlay: None,
+        frame_path: Vec::new(),
             }],
             primary_action: None,
             intent: None,
+            frame_path: Vec::new(),
         }],
         standalone_actions: vec![],
         outputs: vec![],
+        tables: vec![],
         identities: HashMap::new(),
         structural_outline: Default::default(),
     }
@@ -529,6 +539,10 @@ fn deterministic_policy_multi_input_form() {
         nearby_help_text: None,
         autocomplete: None,
         aria_describedby_text: None,
+        semantic_section: None,
+        aria_live: None,
+        overlay: None,
+        frame_path: Vec::new(),
                 },
                 ScreenElement {
                     label: Some("Password".into()),
@@ -551,6 +565,10 @@ fn deterministic_policy_multi_input_form() {
         nearby_help_text: None,
         autocomplete: None,
         aria_describedby_text: None,
+        semantic_section: None,
+        aria_live: None,
+        overlay: None,
+        frame_path: Vec::new(),
                 },
                 ScreenElement {
                     label: Some("Phone".into()),
@@ -573,6 +591,10 @@ fn deterministic_policy_multi_input_form() {
         nearby_help_text: None,
         autocomplete: None,
         aria_describedby_text: None,
+        semantic_section: None,
+        aria_live: None,
+        overlay: None,
+        frame_path: Vec::new(),
                 },
             ],
             actions: vec![ScreenElement {
@@ -596,12 +618,18 @@ fn deterministic_policy_multi_input_form() {
         nearby_help_text: None,
         autocomplete: None,
         aria_describedby_text: None,
+        semantic_section: None,
+        aria_live: None,
+        overlay: None,
+        frame_path: Vec::new(),
             }],
             primary_action: None,
             intent: None,
+            frame_path: Vec::new(),
         }],
         standalone_actions: vec![],
         outputs: vec![],
+        tables: vec![],
         identities: HashMap::new(),
         structural_outline: Default::default(),
     };
@@ -645,6 +673,7 @@ fn agent_error_display_messages() {
     let not_found = AgentError::ElementNotFound {
         element: "Submit".into(),
         context: "form 'login'".into(),
+        candidates: Vec::new(),
     };
     assert!(not_found.to_string().contains("Submit"), "ElementNotFound contains element");
     assert!(not_found.to_string().contains("form 'login'"), "ElementNotFound contains context");
@@ -672,6 +701,7 @@ fn agent_error_source_chain() {
     let not_found = AgentError::ElementNotFound {
         element: "x".into(),
         context: "y".into(),
+        candidates: Vec::new(),
     };
     assert!(not_found.source().is_none(), "ElementNotFound should have no source");
 }
@@ -751,6 +781,91 @@ fn deterministic_policy_waits_on_error_appeared() {
     }
 }
 
+#[test]
+fn deterministic_policy_closes_an_opened_modal() {
+    let policy = DeterministicPolicy;
+    let raw = extract_static(&page("14_catalog_cookie_banner.html")).unwrap();
+    let elements: Vec<DomElement> = serde_json::from_value(raw["dom"].clone()).unwrap();
+    let screen = build_state(Some("https://example.com"), "Catalog", classify(&elements));
+    let diff = diff_with_signal(SemanticSignal::ModalOpened);
+    let memory = AgentMemory::default();
+
+    let decision = policy.decide(&screen, &diff, &memory).unwrap();
+    assert!(matches!(decision.decision, DecisionType::Act));
+    match &decision.next_action {
+        Some(AgentAction::ClickAction { label, identity }) => {
+            // "Close" is preferred over "Accept all"
+            assert_eq!(label, "Close");
+            let target = &screen.identities[identity.as_deref().unwrap()];
+            assert_eq!(target.element.label.as_deref(), Some("Close"));
+        }
+        other => panic!("Expected ClickAction, got {:?}", other),
+    }
+
+    // Without a recognisable button in the modal there is nothing to click
+    let decision = policy.decide(&mock_screen_with_form(), &diff, &memory).unwrap();
+    assert!(matches!(decision.decision, DecisionType::Wait));
+    assert!(matches!(decision.next_action, Some(AgentAction::Wait { .. })));
+}
+
+#[test]
+fn deterministic_policy_waits_after_modal_closed() {
+    let policy = DeterministicPolicy;
+    let screen = mock_screen_with_form();
+    let memory = AgentMemory::default();
+
+    let decision = policy
+        .decide(&screen, &diff_with_signal(SemanticSignal::ModalClosed), &memory)
+        .unwrap();
+    assert!(matches!(decision.decision, DecisionType::Wait));
+    assert_eq!(
+        decision.next_action,
+        Some(AgentAction::Wait { reason: "Modal closed".into() })
+    );
+
+    let toast = SemanticSignal::ToastAppeared { text: "Saved".into() };
+    let decision = policy.decide(&screen, &diff_with_signal(toast), &memory).unwrap();
+    assert!(matches!(decision.decision, DecisionType::Wait));
+    assert_eq!(
+        decision.next_action,
+        Some(AgentAction::Wait { reason: "Toast appeared: Saved".into() })
+    );
+}
+
+#[test]
+fn deterministic_policy_does_not_resubmit_after_closing_a_submit_modal() {
+    let policy = DeterministicPolicy;
+    let memory = AgentMemory::default();
+    let screen_of = |name: &str| {
+        let raw = extract_static(&page(name)).unwrap();
+        let elements: Vec<DomElement> = serde_json::from_value(raw["dom"].clone()).unwrap();
+        build_state(Some("https://example.com"), "Newsletter", classify(&elements))
+    };
+    let form_page = "16_newsletter.html";
+    let modal_page = "17_newsletter_thanks_modal.html";
+
+    // Submit the form
+    let diff = diff_html_initial(&page(form_page));
+    let decision = policy.decide(&screen_of(form_page), &diff, &memory).unwrap();
+    assert!(matches!(decision.next_action, Some(AgentAction::FillAndSubmitForm { .. })));
+
+    // The submit opened a modal: close it
+    let diff = diff_between_html_pages(&page(form_page), &page(modal_page));
+    assert_eq!(diff.signals.last(), Some(&SemanticSignal::ModalOpened));
+    let decision = policy.decide(&screen_of(modal_page), &diff, &memory).unwrap();
+    assert!(matches!(
+        &decision.next_action,
+        Some(AgentAction::ClickAction { label, .. }) if label == "Close"
+    ));
+
+    // Closed: the form is back, but submitting it again would reopen the modal
+    let diff = diff_between_html_pages(&page(modal_page), &page(form_page));
+    assert_eq!(diff.signals, vec![SemanticSignal::ModalClosed]);
+    let decision = policy.decide(&screen_of(form_page), &diff, &memory).unwrap();
+    assert!(matches!(decision.decision, DecisionType::Wait));
+    assert!(matches!(decision.next_action, Some(AgentAction::Wait { .. })));
+}
+
 // =========================================================================
 // DeterministicPolicy â€” edge cases
 // =========================================================================
@@ -764,6 +879,7 @@ fn deterministic_policy_no_forms_returns_none() {
         forms: vec![],
         standalone_actions: vec![],
         outputs: vec![],
+        tables: vec![],
         identities: HashMap::new(),
         structural_outline: Default::default(),
     };
@@ -804,12 +920,18 @@ fn deterministic_policy_form_no_inputs_still_submits() {
         nearby_help_text: None,
         autocomplete: None,
         aria_describedby_text: None,
+        semantic_section: None,
+        aria_live: None,
+        overlay: None,
+        frame_path: Vec::new(),
             }],
             primary_action: None,
             intent: None,
+            frame_path: Vec::new(),
         }],
         standalone_actions: vec![],
         outputs: vec![],
+        tables: vec![],
         identities: HashMap::new(),
         structural_outline: Default::default(),
     };
@@ -898,6 +1020,7 @@ fn execute_action_errors_on_missing_url() {
         forms: vec![],
         standalone_actions: vec![],
         outputs: vec![],
+        tables: vec![],
         identities: HashMap::new(),
         structural_outline: Default::default(),
     };
@@ -925,6 +1048,7 @@ fn execute_action_errors_on_missing_element() {
         forms: vec![],
         standalone_actions: vec![],
         outputs: vec![],
+        tables: vec![],
         identities: HashMap::new(),
         structural_outline: Default::default(),
     };
@@ -983,6 +1107,7 @@ fn execute_action_navigate_to_is_noop_in_subprocess_mode() {
         forms: vec![],
         standalone_actions: vec![],
         outputs: vec![],
+        tables: vec![],
         identities: HashMap::new(),
         structural_outline: Default::default(),
     };
@@ -1005,6 +1130,7 @@ fn execute_action_session_errors_on_missing_element() {
         forms: vec![],
         standalone_actions: vec![],
         outputs: vec![],
+        tables: vec![],
         identities: HashMap::new(),
         structural_outline: Default::default(),
     };
@@ -1143,17 +1269,23 @@ fn rank_form_scores_inputs() {
         nearby_help_text: None,
         autocomplete: None,
         aria_describedby_text: None,
+        semantic_section: None,
+        aria_live: None,
+        overlay: None,
+        frame_path: Vec::new(),
         }],
         actions: vec![], primary_action: None, intent: None,
+        frame_path: Vec::new(),
     };
     let big_form = Form {
         id: "big".into(),
         inputs: vec![
-            ScreenElement { label: Some("Email".into()), kind: ElementKind::Input, tag: Some("input".into()), role: None, input_type: None, required: false, placeholder: None, id: None, href: None, options: None, name: None, value: None, maxlength: None, minlength: None, readonly: false, fieldset_legend: None, section_heading: None, nearby_help_text: None, autocomplete: None, aria_describedby_text: None },
-            ScreenElement { label: Some("Password".into()), kind: ElementKind::Input, tag: Some("input".into()), role: None, input_type: None, required: false, placeholder: None, id: None, href: None, options: None, name: None, value: None, maxlength: None, minlength: None, readonly: false, fieldset_legend: None, section_heading: None, nearby_help_text: None, autocomplete: None, aria_describedby_text: None },
-            ScreenElement { label: Some("Name".into()), kind: ElementKind::Input, tag: Some("input".into()), role: None, input_type: None, required: false, placeholder: None, id: None, href: None, options: None, name: None, value: None, maxlength: None, minlength: None, readonly: false, fieldset_legend: None, section_heading: None, nearby_help_text: None, autocomplete: None, aria_describedby_text: None },
+            ScreenElement { label: Some("Email".into()), kind: ElementKind::Input, tag: Some("input".into()), role: None, input_type: None, required: false, placeholder: None, id: None, href: None, options: None, name: None, value: None, maxlength: None, minlength: None, readonly: false, fieldset_legend: None, section_heading: None, nearby_help_text: None, autocomplete: None, aria_describedby_text: None, semantic_section: None, aria_live: None, overlay: None, frame_path: Vec::new() },
+            ScreenElement { label: Some("Password".into()), kind: ElementKind::Input, tag: Some("input".into()), role: None, input_type: None, required: false, placeholder: None, id: None, href: None, options: None, name: None, value: None, maxlength: None, minlength: None, readonly: false, fieldset_legend: None, section_heading: None, nearby_help_text: None, autocomplete: None, aria_describedby_text: None, semantic_section: None, aria_live: None, overlay: None, frame_path: Vec::new() },
+            ScreenElement { label: Some("Name".into()), kind: ElementKind::Input, tag: Some("input".into()), role: None, input_type: None, required: false, placeholder: None, id: None, href: None, options: None, name: None, value: None, maxlength: None, minlength: None, readonly: false, fieldset_legend: None, section_heading: None, nearby_help_text: None, autocomplete: None, aria_describedby_text: None, semantic_section: None, aria_live: None, overlay: None, frame_path: Vec::new() },
         ],
         actions: vec![], primary_action: None, intent: None,
+        frame_path: Vec::new(),
     };
     assert!(rank_form(&big_form) > rank_form(&small_form));
 }
@@ -1171,6 +1303,10 @@ fn rank_form_scores_primary_action() {
         nearby_help_text: None,
         autocomplete: None,
         aria_describedby_text: None,
+        semantic_section: None,
+        aria_live: None,
+        overlay: None,
+        frame_path: Vec::new(),
         }],
         actions: vec![],
         primary_action: Some(ScreenElement {
@@ -1182,8 +1318,13 @@ fn rank_form_scores_primary_action() {
         nearby_help_text: None,
         autocomplete: None,
         aria_describedby_text: None,
+        semantic_section: None,
+        aria_live: None,
+        overlay: None,
+        frame_path: Vec::new(),
         }),
         intent: None,
+        frame_path: Vec::new(),
     };
     let without_action = Form {
         id: "b".into(),
@@ -1196,8 +1337,13 @@ fn rank_form_scores_primary_action() {
         nearby_help_text: None,
         autocomplete: None,
         aria_describedby_text: None,
+        semantic_section: None,
+        aria_live: None,
+        overlay: None,
+        frame_path: Vec::new(),
         }],
         actions: vec![], primary_action: None, intent: None,
+        frame_path: Vec::new(),
     };
     assert!(rank_form(&with_action) > rank_form(&without_action));
 }
@@ -1208,11 +1354,13 @@ fn rank_form_scores_intent_confidence() {
         id: "a".into(), inputs: vec![], actions: vec![],
         primary_action: None,
         intent: Some(FormIntent { label: "Auth".into(), confidence: 0.8, signals: vec![] }),
+        frame_path: Vec::new(),
     };
     let low_intent = Form {
         id: "b".into(), inputs: vec![], actions: vec![],
         primary_action: None,
         intent: Some(FormIntent { label: "Unknown".into(), confidence: 0.2, signals: vec![] }),
+        frame_path: Vec::new(),
     };
     assert!(rank_form(&high_intent) > rank_form(&low_intent));
 }
@@ -1222,6 +1370,7 @@ fn rank_form_empty_form_scores_zero() {
     let empty = Form {
         id: "e".into(), inputs: vec![], actions: vec![],
         primary_action: None, intent: None,
+        frame_path: Vec::new(),
     };
     assert_eq!(rank_form(&empty), 0.0);
 }
@@ -1239,14 +1388,19 @@ fn select_best_form_chooses_login_over_newsletter() {
         nearby_help_text: None,
         autocomplete: None,
         aria_describedby_text: None,
+        semantic_section: None,
+        aria_live: None,
+        overlay: None,
+        frame_path: Vec::new(),
         }],
         actions: vec![], primary_action: None, intent: None,
+        frame_path: Vec::new(),
     };
     let login = Form {
         id: "login".into(),
         inputs: vec![
-            ScreenElement { label: Some("Email".into()), kind: ElementKind::Input, tag: Some("input".into()), role: None, input_type: Some("email".into()), required: false, placeholder: None, id: None, href: None, options: None, name: None, value: None, maxlength: None, minlength: None, readonly: false, fieldset_legend: None, section_heading: None, nearby_help_text: None, autocomplete: None, aria_describedby_text: None },
-            ScreenElement { label: Some("Password".into()), kind: ElementKind::Input, tag: Some("input".into()), role: None, input_type: Some("password".into()), required: false, placeholder: None, id: None, href: None, options: None, name: None, value: None, maxlength: None, minlength: None, readonly: false, fieldset_legend: None, section_heading: None, nearby_help_text: None, autocomplete: None, aria_describedby_text: None },
+            ScreenElement { label: Some("Email".into()), kind: ElementKind::Input, tag: Some("input".into()), role: None, input_type: Some("email".into()), required: false, placeholder: None, id: None, href: None, options: None, name: None, value: None, maxlength: None, minlength: None, readonly: false, fieldset_legend: None, section_heading: None, nearby_help_text: None, autocomplete: None, aria_describedby_text: None, semantic_section: None, aria_live: None, overlay: None, frame_path: Vec::new() },
+            ScreenElement { label: Some("Password".into()), kind: ElementKind::Input, tag: Some("input".into()), role: None, input_type: Some("password".into()), required: false, placeholder: None, id: None, href: None, options: None, name: None, value: None, maxlength: None, minlength: None, readonly: false, fieldset_legend: None, section_heading: None, nearby_help_text: None, autocomplete: None, aria_describedby_text: None, semantic_section: None, aria_live: None, overlay: None, frame_path: Vec::new() },
         ],
         actions: vec![],
         primary_action: Some(ScreenElement {
@@ -1258,8 +1412,13 @@ fn select_best_form_chooses_login_over_newsletter() {
         nearby_help_text: None,
         autocomplete: None,
         aria_describedby_text: None,
+        semantic_section: None,
+        aria_live: None,
+        overlay: None,
+        frame_path: Vec::new(),
         }),
         intent: Some(FormIntent { label: "Authentication".into(), confidence: 0.8, signals: vec![IntentSignal::InputType("password".into())] }),
+        frame_path: Vec::new(),
     };
     let forms = vec![newsletter, login];
     let best = select_best_form(&forms).unwrap();
@@ -1290,6 +1449,10 @@ fn deterministic_policy_uses_page_category() {
         nearby_help_text: None,
         autocomplete: None,
         aria_describedby_text: None,
+        semantic_section: None,
+        aria_live: None,
+        overlay: None,
+        frame_path: Vec::new(),
             }],
             actions: vec![ScreenElement {
                 label: Some("Sign In".into()), kind: ElementKind::Action, tag: Some("button".into()),
@@ -1301,11 +1464,17 @@ fn deterministic_policy_uses_page_category() {
         nearby_help_text: None,
         autocomplete: None,
         aria_describedby_text: None,
+        semantic_section: None,
+        aria_live: None,
+        overlay: None,
+        frame_path: Vec::new(),
             }],
             primary_action: None, intent: None,
+            frame_path: Vec::new(),
         }],
         standalone_actions: vec![], outputs: vec![], identities: HashMap::new(),
         structural_outline: Default::default(),
+        tables: vec![],
     };
     let diff = SemanticStateDiff {
         forms: FormDiff { added: vec![], removed: vec![], changed: vec![] },
@@ -1344,15 +1513,20 @@ fn deterministic_policy_selects_main_form() {
         nearby_help_text: None,
         autocomplete: None,
         aria_describedby_text: None,
+        semantic_section: None,
+        aria_live: None,
+        overlay: None,
+        frame_path: Vec::new(),
                 }],
                 actions: vec![], primary_action: None, intent: None,
+                frame_path: Vec::new(),
             },
             Form {
                 id: "contact".into(),
                 inputs: vec![
-                    ScreenElement { label: Some("Name".into()), kind: ElementKind::Input, tag: Some("input".into()), role: None, input_type: Some("text".into()), required: false, placeholder: None, id: None, href: None, options: None, name: None, value: None, maxlength: None, minlength: None, readonly: false, fieldset_legend: None, section_heading: None, nearby_help_text: None, autocomplete: None, aria_describedby_text: None },
-                    ScreenElement { label: Some("Email".into()), kind: ElementKind::Input, tag: Some("input".into()), role: None, input_type: Some("email".into()), required: false, placeholder: None, id: None, href: None, options: None, name: None, value: None, maxlength: None, minlength: None, readonly: false, fieldset_legend: None, section_heading: None, nearby_help_text: None, autocomplete: None, aria_describedby_text: None },
-                    ScreenElement { label: Some("Message".into()), kind: ElementKind::Input, tag: Some("textarea".into()), role: None, input_type: None, required: false, placeholder: None, id: None, href: None, options: None, name: None, value: None, maxlength: None, minlength: None, readonly: false, fieldset_legend: None, section_heading: None, nearby_help_text: None, autocomplete: None, aria_describedby_text: None },
+                    ScreenElement { label: Some("Name".into()), kind: ElementKind::Input, tag: Some("input".into()), role: None, input_type: Some("text".into()), required: false, placeholder: None, id: None, href: None, options: None, name: None, value: None, maxlength: None, minlength: None, readonly: false, fieldset_legend: None, section_heading: None, nearby_help_text: None, autocomplete: None, aria_describedby_text: None, semantic_section: None, aria_live: None, overlay: None, frame_path: Vec::new() },
+                    ScreenElement { label: Some("Email".into()), kind: ElementKind::Input, tag: Some("input".into()), role: None, input_type: Some("email".into()), required: false, placeholder: None, id: None, href: None, options: None, name: None, value: None, maxlength: None, minlength: None, readonly: false, fieldset_legend: None, section_heading: None, nearby_help_text: None, autocomplete: None, aria_describedby_text: None, semantic_section: None, aria_live: None, overlay: None, frame_path: Vec::new() },
+                    ScreenElement { label: Some("Message".into()), kind: ElementKind::Input, tag: Some("textarea".into()), role: None, input_type: None, required: false, placeholder: None, id: None, href: None, options: None, name: None, value: None, maxlength: None, minlength: None, readonly: false, fieldset_legend: None, section_heading: None, nearby_help_text: None, autocomplete: None, aria_describedby_text: None, semantic_section: None, aria_live: None, overlay: None, frame_path: Vec::new() },
                 ],
                 actions: vec![],
                 primary_action: Some(ScreenElement {
@@ -1365,12 +1539,18 @@ fn deterministic_policy_selects_main_form() {
         nearby_help_text: None,
         autocomplete: None,
         aria_describedby_text: None,
+        semantic_section: None,
+        aria_live: None,
+        overlay: None,
+        frame_path: Vec::new(),
                 }),
                 intent: None,
+                frame_path: Vec::new(),
             },
         ],
         standalone_actions: vec![], outputs: vec![], identities: HashMap::new(),
         structural_outline: Default::default(),
+        tables: vec![],
     };
     let diff = SemanticStateDiff {
         forms: FormDiff { added: vec![], removed: vec![], changed: vec![] },
@@ -1415,7 +1595,13 @@ fn disabled_input_excluded_from_form() {
         section_heading: None,
         nearby_help_text: None,
         semantic_section: None,
+        aria_live: None,
+        in_dialog: false,
+        live_region: None,
         visible: false,
+        frame_path: Vec::new(),
+        shadow_path: Vec::new(),
+        table: None,
         },
         DomElement {
             tag: "input".into(), text: None, role: Some("textbox".into()),
@@ -1437,7 +1623,13 @@ fn disabled_input_excluded_from_form() {
         section_heading: None,
         nearby_help_text: None,
         semantic_section: None,
+        aria_live: None,
+        in_dialog: false,
+        live_region: None,
         visible: false,
+        frame_path: Vec::new(),
+        shadow_path: Vec::new(),
+        table: None,
         },
     ];
     let semantics = classify(&elements);
@@ -1470,7 +1662,13 @@ fn screen_element_carries_name_and_value() {
         section_heading: None,
         nearby_help_text: None,
         semantic_section: None,
+        aria_live: None,
+        in_dialog: false,
+        live_region: None,
         visible: false,
+        frame_path: Vec::new(),
+        shadow_path: Vec::new(),
+        table: None,
     }];
     let semantics = classify(&elements);
     let form = semantics.forms.iter().find(|f| f.id == "f").unwrap();
@@ -0,0 +1,1632 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use serde_json::{Value, json};
+
+use screen_detection::agent::agent::execute_action_session;
+use screen_detection::agent::agent_model::AgentAction;
+use screen_detection::agent::data_generator::placeholder_upload_path;
+use screen_detection::agent::error::AgentError;
+use screen_detection::agent::page_analyzer::MockPageAnalyzer;
+use screen_detection::browser::backend::BrowserBackend;
+use screen_detection::browser::console::{ConsoleEntry, ConsoleLevel};
+use screen_detection::browser::dialogs::{DialogKind, DialogPolicy};
+use screen_detection::browser::mock::{MockAction, MockBrowser};
+use screen_detection::browser::network::NetworkEntry;
+use screen_detection::browser::options::{SessionOptions, Viewport};
+use screen_detection::browser::playwright::SelectorHint;
+use screen_detection::browser::recording::RecordedEvent;
+use screen_detection::browser::routes::RouteMock;
+use screen_detection::browser::tabs::TabSelector;
+use screen_detection::cli::config::AuthConfig;
+use screen_detection::explorer::app_map::{ExplorerConfig, TransitionKind};
+use screen_detection::explorer::explorer::{authenticate, explore_live, perform_login};
+use screen_detection::spec::pool::{run_pool, worker_config};
+use screen_detection::spec::recorder::{Recorder, record_session};
+use screen_detection::spec::runner::TestRunner;
+use screen_detection::spec::runner_config::{ArtifactMode, RunnerConfig};
+use screen_detection::spec::spec_model::{AssertionSpec, TestSpec, TestStep};
+
+// =========================================================================
+// Helpers
+// =========================================================================
+
+fn input(label: &str, form: &str, input_type: &str) -> Value {
+    json!({
+        "tag": "input", "text": null, "role": "textbox", "type": input_type,
+        "ariaLabel": label, "disabled": false, "required": true, "formId": form,
+        "visible": true
+    })
+}
+
+fn button(label: &str, form: Option<&str>) -> Value {
+    json!({
+        "tag": "button", "text": label, "role": null, "type": "submit",
+        "ariaLabel": null, "disabled": false, "required": false, "formId": form,
+        "visible": true
+    })
+}
+
+fn text(tag: &str, id: &str, content: &str) -> Value {
+    json!({
+        "tag": tag, "id": id, "text": content, "role": null, "type": null,
+        "ariaLabel": null, "disabled": false, "required": false, "formId": null,
+        "visible": true
+    })
+}
+
+fn page(url: &str, title: &str, dom: Vec<Value>) -> Value {
+    json!({ "url": url, "title": title, "dom": dom })
+}
+
+fn login_browser() -> MockBrowser {
+    MockBrowser::new()
+        .with_page(
+            "https://app.test/login",
+            page(
+                "https://app.test/login",
+                "Login",
+                vec![
+                    input("Email", "login", "email"),
+                    input("Password", "login", "password"),
+                    button("Sign in", Some("login")),
+                ],
+            ),
+        )
+        .with_page(
+            "https://app.test/dashboard",
+            page(
+                "https://app.test/dashboard",
+                "Dashboard",
+                vec![
+                    text("h1", "welcome", "Welcome back"),
+                    text("li", "row1", "Order 1"),
+                    text("li", "row2", "Order 2"),
+                ],
+            ),
+        )
+        .with_click_target("Sign in", "https://app.test/dashboard")
+}
+
+// =========================================================================
+// MockBrowser basics
+// =========================================================================
+
+#[test]
+fn mock_browser_navigate_and_extract() {
+    let mut browser = login_browser();
+    browser.navigate("https://app.test/login").unwrap();
+    let data = browser.extract().unwrap();
+    assert_eq!(data["title"], "Login");
+    assert_eq!(data["dom"].as_array().unwrap().len(), 3);
+    assert_eq!(browser.current_url().unwrap(), "https://app.test/login");
+}
+
+#[test]
+fn mock_browser_navigate_unknown_url_fails() {
+    let mut browser = login_browser();
+    assert!(browser.navigate("https://app.test/missing").is_err());
+}
+
+#[test]
+fn mock_browser_fill_unknown_field_fails() {
+    let mut browser = login_browser();
+    browser.navigate("https://app.test/login").unwrap();
+    let hint = SelectorHint {
+        role: Some("textbox".into()),
+        name: Some("Username".into()),
+        tag: Some("input".into()),
+        input_type: None,
+        form_id: Some("login".into()),
+        frame_path: Vec::new(),
+        shadow_path: Vec::new(),
+    };
+    assert!(browser.fill(&hint, "x").is_err());
+}
+
+#[test]
+fn runner_suggests_nearest_element_when_a_step_misses() {
+    let spec = TestSpec {
+        name: "Typo".into(),
+        start_url: "https://app.test/login".into(),
+        steps: vec![TestStep::Click { label: "Sign-in".into(), frame_path: Vec::new() }],
+        options: None,
+        auth: false,
+        mocks: Vec::new(),
+    };
+    let result = TestRunner::run_with_config(&spec, &mut login_browser(), &quiet_config());
+    assert!(!result.passed);
+    let error = result.error.as_deref().unwrap();
+    assert!(error.contains("closest: element 'Sign in' <button> in form 'login' (1.00)"), "{}", error);
+    assert_eq!(result.suggestions.len(), 1);
+    assert_eq!(result.did_you_mean().as_deref(), Some("did you mean 'Sign in'?"));
+}
+
+#[test]
+fn mock_browser_click_follows_target_and_records_actions() {
+    let mut browser = login_browser();
+    browser.navigate("https://app.test/login").unwrap();
+    let hint = SelectorHint {
+        role: Some("button".into()),
+        name: Some("sign in".into()),
+        tag: None,
+        input_type: None,
+        form_id: None,
+        frame_path: Vec::new(),
+        shadow_path: Vec::new(),
+    };
+    browser.click(&hint).unwrap();
+    assert_eq!(browser.last_url(), Some("https://app.test/dashboard"));
+    assert_eq!(
+        browser.actions(),
+        &[
+            MockAction::Navigate("https://app.test/login".into()),
+            MockAction::Click("sign in".into()),
+        ]
+    );
+}
+
+#[test]
+fn mock_browser_queries_by_tag_and_id() {
+    let mut browser = login_browser();
+    browser.navigate("https://app.test/dashboard").unwrap();
+    assert_eq!(browser.query_text("#welcome").unwrap(), Some("Welcome back".into()));
+    assert_eq!(browser.query_count("li").unwrap(), 2);
+    assert!(browser.query_visible("h1").unwrap());
+    assert!(!browser.query_visible("#nope").unwrap());
+    assert!(browser.query_text("div.card > span").is_err());
+}
+
+// =========================================================================
+// Runner, snapshot, explorer and login against MockBrowser
+// =========================================================================
+
+#[test]
+fn runner_executes_spec_against_mock_browser() {
+    let mut values = HashMap::new();
+    values.insert("Email".to_string(), "user@example.com".to_string());
+    values.insert("Password".to_string(), "secret".to_string());
+
+    let spec = TestSpec {
+        name: "Login flow".into(),
+        start_url: "https://app.test/login".into(),
+        steps: vec![
+            TestStep::FillAndSubmit {
+                form: "login".into(),
+                values,
+                submit_label: Some("Sign in".into()),
+                frame_path: Vec::new(),
+            },
+            TestStep::Assert {
+                assertions: vec![
+                    AssertionSpec::UrlContains { expected: "/dashboard".into() },
+                    AssertionSpec::TitleContains { expected: "Dash".into() },
+                    AssertionSpec::TextPresent { expected: "welcome back".into() },
+                    AssertionSpec::ElementCount { selector: "li".into(), expected: 2 },
+                ],
+            },
+        ],
+        options: None,
+        auth: false,
+        mocks: Vec::new(),
+    };
+
+    let mut browser = login_browser();
+    let result = TestRunner::run(&spec, &mut browser);
+    assert!(result.passed, "result = {:?}", result);
+    assert_eq!(result.assertion_results.len(), 4);
+    assert_eq!(result.retry_attempts, 0);
+}
+
+#[test]
+fn runner_reports_step_error_from_mock_browser() {
+    let spec = TestSpec {
+        name: "Missing button".into(),
+        start_url: "https://app.test/login".into(),
+        steps: vec![TestStep::Click { label: "Register".into(), frame_path: Vec::new() }],
+        options: None,
+        auth: false,
+        mocks: Vec::new(),
+    };
+    let config = RunnerConfig {
+        screenshot_on_failure: false,
+        ..RunnerConfig::default()
+    };
+
+    let mut browser = login_browser();
+    let result = TestRunner::run_with_config(&spec, &mut browser, &config);
+    assert!(!result.passed);
+    assert!(result.error.unwrap().contains("Register"));
+}
+
+#[test]
+fn runner_retries_failing_assertions_on_mock_browser() {
+    let spec = TestSpec {
+        name: "Wrong title".into(),
+        start_url: "https://app.test/login".into(),
+        steps: vec![TestStep::Assert {
+            assertions: vec![AssertionSpec::TitleContains { expected: "Nope".into() }],
+        }],
+        options: None,
+        auth: false,
+        mocks: Vec::new(),
+    };
+    let config = RunnerConfig {
+        max_assertion_retries: 2,
+        retry_delay_ms: 1,
+        screenshot_on_failure: true,
+        screenshot_dir: std::env::temp_dir().to_string_lossy().to_string(),
+        storage_state: None,
+        record_har: false,
+        record_trace: ArtifactMode::Off,
+        record_video: ArtifactMode::Off,
+        fail_on_page_error: false,
+    };
+
+    let mut browser = login_browser();
+    let result = TestRunner::run_with_config(&spec, &mut browser, &config);
+    assert!(!result.passed);
+    assert_eq!(result.retry_attempts, 2);
+    assert!(browser
+        .actions()
+        .iter()
+        .any(|a| matches!(a, MockAction::Screenshot(_))));
+}
+
+#[test]
+fn runner_applies_and_reverts_spec_options() {
+    let mobile = SessionOptions {
+        viewport: Some(Viewport { width: 375, height: 667 }),
+        ..SessionOptions::default()
+    };
+    let spec = TestSpec {
+        options: Some(mobile.clone()),
+        ..title_spec("Mobile login", "https://app.test/login", "Login")
+    };
+
+    let mut browser = login_browser();
+    let result = TestRunner::run(&spec, &mut browser);
+    assert!(result.passed);
+    let actions = browser.actions();
+    assert_eq!(actions.first(), Some(&MockAction::Configure(mobile)));
+    assert_eq!(actions.last(), Some(&MockAction::Configure(SessionOptions::default())));
+
+    // Specs without options never touch the context
+    let mut browser = login_browser();
+    TestRunner::run(&title_spec("Plain", "https://app.test/login", "Login"), &mut browser);
+    assert!(!browser.actions().iter().any(|a| matches!(a, MockAction::Configure(_))));
+}
+
+#[test]
+fn snapshot_session_works_with_mock_browser() {
+    let mut browser = login_browser();
+    browser.navigate("https://app.test/login").unwrap();
+    let (screen, canonical) = screen_detection::snapshot_session(&mut browser).unwrap();
+    assert_eq!(screen.title, "Login");
+    assert_eq!(screen.forms.len(), 1);
+    assert!(canonical.forms.contains_key("login"));
+}
+
+#[test]
+fn perform_login_against_mock_browser() {
+    let mut credentials = HashMap::new();
+    credentials.insert("Email".to_string(), "admin@app.test".to_string());
+    credentials.insert("Password".to_string(), "hunter2".to_string());
+    let auth = AuthConfig {
+        login_url: Some("https://app.test/login".into()),
+        credentials,
+        submit_label: None,
+        success_url_contains: Some("/dashboard".into()),
+        success_text: None,
+        storage_state: None,
+    };
+
+    let mut browser = login_browser();
+    let logged_in = perform_login(&mut browser, &auth, &MockPageAnalyzer).unwrap();
+    assert!(logged_in);
+    assert!(browser.actions().contains(&MockAction::Fill {
+        name: "Password".into(),
+        value: "hunter2".into(),
+    }));
+}
+
+fn storage_auth(state: &std::path::Path) -> AuthConfig {
+    let mut credentials = HashMap::new();
+    credentials.insert("Email".to_string(), "admin@app.test".to_string());
+    credentials.insert("Password".to_string(), "hunter2".to_string());
+    AuthConfig {
+        login_url: Some("https://app.test/login".into()),
+        credentials,
+        submit_label: None,
+        success_url_contains: Some("/dashboard".into()),
+        success_text: None,
+        storage_state: Some(state.to_string_lossy().to_string()),
+    }
+}
+
+fn temp_state(name: &str, exists: bool) -> std::path::PathBuf {
+    let path = std::env::temp_dir().join(format!("sd_state_{}_{}.json", name, std::process::id()));
+    if exists {
+        std::fs::write(&path, "{}").unwrap();
+    } else {
+        let _ = std::fs::remove_file(&path);
+    }
+    path
+}
+
+#[test]
+fn authenticate_reuses_valid_storage_state() {
+    let state = temp_state("valid", true);
+    let auth = storage_auth(&state);
+    // A logged-in user visiting /login is forwarded to the dashboard
+    let mut browser =
+        login_browser().with_redirect("https://app.test/login", "https://app.test/dashboard");
+
+    assert!(authenticate(&mut browser, &auth, &MockPageAnalyzer).unwrap());
+    let state = state.to_string_lossy().to_string();
+    assert_eq!(browser.actions()[0], MockAction::LoadStorageState(state));
+    assert!(!browser.actions().iter().any(|a| matches!(a, MockAction::Fill { .. })));
+}
+
+#[test]
+fn authenticate_falls_back_to_form_login_and_saves_state() {
+    let state = temp_state("expired", true);
+    let auth = storage_auth(&state);
+    let mut browser = login_browser();
+
+    assert!(authenticate(&mut browser, &auth, &MockPageAnalyzer).unwrap());
+    let state = state.to_string_lossy().to_string();
+    let actions = browser.actions();
+    assert_eq!(actions.first(), Some(&MockAction::LoadStorageState(state.clone())));
+    assert!(actions.iter().any(|a| matches!(a, MockAction::Fill { .. })));
+    assert_eq!(actions.last(), Some(&MockAction::SaveStorageState(state)));
+}
+
+#[test]
+fn authenticate_without_saved_state_logs_in_and_saves() {
+    let state = temp_state("missing", false);
+    let mut browser = login_browser();
+    assert!(authenticate(&mut browser, &storage_auth(&state), &MockPageAnalyzer).unwrap());
+    assert!(!browser.actions().iter().any(|a| matches!(a, MockAction::LoadStorageState(_))));
+    assert!(matches!(browser.actions().last(), Some(MockAction::SaveStorageState(_))));
+}
+
+#[test]
+fn runner_loads_storage_state_for_auth_specs() {
+    let spec = TestSpec {
+        auth: true,
+        ..title_spec("Orders", "https://app.test/dashboard", "Dashboard")
+    };
+    let config = RunnerConfig {
+        storage_state: Some("auth.json".into()),
+        ..quiet_config()
+    };
+    let mut browser = login_browser();
+    let result = TestRunner::run_with_config(&spec, &mut browser, &config);
+    assert!(result.passed);
+    assert_eq!(browser.actions()[0], MockAction::LoadStorageState("auth.json".into()));
+
+    let mut browser = login_browser();
+    let result = TestRunner::run_with_config(&spec, &mut browser, &quiet_config());
+    assert!(!result.passed);
+    assert!(result.error.unwrap().contains("storage state"));
+}
+
+#[test]
+fn explore_live_against_mock_browser() {
+    let config = ExplorerConfig {
+        start_url: "https://app.test/login".into(),
+        max_pages: 5,
+        ..ExplorerConfig::default()
+    };
+
+    let mut browser = login_browser();
+    let app_map = explore_live(&config, &mut browser, &MockPageAnalyzer, None, None, None).unwrap();
+    assert!(app_map.has_page("https://app.test/login"));
+    assert!(app_map.has_page("https://app.test/dashboard"));
+    assert!(!app_map.transitions.is_empty());
+}
+
+// =========================================================================
+// Parallel execution pool
+// =========================================================================
+
+fn title_spec(name: &str, url: &str, expected: &str) -> TestSpec {
+    TestSpec {
+        name: name.into(),
+        start_url: url.into(),
+        steps: vec![TestStep::Assert {
+            assertions: vec![AssertionSpec::TitleContains { expected: expected.into() }],
+        }],
+        options: None,
+        auth: false,
+        mocks: Vec::new(),
+    }
+}
+
+fn pool_specs() -> Vec<TestSpec> {
+    vec![
+        title_spec("e", "https://app.test/login", "Login"),
+        title_spec("d", "https://app.test/dashboard", "Dashboard"),
+        title_spec("c", "https://app.test/login", "Nope"),
+        title_spec("b", "https://app.test/dashboard", "Dash"),
+        title_spec("a", "https://app.test/login", "Log"),
+    ]
+}
+
+fn quiet_config() -> RunnerConfig {
+    RunnerConfig {
+        max_assertion_retries: 0,
+        retry_delay_ms: 1,
+        screenshot_on_failure: false,
+        ..RunnerConfig::default()
+    }
+}
+
+#[test]
+fn run_pool_keeps_spec_order_across_workers() {
+    let specs = pool_specs();
+    let results = run_pool(&specs, 3, &quiet_config(), |_| Ok(login_browser())).unwrap();
+    let names: Vec<_> = results.iter().map(|r| r.spec_name.as_str()).collect();
+    assert_eq!(names, vec!["e", "d", "c", "b", "a"]);
+    let passed: Vec<_> = results.iter().map(|r| r.passed).collect();
+    assert_eq!(passed, vec![true, true, false, true, true]);
+}
+
+#[test]
+fn run_pool_tolerates_some_workers_failing_to_launch() {
+    let specs = pool_specs();
+    let results = run_pool(&specs, 4, &quiet_config(), |worker| {
+        if worker == 0 {
+            Ok(login_browser())
+        } else {
+            Err(AgentError::SessionIO("no browser".into()))
+        }
+    })
+    .unwrap();
+    assert_eq!(results.len(), 5);
+}
+
+#[test]
+fn run_pool_errors_when_no_worker_launches() {
+    let specs = pool_specs();
+    let result = run_pool(&specs, 2, &quiet_config(), |_| {
+        Err::<MockBrowser, _>(AgentError::SessionIO("no browser".into()))
+    });
+    assert!(result.unwrap_err().to_string().contains("no browser"));
+}
+
+#[test]
+fn worker_config_separates_screenshot_dirs() {
+    let config = RunnerConfig::default();
+    assert_eq!(worker_config(&config, 0, 1).screenshot_dir, "screenshots");
+    let a = worker_config(&config, 0, 2).screenshot_dir;
+    let b = worker_config(&config, 1, 2).screenshot_dir;
+    assert_ne!(a, b);
+    assert!(b.ends_with("worker-1"));
+}
+
+// =========================================================================
+// Network assertions and HAR export
+// =========================================================================
+
+fn request(url: &str, method: &str, status: u16) -> NetworkEntry {
+    NetworkEntry {
+        url: url.into(),
+        method: method.into(),
+        status: Some(status),
+        resource_type: Some("fetch".into()),
+        mime_type: None,
+        started_at: None,
+        duration_ms: Some(12.0),
+        size: None,
+        failure: None,
+    }
+}
+
+fn network_browser() -> MockBrowser {
+    login_browser()
+        .with_requests(
+            "https://app.test/login",
+            vec![request("https://app.test/login", "GET", 200)],
+        )
+        .with_requests(
+            "https://app.test/dashboard",
+            vec![
+                request("https://app.test/api/orders", "GET", 200),
+                request("https://app.test/api/stats", "GET", 500),
+            ],
+        )
+}
+
+#[test]
+fn runner_evaluates_network_assertions() {
+    let spec = TestSpec {
+        name: "Dashboard traffic".into(),
+        start_url: "https://app.test/login".into(),
+        steps: vec![
+            TestStep::Assert {
+                assertions: vec![AssertionSpec::NoFailedRequests { min_status: 400 }],
+            },
+            TestStep::Navigate { url: "https://app.test/dashboard".into() },
+            TestStep::Assert {
+                assertions: vec![
+                    AssertionSpec::RequestMade { url_contains: "/api/orders".into(), method: Some("get".into()) },
+                    AssertionSpec::RequestMade { url_contains: "/api/orders".into(), method: Some("POST".into()) },
+                    AssertionSpec::NoFailedRequests { min_status: 400 },
+                    AssertionSpec::NoFailedRequests { min_status: 501 },
+                ],
+            },
+        ],
+        options: None,
+        auth: false,
+        mocks: Vec::new(),
+    };
+
+    let mut browser = network_browser();
+    let result = TestRunner::run_with_config(&spec, &mut browser, &quiet_config());
+    let passed: Vec<_> = result.assertion_results.iter().map(|a| a.passed).collect();
+    assert_eq!(passed, vec![true, true, false, false, true]);
+    let failure = result.assertion_results[3].message.as_deref().unwrap();
+    assert!(failure.contains("GET https://app.test/api/stats → 500"));
+    assert!(result.har_path.is_none());
+}
+
+#[test]
+fn runner_evaluates_table_assertions() {
+    let orders = json!({
+        "tag": "table", "id": "orders", "text": null, "role": null, "type": null,
+        "ariaLabel": null, "disabled": false, "required": false, "formId": null,
+        "visible": true,
+        "table": {
+            "kind": "table",
+            "caption": "Recent orders",
+            "headers": ["Order", "Total"],
+            "rows": [["#1001", "$1,200.00"], ["#1002", "$89.50"], ["#1003", "$12.00"]]
+        }
+    });
+    let browser_page = page("https://app.test/orders", "Orders", vec![orders]);
+    let mut browser = MockBrowser::new().with_page("https://app.test/orders", browser_page);
+
+    let spec = TestSpec {
+        name: "Orders table".into(),
+        start_url: "https://app.test/orders".into(),
+        steps: vec![TestStep::Assert {
+            assertions: vec![
+                AssertionSpec::TableRows { table: Some("recent orders".into()), min: 3 },
+                AssertionSpec::TableRows { table: None, min: 4 },
+                AssertionSpec::TableColumnContains { table: None, column: "order".into(), expected: "#1002".into() },
+                AssertionSpec::TableColumnContains { table: None, column: "Status".into(), expected: "Paid".into() },
+                AssertionSpec::TableSorted { table: Some("orders".into()), column: "Total".into(), descending: true },
+                AssertionSpec::TableSorted { table: Some("orders".into()), column: "Total".into(), descending: false },
+                AssertionSpec::TableRows { table: Some("invoices".into()), min: 1 },
+            ],
+        }],
+        options: None,
+        auth: false,
+        mocks: Vec::new(),
+    };
+
+    let result = TestRunner::run_with_config(&spec, &mut browser, &quiet_config());
+    let passed: Vec<_> = result.assertion_results.iter().map(|a| a.passed).collect();
+    assert_eq!(passed, vec![true, false, true, false, true, false, false]);
+    let messages: Vec<_> = result.assertion_results.iter().map(|a| a.message.as_deref()).collect();
+    assert_eq!(messages[1], Some("Table 'table:orders' has 3 row(s), expected at least 4"));
+    assert_eq!(messages[3], Some("No table with a 'Status' column on page"));
+    assert_eq!(messages[5], Some("Column 'Total' of table 'table:orders' is not sorted ascending"));
+    assert_eq!(messages[6], Some("No table 'invoices' on page"));
+}
+
+fn in_dialog(mut element: Value) -> Value {
+    element["in_dialog"] = json!(true);
+    element
+}
+
+fn modal_browser(buttons: &[&str]) -> MockBrowser {
+    let mut toast = text("p", "toast", "Saved to your list");
+    toast["aria_live"] = json!("polite");
+    let mut dom = vec![text("h1", "title", "Welcome"), toast, in_dialog(text("p", "cookies", "We use cookies"))];
+    dom.extend(buttons.iter().map(|label| in_dialog(button(label, None))));
+    let home = page("https://app.test/home", "Home", dom);
+    MockBrowser::new()
+        .with_page("https://app.test/home", home)
+        .with_page(
+            "https://app.test/home/consented",
+            page("https://app.test/home/consented", "Home", vec![text("h1", "title", "Welcome")]),
+        )
+        .with_click_target("Accept all", "https://app.test/home/consented")
+        .with_click_target("Close", "https://app.test/home/consented")
+}
+
+#[test]
+fn runner_dismisses_modals_and_asserts_overlays() {
+    let spec = TestSpec {
+        name: "Cookie banner".into(),
+        start_url: "https://app.test/home".into(),
+        steps: vec![
+            TestStep::Assert {
+                assertions: vec![
+                    AssertionSpec::ModalOpen { open: true },
+                    AssertionSpec::ToastShown { contains: Some("saved".into()) },
+                    AssertionSpec::ToastShown { contains: Some("Deleted".into()) },
+                ],
+            },
+            TestStep::DismissModal,
+            TestStep::Assert {
+                assertions: vec![AssertionSpec::ModalOpen { open: false }, AssertionSpec::ModalOpen { open: true }],
+            },
+            // Nothing left to dismiss
+            TestStep::DismissModal,
+        ],
+        options: None,
+        auth: false,
+        mocks: Vec::new(),
+    };
+
+    let mut browser = modal_browser(&["Accept all", "Close"]);
+    let result = TestRunner::run_with_config(&spec, &mut browser, &quiet_config());
+    let passed: Vec<_> = result.assertion_results.iter().map(|a| a.passed).collect();
+    assert_eq!(passed, vec![true, true, false, true, false]);
+    assert_eq!(result.assertion_results[2].message.as_deref(), Some("No toast containing 'Deleted' is shown"));
+    assert_eq!(result.assertion_results[4].message.as_deref(), Some("No modal is open"));
+    assert_eq!(browser.last_url(), Some("https://app.test/home/consented"));
+    // "Close" is preferred over "Accept all"
+    assert!(browser.actions().contains(&MockAction::Click("Close".into())), "{:?}", browser.actions());
+
+    // A modal without a dismiss button is closed with Escape
+    let spec = TestSpec { steps: vec![TestStep::DismissModal], ..spec };
+    let mut browser = modal_browser(&["Learn more"]);
+    let result = TestRunner::run_with_config(&spec, &mut browser, &quiet_config());
+    assert!(result.passed, "{:?}", result.error);
+    assert!(browser.actions().contains(&MockAction::Press { name: None, key: "Escape".into() }));
+}
+
+#[test]
+fn runner_writes_har_when_enabled() {
+    let dir = std::env::temp_dir().join(format!("sd_har_{}", std::process::id()));
+    let config = RunnerConfig {
+        record_har: true,
+        screenshot_dir: dir.to_string_lossy().to_string(),
+        ..quiet_config()
+    };
+    let spec = title_spec("Dash", "https://app.test/dashboard", "Dashboard");
+
+    let mut browser = network_browser();
+    let result = TestRunner::run_with_config(&spec, &mut browser, &config);
+    let har_path = result.har_path.expect("HAR path recorded");
+    assert!(har_path.ends_with(".har"));
+    let har: Value = serde_json::from_str(&std::fs::read_to_string(&har_path).unwrap()).unwrap();
+    assert_eq!(har["log"]["entries"].as_array().unwrap().len(), 2);
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn runner_keeps_trace_and_video_only_when_the_mode_says_so() {
+    let dir = std::env::temp_dir().join(format!("sd_trace_{}", std::process::id()));
+    let config = RunnerConfig {
+        record_trace: ArtifactMode::OnFailure,
+        record_video: ArtifactMode::Always,
+        screenshot_dir: dir.to_string_lossy().to_string(),
+        ..quiet_config()
+    };
+
+    let mut browser = login_browser();
+    let passing = TestRunner::run_with_config(&title_spec("Ok", "https://app.test/login", "Login"), &mut browser, &config);
+    assert!(passing.passed);
+    assert!(passing.trace_path.is_none());
+    let video = passing.video_path.expect("video kept for every test");
+    assert!(video.ends_with(".webm"));
+    assert!(browser.actions().contains(&MockAction::StopTrace(None)));
+    assert!(browser.actions().contains(&MockAction::StopVideo(Some(video))));
+
+    let mut browser = login_browser();
+    let failing = TestRunner::run_with_config(&title_spec("Bad", "https://app.test/login", "Nope"), &mut browser, &config);
+    assert!(!failing.passed);
+    let trace = failing.trace_path.expect("trace kept on failure");
+    assert!(trace.ends_with(".zip") && trace.contains("bad_trace_"));
+    let stops: Vec<_> = browser
+        .actions()
+        .iter()
+        .filter(|a| matches!(a, MockAction::StopTrace(_) | MockAction::StopVideo(_)))
+        .collect();
+    assert_eq!(stops.len(), 2);
+
+    // Off by default: nothing is started
+    let mut browser = login_browser();
+    TestRunner::run_with_config(&title_spec("Ok", "https://app.test/login", "Login"), &mut browser, &quiet_config());
+    assert!(!browser.actions().iter().any(|a| matches!(a, MockAction::StartTrace | MockAction::StartVideo)));
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+// =========================================================================
+// Console messages and page errors
+// =========================================================================
+
+fn console_entry(level: ConsoleLevel, text: &str) -> ConsoleEntry {
+    ConsoleEntry { level, text: text.into(), location: None, stack: None, step: None }
+}
+
+fn console_browser() -> MockBrowser {
+    login_browser()
+        .with_console(
+            "https://app.test/login",
+            vec![console_entry(ConsoleLevel::Log, "login page ready")],
+        )
+        .with_console(
+            "https://app.test/dashboard",
+            vec![
+                console_entry(ConsoleLevel::Warning, "slow stats"),
+                console_entry(ConsoleLevel::PageError, "stats is undefined"),
+            ],
+        )
+}
+
+fn console_spec() -> TestSpec {
+    TestSpec {
+        name: "Dashboard console".into(),
+        start_url: "https://app.test/login".into(),
+        steps: vec![
+            TestStep::Wait { duration_ms: 1 },
+            TestStep::Navigate { url: "https://app.test/dashboard".into() },
+        ],
+        options: None,
+        auth: false,
+        mocks: Vec::new(),
+    }
+}
+
+#[test]
+fn runner_attaches_console_messages_per_step() {
+    let mut browser = console_browser();
+    let result = TestRunner::run_with_config(&console_spec(), &mut browser, &quiet_config());
+    assert!(result.passed, "page errors do not fail a test by default");
+    let tagged: Vec<_> = result.console.iter().map(|e| (e.text.as_str(), e.step)).collect();
+    assert_eq!(
+        tagged,
+        vec![("login page ready", None), ("slow stats", Some(1)), ("stats is undefined", Some(1))]
+    );
+}
+
+#[test]
+fn runner_fails_on_page_error_when_enabled() {
+    let config = RunnerConfig { fail_on_page_error: true, ..quiet_config() };
+    let mut browser = console_browser();
+    let result = TestRunner::run_with_config(&console_spec(), &mut browser, &config);
+    assert!(!result.passed);
+    let error = result.error.unwrap();
+    assert!(error.contains("1 uncaught page error(s)"));
+    assert!(error.contains("[step 1] uncaught exception: stats is undefined"));
+
+    // Console errors alone are not uncaught exceptions
+    let mut quiet = login_browser().with_console(
+        "https://app.test/login",
+        vec![console_entry(ConsoleLevel::Error, "404 favicon")],
+    );
+    let result = TestRunner::run_with_config(&console_spec(), &mut quiet, &config);
+    assert!(result.passed);
+}
+
+#[test]
+fn runner_keeps_console_when_a_step_fails() {
+    let mut spec = console_spec();
+    spec.steps.push(TestStep::Click { label: "No such button".into(), frame_path: Vec::new() });
+    let mut browser = console_browser().with_console(
+        "https://app.test/dashboard",
+        vec![console_entry(ConsoleLevel::PageError, "render failed")],
+    );
+    let result = TestRunner::run_with_config(&spec, &mut browser, &quiet_config());
+    assert!(!result.passed);
+    assert_eq!(result.steps_run, 3);
+    let last = result.console.last().unwrap();
+    assert_eq!((last.text.as_str(), last.step), ("render failed", Some(1)));
+}
+
+// =========================================================================
+// Tabs and popups
+// =========================================================================
+
+fn button_hint(label: &str) -> SelectorHint {
+    SelectorHint {
+        role: Some("button".into()),
+        name: Some(label.into()),
+        tag: None,
+        input_type: None,
+        form_id: None,
+        frame_path: Vec::new(),
+        shadow_path: Vec::new(),
+    }
+}
+
+fn popup_browser() -> MockBrowser {
+    login_browser().with_popup_target("Sign in", "https://app.test/dashboard")
+}
+
+#[test]
+fn mock_browser_popup_switch_and_close() {
+    let mut browser = popup_browser();
+    browser.navigate("https://app.test/login").unwrap();
+    browser.click(&button_hint("Sign in")).unwrap();
+
+    // The popup opens in the background
+    assert_eq!(browser.current_url().unwrap(), "https://app.test/login");
+    let tabs = browser.tabs().unwrap();
+    assert_eq!(tabs.len(), 2);
+    assert!(tabs[0].active);
+    assert_eq!(tabs[1].title, "Dashboard");
+
+    let popup = browser.wait_for_popup(1000).unwrap();
+    assert_eq!((popup.index, popup.url.as_str()), (1, "https://app.test/dashboard"));
+    assert!(matches!(browser.wait_for_popup(1000), Err(AgentError::Timeout { .. })));
+
+    browser.switch_tab(&TabSelector::index(0)).unwrap();
+    browser.close_tab(&TabSelector::url_contains("dashboard")).unwrap();
+    assert_eq!(browser.tabs().unwrap().len(), 1);
+    assert_eq!(browser.current_url().unwrap(), "https://app.test/login");
+    assert!(browser.close_tab(&TabSelector::default()).is_err(), "last tab stays open");
+    assert!(browser.switch_tab(&TabSelector::url_contains("dashboard")).is_err());
+}
+
+#[test]
+fn runner_switches_to_popup_and_closes_it() {
+    let spec = TestSpec {
+        name: "OAuth popup".into(),
+        start_url: "https://app.test/login".into(),
+        steps: vec![
+            TestStep::Click { label: "Sign in".into(), frame_path: Vec::new() },
+            TestStep::SwitchTab { tab: TabSelector::url_contains("dashboard"), timeout_ms: 1000 },
+            TestStep::Assert {
+                assertions: vec![AssertionSpec::TitleContains { expected: "Dashboard".into() }],
+            },
+            TestStep::CloseTab { tab: TabSelector::default() },
+            TestStep::Assert {
+                assertions: vec![AssertionSpec::UrlContains { expected: "/login".into() }],
+            },
+        ],
+        options: None,
+        auth: false,
+        mocks: Vec::new(),
+    };
+
+    let mut browser = popup_browser();
+    let result = TestRunner::run_with_config(&spec, &mut browser, &quiet_config());
+    assert!(result.passed, "{:?}", result);
+    assert!(browser.actions().contains(&MockAction::CloseTab(1)));
+}
+
+#[test]
+fn runner_switch_tab_without_criteria_waits_for_the_popup() {
+    let spec = |steps| TestSpec {
+        name: "Newest tab".into(),
+        start_url: "https://app.test/login".into(),
+        steps,
+        options: None,
+        auth: false,
+        mocks: Vec::new(),
+    };
+    let newest = TestStep::SwitchTab { tab: TabSelector::default(), timeout_ms: 10 };
+
+    // Without a popup the current tab is not mistaken for it
+    let result = TestRunner::run_with_config(&spec(vec![newest.clone()]), &mut popup_browser(), &quiet_config());
+    assert!(!result.passed);
+    assert!(result.error.unwrap().contains("wait_for_popup"));
+
+    let steps = vec![
+        TestStep::Click { label: "Sign in".into(), frame_path: Vec::new() },
+        newest,
+        TestStep::Assert {
+            assertions: vec![AssertionSpec::TitleContains { expected: "Dashboard".into() }],
+        },
+    ];
+    let result = TestRunner::run_with_config(&spec(steps), &mut popup_browser(), &quiet_config());
+    assert!(result.passed, "{:?}", result);
+}
+
+#[test]
+fn runner_fails_when_tab_never_opens() {
+    let spec = TestSpec {
+        name: "Missing popup".into(),
+        start_url: "https://app.test/login".into(),
+        steps: vec![TestStep::SwitchTab { tab: TabSelector::url_contains("oauth"), timeout_ms: 10 }],
+        options: None,
+        auth: false,
+        mocks: Vec::new(),
+    };
+    let mut browser = popup_browser();
+    let result = TestRunner::run_with_config(&spec, &mut browser, &quiet_config());
+    assert!(!result.passed);
+    assert!(result.error.unwrap().contains("wait_for_popup"));
+}
+
+#[test]
+fn explore_live_follows_form_submission_into_popup() {
+    let config = ExplorerConfig {
+        start_url: "https://app.test/login".into(),
+        max_pages: 5,
+        ..ExplorerConfig::default()
+    };
+
+    let mut browser = popup_browser();
+    let app_map = explore_live(&config, &mut browser, &MockPageAnalyzer, None, None, None).unwrap();
+    assert!(app_map.has_page("https://app.test/dashboard"));
+    assert!(app_map.transitions.iter().any(|t| t.from_url == "https://app.test/login"
+        && t.to_url == "https://app.test/dashboard"
+        && matches!(t.kind, TransitionKind::FormSubmission { .. })));
+    assert_eq!(browser.tabs().unwrap().len(), 1, "popup closed after exploring it");
+}
+
+// =========================================================================
+// Frame-scoped selectors
+// =========================================================================
+
+fn in_frame(mut el: Value, frame_path: &[&str]) -> Value {
+    el["frame_path"] = json!(frame_path);
+    el
+}
+
+fn checkout_browser() -> MockBrowser {
+    let card_frame = ["iframe#payment", "iframe#card"];
+    MockBrowser::new()
+        .with_page(
+            "https://app.test/checkout",
+            page(
+                "https://app.test/checkout",
+                "Checkout",
+                vec![
+                    input("Coupon", "order", "text"),
+                    in_frame(input("Card number", "card-form", "text"), &card_frame),
+                    in_frame(input("Cardholder", "", "text"), &["iframe#payment"]),
+                    in_frame(button("Pay", None), &card_frame),
+                ],
+            ),
+        )
+        .with_page("https://app.test/thanks", page("https://app.test/thanks", "Thanks", vec![]))
+        .with_click_target("Pay", "https://app.test/thanks")
+}
+
+#[test]
+fn mock_browser_matches_elements_only_in_their_frame() {
+    let mut browser = checkout_browser();
+    browser.navigate("https://app.test/checkout").unwrap();
+
+    // Frame content is invisible to main-frame selectors, and vice versa
+    assert!(browser.click(&button_hint("Pay")).is_err());
+    let mut coupon = button_hint("Coupon");
+    coupon.frame_path = vec!["iframe#payment".into()];
+    assert!(browser.fill(&coupon, "X").is_err());
+
+    let mut pay = button_hint("Pay");
+    pay.frame_path = vec!["iframe#payment".into(), "iframe#card".into()];
+    browser.click(&pay).unwrap();
+    assert_eq!(browser.current_url().unwrap(), "https://app.test/thanks");
+}
+
+/// A card form with its own submit button, inside an iframe.
+fn framed_card_browser() -> MockBrowser {
+    let frame = ["iframe#card"];
+    MockBrowser::new()
+        .with_page(
+            "https://app.test/pay",
+            page(
+                "https://app.test/pay",
+                "Pay",
+                vec![
+                    in_frame(input("Card number", "card-form", "text"), &frame),
+                    in_frame(button("Pay", Some("card-form")), &frame),
+                ],
+            ),
+        )
+        .with_page("https://app.test/thanks", page("https://app.test/thanks", "Thanks", vec![]))
+        .with_click_target("Pay", "https://app.test/thanks")
+}
+
+#[test]
+fn agent_fills_and_submits_forms_inside_frames() {
+    let mut browser = framed_card_browser();
+    browser.navigate("https://app.test/pay").unwrap();
+    let (screen, _) = screen_detection::snapshot_session(&mut browser).unwrap();
+
+    let pay = AgentAction::FillAndSubmitForm {
+        form_id: "card-form".into(),
+        values: vec![("Card number".into(), "4242".into())],
+        submit_label: Some("Pay".into()),
+    };
+    execute_action_session(&pay, &screen, &mut browser).unwrap();
+    assert!(browser.actions().contains(&MockAction::Fill { name: "Card number".into(), value: "4242".into() }));
+    assert_eq!(browser.last_url(), Some("https://app.test/thanks"));
+}
+
+#[test]
+fn explore_live_submits_forms_inside_frames() {
+    let config = ExplorerConfig {
+        start_url: "https://app.test/pay".into(),
+        max_pages: 5,
+        ..ExplorerConfig::default()
+    };
+
+    let mut browser = framed_card_browser();
+    let app_map = explore_live(&config, &mut browser, &MockPageAnalyzer, None, None, None).unwrap();
+    assert!(app_map.has_page("https://app.test/thanks"), "{:?}", browser.actions());
+}
+
+#[test]
+fn runner_fills_and_clicks_inside_frames() {
+    let card_frame = vec!["iframe#payment".to_string(), "iframe#card".to_string()];
+    let spec = TestSpec {
+        name: "Pay by card".into(),
+        start_url: "https://app.test/checkout".into(),
+        steps: vec![
+            TestStep::FillForm {
+                form: "card-form".into(),
+                values: HashMap::from([("Card number".into(), "4242".into())]),
+                frame_path: card_frame.clone(),
+            },
+            // The payment frame's implicit form is named after the frame
+            TestStep::FillForm {
+                form: "iframe#payment".into(),
+                values: HashMap::from([("Cardholder".into(), "Ada".into())]),
+                frame_path: vec!["iframe#payment".into()],
+            },
+            TestStep::Click { label: "Pay".into(), frame_path: card_frame },
+            TestStep::Assert {
+                assertions: vec![AssertionSpec::UrlContains { expected: "/thanks".into() }],
+            },
+        ],
+        options: None,
+        auth: false,
+        mocks: Vec::new(),
+    };
+
+    let mut browser = checkout_browser();
+    let result = TestRunner::run_with_config(&spec, &mut browser, &quiet_config());
+    assert!(result.passed, "{:?}", result.error);
+    let fills: Vec<_> = browser
+        .actions()
+        .iter()
+        .filter_map(|a| match a {
+            MockAction::Fill { name, value } => Some((name.as_str(), value.as_str())),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(fills, vec![("Card number", "4242"), ("Cardholder", "Ada")]);
+}
+
+#[test]
+fn runner_fills_virtual_forms_of_formless_pages() {
+    let mut email = input("Email", "", "email");
+    email["formId"] = Value::Null;
+    email["section_heading"] = json!("Newsletter");
+    let mut subscribe = button("Subscribe", None);
+    subscribe["section_heading"] = json!("Newsletter");
+    let mut browser = MockBrowser::new()
+        .with_page("https://app.test/", page("https://app.test/", "Home", vec![email, subscribe]))
+        .with_page("https://app.test/subscribed", page("https://app.test/subscribed", "Thanks", vec![]))
+        .with_click_target("Subscribe", "https://app.test/subscribed");
+
+    let spec = TestSpec {
+        name: "Subscribe".into(),
+        start_url: "https://app.test/".into(),
+        steps: vec![
+            TestStep::FillAndSubmit {
+                form: "virtual:newsletter".into(),
+                values: HashMap::from([("Email".into(), "ada@example.com".into())]),
+                submit_label: Some("Subscribe".into()),
+                frame_path: Vec::new(),
+            },
+            TestStep::Assert {
+                assertions: vec![AssertionSpec::UrlContains { expected: "/subscribed".into() }],
+            },
+        ],
+        options: None,
+        auth: false,
+        mocks: Vec::new(),
+    };
+
+    let result = TestRunner::run_with_config(&spec, &mut browser, &quiet_config());
+    assert!(result.passed, "{:?}", result.error);
+}
+
+#[test]
+fn virtual_form_selectors_skip_real_forms_and_reject_ambiguous_matches() {
+    let orphan_email = |heading: &str| {
+        let mut email = input("Email", "", "email");
+        email["formId"] = Value::Null;
+        email["section_heading"] = json!(heading);
+        email
+    };
+    let newsletter = SelectorHint {
+        role: Some("textbox".into()),
+        name: Some("Email".into()),
+        tag: None,
+        input_type: None,
+        form_id: Some("virtual:newsletter".into()),
+        frame_path: Vec::new(),
+        shadow_path: Vec::new(),
+    };
+
+    // The login form's "Email" is not part of any virtual form
+    let mut browser = MockBrowser::new().with_page(
+        "https://app.test/",
+        page("https://app.test/", "Home", vec![input("Email", "login", "email"), orphan_email("Newsletter")]),
+    );
+    browser.navigate("https://app.test/").unwrap();
+    browser.fill(&newsletter, "ada@example.com").unwrap();
+
+    // Two form-less "Email" fields cannot be told apart page-wide
+    let mut browser = MockBrowser::new().with_page(
+        "https://app.test/",
+        page("https://app.test/", "Home", vec![orphan_email("Newsletter"), orphan_email("Contact")]),
+    );
+    browser.navigate("https://app.test/").unwrap();
+    match browser.fill(&newsletter, "ada@example.com") {
+        Err(AgentError::AmbiguousElement { element, matches, .. }) => {
+            assert_eq!(element, "Email");
+            assert_eq!(matches, 2);
+        }
+        other => panic!("expected AmbiguousElement, got {:?}", other),
+    }
+}
+
+// =========================================================================
+// Rich interactions and file uploads
+// =========================================================================
+
+fn profile_browser() -> MockBrowser {
+    MockBrowser::new()
+        .with_page(
+            "https://app.test/profile",
+            page(
+                "https://app.test/profile",
+                "Profile",
+                vec![
+                    button("Menu", None),
+                    input("City", "profile", "text"),
+                    input("Avatar", "profile", "file"),
+                    button("Card A", None),
+                    button("Save", Some("profile")),
+                ],
+            ),
+        )
+        .with_page("https://app.test/saved", page("https://app.test/saved", "Saved", vec![]))
+        .with_click_target("Save", "https://app.test/saved")
+}
+
+#[test]
+fn runner_performs_rich_interactions_on_mock_browser() {
+    let spec = TestSpec {
+        name: "Profile widgets".into(),
+        start_url: "https://app.test/profile".into(),
+        steps: vec![
+            TestStep::Hover { label: "Menu".into(), frame_path: Vec::new() },
+            TestStep::Type {
+                label: "City".into(),
+                text: "Ber".into(),
+                form: Some("profile".into()),
+                delay_ms: 50,
+            },
+            TestStep::Press { key: "Enter".into(), label: None },
+            TestStep::Scroll { label: Some("Save".into()), delta_x: 0, delta_y: 0 },
+            TestStep::Scroll { label: None, delta_x: 0, delta_y: 400 },
+            TestStep::DragAndDrop { source: "Card A".into(), target: "Save".into() },
+            TestStep::Upload {
+                label: "Avatar".into(),
+                files: vec!["avatar.png".into()],
+                form: Some("profile".into()),
+            },
+        ],
+        options: None,
+        auth: false,
+        mocks: Vec::new(),
+    };
+
+    let mut browser = profile_browser();
+    let result = TestRunner::run_with_config(&spec, &mut browser, &quiet_config());
+    assert!(result.passed, "{:?}", result.error);
+    assert_eq!(
+        &browser.actions()[1..],
+        &[
+            MockAction::Hover("Menu".into()),
+            MockAction::Type { name: "City".into(), text: "Ber".into() },
+            MockAction::Press { name: None, key: "Enter".into() },
+            MockAction::ScrollIntoView("Save".into()),
+            MockAction::ScrollBy(0, 400),
+            MockAction::Drag { source: "Card A".into(), target: "Save".into() },
+            MockAction::SetInputFiles { name: "Avatar".into(), files: vec!["avatar.png".into()] },
+        ]
+    );
+}
+
+#[test]
+fn explore_live_uploads_placeholder_to_file_fields() {
+    let config = ExplorerConfig {
+        start_url: "https://app.test/profile".into(),
+        max_pages: 5,
+        ..ExplorerConfig::default()
+    };
+
+    // Only the form: the explorer would follow standalone buttons as links
+    let mut browser = MockBrowser::new()
+        .with_page(
+            "https://app.test/profile",
+            page(
+                "https://app.test/profile",
+                "Profile",
+                vec![input("Avatar", "profile", "file"), button("Save", Some("profile"))],
+            ),
+        )
+        .with_page("https://app.test/saved", page("https://app.test/saved", "Saved", vec![]))
+        .with_click_target("Save", "https://app.test/saved");
+    explore_live(&config, &mut browser, &MockPageAnalyzer, None, None, None).unwrap();
+    let placeholder = placeholder_upload_path();
+    assert!(browser.actions().contains(&MockAction::SetInputFiles {
+        name: "Avatar".into(),
+        files: vec![placeholder.clone()],
+    }));
+    assert!(std::path::Path::new(&placeholder).exists());
+    assert!(
+        !browser.actions().iter().any(|a| matches!(a, MockAction::Fill { name, .. } if name == "Avatar")),
+        "file inputs are never filled with text"
+    );
+}
+
+#[test]
+fn agent_uploads_files_and_types_through_session() {
+    let mut browser = profile_browser();
+    browser.navigate("https://app.test/profile").unwrap();
+    let (screen, _) = screen_detection::snapshot_session(&mut browser).unwrap();
+
+    let upload = AgentAction::FillAndSubmitForm {
+        form_id: "profile".into(),
+        values: vec![("Avatar".into(), "a.png, b.png".into())],
+        submit_label: Some("Save".into()),
+    };
+    execute_action_session(&upload, &screen, &mut browser).unwrap();
+    let typed = AgentAction::TypeText {
+        form_id: "profile".into(),
+        input_label: "City".into(),
+        text: "Par".into(),
+        delay_ms: 0,
+        identity: None,
+    };
+    assert!(execute_action_session(&typed, &screen, &mut browser).is_err(), "page changed after submit");
+
+    assert!(browser.actions().contains(&MockAction::SetInputFiles {
+        name: "Avatar".into(),
+        files: vec!["a.png".into(), "b.png".into()],
+    }));
+    assert_eq!(browser.last_url(), Some("https://app.test/saved"));
+}
+
+// =========================================================================
+// Route mocks
+// =========================================================================
+
+#[test]
+fn runner_installs_spec_mocks_before_start_url_and_removes_them() {
+    let spec = TestSpec {
+        name: "Orders API down".into(),
+        start_url: "https://app.test/dashboard".into(),
+        steps: vec![TestStep::Assert {
+            assertions: vec![AssertionSpec::NoFailedRequests { min_status: 400 }],
+        }],
+        options: None,
+        auth: false,
+        mocks: vec![
+            RouteMock::status("**/api/orders", 503),
+            RouteMock::status("**/api/stats*", 200),
+        ],
+    };
+
+    let mut browser = network_browser();
+    let result = TestRunner::run_with_config(&spec, &mut browser, &quiet_config());
+    assert!(!result.passed);
+    let failure = result.assertion_results[0].message.as_deref().unwrap();
+    assert!(failure.contains("/api/orders → 503"), "{}", failure);
+    assert!(!failure.contains("/api/stats"), "stats call is answered by its mock: {}", failure);
+
+    let actions = browser.actions();
+    assert_eq!(actions[0], MockAction::Route("**/api/orders".into()));
+    assert_eq!(actions[2], MockAction::Navigate("https://app.test/dashboard".into()));
+    assert_eq!(actions.last(), Some(&MockAction::Unroute(None)));
+
+    // Later specs on the same browser see the real backend again
+    browser.navigate("https://app.test/dashboard").unwrap();
+    let statuses: Vec<_> = browser.take_network_log().unwrap().iter().map(|e| e.status).collect();
+    assert_eq!(statuses, vec![Some(200), Some(500)]);
+}
+
+#[test]
+fn runner_reports_unreadable_mock_fixture() {
+    let mut mock = RouteMock::status("**/api/orders", 200);
+    mock.fixture = Some("fixtures/definitely-missing.json".into());
+    let mut spec = title_spec("Missing fixture", "https://app.test/dashboard", "Dashboard");
+    spec.mocks = vec![mock];
+
+    let mut browser = network_browser();
+    let result = TestRunner::run_with_config(&spec, &mut browser, &quiet_config());
+    assert!(!result.passed);
+    assert!(result.error.unwrap().contains("definitely-missing.json"));
+    assert!(!browser.actions().iter().any(|a| matches!(a, MockAction::Navigate(_))));
+}
+
+#[test]
+fn explore_live_installs_configured_mocks_first() {
+    let config = ExplorerConfig {
+        start_url: "https://app.test/login".into(),
+        max_pages: 1,
+        mocks: vec![RouteMock::status("https://analytics.test/**", 204)],
+        ..ExplorerConfig::default()
+    };
+    let mut browser = network_browser();
+    explore_live(&config, &mut browser, &MockPageAnalyzer, None, None, None).unwrap();
+    assert_eq!(browser.actions()[0], MockAction::Route("https://analytics.test/**".into()));
+}
+
+// =========================================================================
+// Recording
+// =========================================================================
+
+fn event(value: Value) -> RecordedEvent {
+    serde_json::from_value(value).unwrap()
+}
+
+fn fill_event(element: Value, value: &str) -> RecordedEvent {
+    event(json!({ "kind": "fill", "element": element, "value": value }))
+}
+
+fn click_event(element: Value) -> RecordedEvent {
+    event(json!({ "kind": "click", "element": element }))
+}
+
+#[test]
+fn record_session_builds_replayable_spec_with_suggested_assertions() {
+    let mut browser = login_browser()
+        .with_recorded_events(vec![
+            fill_event(input("Email", "login", "email"), "ada@example.com"),
+            fill_event(input("Password", "login", "password"), "hunter22"),
+        ])
+        .with_recorded_events(vec![
+            click_event(button("Sign in", Some("login"))),
+            event(json!({ "kind": "navigate", "url": "https://app.test/dashboard" })),
+        ]);
+
+    let mut polls = 0;
+    let spec = record_session(
+        &mut browser,
+        "https://app.test/login",
+        None,
+        Duration::ZERO,
+        &mut || {
+            polls += 1;
+            polls > 3
+        },
+    )
+    .unwrap();
+
+    assert_eq!(&browser.actions()[..2], &[
+        MockAction::Navigate("https://app.test/login".into()),
+        MockAction::StartRecording,
+    ]);
+    assert_eq!(spec.name, "Recorded: Dashboard");
+    assert_eq!(spec.start_url, "https://app.test/login");
+    assert_eq!(spec.steps.len(), 2);
+    assert_eq!(
+        spec.steps[0],
+        TestStep::FillAndSubmit {
+            form: "login".into(),
+            values: HashMap::from([
+                ("Email".to_string(), "ada@example.com".to_string()),
+                ("Password".to_string(), "hunter22".to_string()),
+            ]),
+            submit_label: Some("Sign in".into()),
+            frame_path: Vec::new(),
+        }
+    );
+    let TestStep::Assert { assertions } = &spec.steps[1] else {
+        panic!("expected suggested assertions, got {:?}", spec.steps[1]);
+    };
+    assert_eq!(assertions[0], AssertionSpec::UrlContains { expected: "/dashboard".into() });
+    assert!(matches!(&assertions[1], AssertionSpec::TextPresent { expected } if expected.contains("Welcome back") || expected.starts_with("Order")));
+    assert_eq!(assertions[2], AssertionSpec::TitleContains { expected: "Dashboard".into() });
+
+    // The recording replays against the same app
+    let result = TestRunner::run_with_config(&spec, &mut login_browser(), &quiet_config());
+    assert!(result.passed, "{:?}", result);
+}
+
+#[test]
+fn recorder_maps_navigation_toggles_and_formless_inputs() {
+    let mut search = input("Search", "x", "search");
+    search["formId"] = Value::Null;
+    let mut terms = input("Accept terms", "signup", "checkbox");
+    terms["role"] = json!("checkbox");
+
+    let mut recorder = Recorder::new("https://app.test/");
+    for e in [
+        event(json!({ "kind": "navigate", "url": "https://app.test/" })),
+        click_event(input("Email", "signup", "email")),
+        fill_event(search.clone(), "sh"),
+        fill_event(search, "shoes"),
+        click_event(terms),
+        click_event(button("Docs", None)),
+        event(json!({ "kind": "navigate", "url": "https://app.test/docs" })),
+        event(json!({ "kind": "navigate", "url": "https://app.test/docs/intro" })),
+        click_event(text("p", "note", "Just text")),
+        event(json!({ "kind": "navigate", "url": "https://app.test/pricing" })),
+    ] {
+        recorder.record(&e);
+    }
+
+    assert_eq!(
+        recorder.steps(),
+        &[
+            TestStep::Type { label: "Search".into(), text: "shoes".into(), form: None, delay_ms: 0 },
+            TestStep::Click { label: "Accept terms".into(), frame_path: Vec::new() },
+            TestStep::Click { label: "Docs".into(), frame_path: Vec::new() },
+            TestStep::Navigate { url: "https://app.test/pricing".into() },
+        ]
+    );
+}
+
+#[test]
+fn recorder_submits_forms_inside_frames_in_one_step() {
+    let frame = ["iframe#card"];
+    let mut recorder = Recorder::new("https://app.test/pay");
+    recorder.record(&fill_event(in_frame(input("Card number", "card-form", "text"), &frame), "4242"));
+    recorder.record(&click_event(in_frame(button("Pay", Some("card-form")), &frame)));
+
+    let step = TestStep::FillAndSubmit {
+        form: "card-form".into(),
+        values: HashMap::from([("Card number".into(), "4242".into())]),
+        submit_label: Some("Pay".into()),
+        frame_path: vec!["iframe#card".into()],
+    };
+    assert_eq!(recorder.steps(), std::slice::from_ref(&step));
+
+    let spec = TestSpec {
+        name: "Pay".into(),
+        start_url: "https://app.test/pay".into(),
+        steps: vec![step],
+        options: None,
+        auth: false,
+        mocks: Vec::new(),
+    };
+    let mut browser = framed_card_browser();
+    let result = TestRunner::run_with_config(&spec, &mut browser, &quiet_config());
+    assert!(result.passed, "{:?}", result.error);
+    assert_eq!(browser.last_url(), Some("https://app.test/thanks"));
+}
+
+// =========================================================================
+// Dialogs
+// =========================================================================
+
+fn delete_browser() -> MockBrowser {
+    MockBrowser::new()
+        .with_page(
+            "https://app.test/items",
+            page("https://app.test/items", "Items", vec![button("Delete", None)]),
+        )
+        .with_page(
+            "https://app.test/items?deleted=1",
+            page("https://app.test/items?deleted=1", "Items", vec![text("p", "flash", "Item deleted")]),
+        )
+        .with_click_target("Delete", "https://app.test/items?deleted=1")
+        .with_dialog("Delete", DialogKind::Confirm, "Delete this item?")
+}
+
+fn delete_spec(steps: Vec<TestStep>) -> TestSpec {
+    TestSpec {
+        name: "Delete item".into(),
+        start_url: "https://app.test/items".into(),
+        steps,
+        options: None,
+        auth: false,
+        mocks: Vec::new(),
+    }
+}
+
+fn click_delete() -> TestStep {
+    TestStep::Click { label: "Delete".into(), frame_path: Vec::new() }
+}
+
+#[test]
+fn runner_accepts_expected_dialog_and_asserts_it() {
+    let spec = delete_spec(vec![
+        TestStep::ExpectDialog { contains: Some("delete this".into()), accept: true, prompt_text: None },
+        click_delete(),
+        TestStep::Assert {
+            assertions: vec![
+                AssertionSpec::DialogShown { contains: Some("Delete".into()) },
+                AssertionSpec::TextPresent { expected: "Item deleted".into() },
+            ],
+        },
+    ]);
+    let mut browser = delete_browser();
+    let result = TestRunner::run_with_config(&spec, &mut browser, &quiet_config());
+    assert!(result.passed, "{:?}", result);
+
+    // The policy only covers the step after ExpectDialog
+    let policies: Vec<_> = browser
+        .actions()
+        .iter()
+        .filter_map(|a| match a {
+            MockAction::SetDialogPolicy(p) => Some(p.clone()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(policies, vec![DialogPolicy::Accept, DialogPolicy::Dismiss]);
+}
+
+#[test]
+fn runner_dismisses_unexpected_dialogs() {
+    let spec = delete_spec(vec![
+        click_delete(),
+        TestStep::Assert {
+            assertions: vec![
+                AssertionSpec::UrlEquals { expected: "https://app.test/items".into() },
+                AssertionSpec::DialogShown { contains: None },
+            ],
+        },
+    ]);
+    let mut browser = delete_browser();
+    let result = TestRunner::run_with_config(&spec, &mut browser, &quiet_config());
+    assert!(result.passed, "{:?}", result);
+}
+
+#[test]
+fn runner_fails_step_when_expected_dialog_does_not_match() {
+    let spec = delete_spec(vec![
+        TestStep::ExpectDialog { contains: Some("Discard draft?".into()), accept: false, prompt_text: None },
+        click_delete(),
+    ]);
+    let mut browser = delete_browser();
+    let result = TestRunner::run_with_config(&spec, &mut browser, &quiet_config());
+    assert!(!result.passed);
+    assert_eq!(result.steps_run, 2);
+    let error = result.error.unwrap();
+    assert!(error.contains("Expected a dialog containing 'Discard draft?'"), "{}", error);
+    assert!(error.contains(r#"confirm "Delete this item?" (dismissed)"#), "{}", error);
+
+    let spec = delete_spec(vec![TestStep::Assert {
+        assertions: vec![AssertionSpec::DialogShown { contains: None }],
+    }]);
+    let result = TestRunner::run_with_config(&spec, &mut delete_browser(), &quiet_config());
+    assert!(!result.passed);
+    assert_eq!(result.assertion_results[0].message.as_deref(), Some("No dialog was shown"));
+}
+
+#[test]
+fn mock_browser_answers_prompt_with_policy_text() {
+    let mut browser = delete_browser().with_dialog("Delete", DialogKind::Prompt, "Type DELETE to confirm");
+    browser.navigate("https://app.test/items").unwrap();
+    browser.set_dialog_policy(&DialogPolicy::Respond { text: "DELETE".into() }).unwrap();
+    browser.click(&button_hint("Delete")).unwrap();
+
+    let dialogs = browser.take_dialogs().unwrap();
+    assert_eq!(dialogs.len(), 1);
+    assert_eq!(dialogs[0].kind, DialogKind::Prompt);
+    assert_eq!(dialogs[0].prompt_text.as_deref(), Some("DELETE"));
+    assert_eq!(dialogs[0].url.as_deref(), Some("https://app.test/items"));
+    assert_eq!(browser.last_url(), Some("https://app.test/items?deleted=1"));
+    assert!(browser.take_dialogs().unwrap().is_empty());
+}
+
+#[test]
+fn explore_live_records_dialogs_on_transitions() {
+    let config = ExplorerConfig {
+        start_url: "https://app.test/login".into(),
+        max_pages: 5,
+        ..ExplorerConfig::default()
+    };
+    let mut browser = login_browser().with_dialog("Sign in", DialogKind::Alert, "Welcome back!");
+    let app_map = explore_live(&config, &mut browser, &MockPageAnalyzer, None, None, None).unwrap();
+
+    let submission = app_map
+        .transitions
+        .iter()
+        .find(|t| matches!(t.kind, TransitionKind::FormSubmission { .. }))
+        .expect("login form was submitted");
+    assert_eq!(submission.dialogs.len(), 1);
+    assert_eq!(submission.dialogs[0].message, "Welcome back!");
+    assert_eq!(submission.dialogs[0].kind, DialogKind::Alert);
+}
@@ -1,7 +1,17 @@
 use screen_detection::{
+    agent::error::AgentError,
     browser::{
-        playwright::SelectorHint,
-        session::{BrowserRequest, BrowserResponse},
+        candidates::nearest_candidates,
+        console::{ConsoleEntry, ConsoleLevel},
+        dialogs::{DialogEntry, DialogKind, DialogPolicy},
+        network::{NetworkEntry, to_har},
+        playwright::{SelectorHint, is_virtual_form_id},
+        recording::RecordedEvent,
+        routes::{RouteMock, glob_matches},
+        session::{
+            BrowserRequest, BrowserResponse, ErrorCode, LEGACY_PROTOCOL_VERSION, PROTOCOL_VERSION, ServerHandshake,
+        },
+        tabs::{TabInfo, TabSelector},
     },
     screen::{
         classifier::classify,
@@ -51,7 +61,13 @@ fn classifier_populates_screen_element_fields() {
             section_heading: None,
             nearby_help_text: None,
             semantic_section: None,
+            aria_live: None,
+            in_dialog: false,
+            live_region: None,
             visible: false,
+            frame_path: Vec::new(),
+            shadow_path: Vec::new(),
+            table: None,
         },
         DomElement {
             tag: "button".into(),
@@ -88,7 +104,13 @@ fn classifier_populates_screen_element_fields() {
             section_heading: None,
             nearby_help_text: None,
             semantic_section: None,
+            aria_live: None,
+            in_dialog: false,
+            live_region: None,
             visible: false,
+            frame_path: Vec::new(),
+            shadow_path: Vec::new(),
+            table: None,
         },
         DomElement {
             tag: "div".into(),
@@ -125,7 +147,13 @@ fn classifier_populates_screen_element_fields() {
             section_heading: None,
             nearby_help_text: None,
             semantic_section: None,
+            aria_live: None,
+            in_dialog: false,
+            live_region: None,
             visible: false,
+            frame_path: Vec::new(),
+            shadow_path: Vec::new(),
+            table: None,
         },
     ];
 
@@ -164,6 +192,8 @@ fn selector_hint_serializes_with_correct_field_names() {
         tag: Some("input".into()),
         input_type: Some("email".into()),
         form_id: Some("login".into()),
+        frame_path: Vec::new(),
+        shadow_path: Vec::new(),
     };
 
     let json: serde_json::Value = serde_json::to_value(&hint).unwrap();
@@ -188,6 +218,8 @@ fn selector_hint_skips_none_fields() {
         tag: Some("a".into()),
         input_type: None,
         form_id: None,
+        frame_path: Vec::new(),
+        shadow_path: Vec::new(),
     };
 
     let json_str = serde_json::to_string(&hint).unwrap();
@@ -197,6 +229,170 @@ fn selector_hint_skips_none_fields() {
     assert!(!json_str.contains("formId"), "None fields must be skipped");
     assert!(json_str.contains("name"), "Present fields must appear");
     assert!(json_str.contains("tag"), "Present fields must appear");
+    assert!(!json_str.contains("framePath"), "Main-frame hints carry no frame path");
+}
+
+#[test]
+fn selector_hint_serializes_frame_path() {
+    let hint = SelectorHint {
+        role: Some("textbox".into()),
+        name: Some("Card number".into()),
+        tag: None,
+        input_type: None,
+        form_id: None,
+        frame_path: vec!["iframe#checkout".into(), "iframe[name=\"card\"]".into()],
+        shadow_path: Vec::new(),
+    };
+    let json = serde_json::to_value(&hint).unwrap();
+    assert_eq!(json["framePath"], serde_json::json!(["iframe#checkout", "iframe[name=\"card\"]"]));
+    assert!(json.get("shadowPath").is_none());
+
+    let hint = SelectorHint { frame_path: Vec::new(), shadow_path: vec!["ds-field#email".into()], ..hint };
+    let json = serde_json::to_value(&hint).unwrap();
+    assert_eq!(json["shadowPath"], serde_json::json!(["ds-field#email"]));
+    assert!(json.get("framePath").is_none());
+}
+
+// =========================================================================
+// Frame-aware classification
+// =========================================================================
+
+#[test]
+fn classify_separates_frame_forms_and_groups_formless_frame_content() {
+    let elements: Vec<DomElement> = serde_json::from_value(serde_json::json!([
+        {"tag": "input", "type": "email", "ariaLabel": "Email", "formId": "pay",
+         "disabled": false, "required": true, "text": null, "role": null},
+        {"tag": "input", "type": "text", "ariaLabel": "Name", "formId": "pay",
+         "disabled": false, "required": true, "text": null, "role": null,
+         "frame_path": ["iframe#billing"]},
+        {"tag": "input", "type": "text", "ariaLabel": "Card number", "formId": null,
+         "disabled": false, "required": true, "text": null, "role": null,
+         "frame_path": ["iframe#billing", "iframe#card"]},
+        {"tag": "button", "text": "Pay now", "formId": null, "type": "submit",
+         "disabled": false, "required": false, "ariaLabel": null, "role": null,
+         "frame_path": ["iframe#billing", "iframe#card"]},
+        {"tag": "button", "text": "Cancel", "formId": null, "type": "button",
+         "disabled": false, "required": false, "ariaLabel": null, "role": null}
+    ]))
+    .unwrap();
+
+    let semantics = classify(&elements);
+    assert_eq!(semantics.forms.len(), 3);
+
+    let main_pay = semantics.forms.iter().find(|f| f.id == "pay" && f.frame_path.is_empty()).unwrap();
+    assert_eq!(main_pay.inputs[0].label.as_deref(), Some("Email"));
+    let frame_pay = semantics.forms.iter().find(|f| f.id == "pay" && !f.frame_path.is_empty()).unwrap();
+    assert_eq!(frame_pay.frame_path, vec!["iframe#billing"]);
+    assert_eq!(frame_pay.inputs[0].label.as_deref(), Some("Name"));
+
+    let card = semantics.forms.iter().find(|f| f.id == "iframe#card").unwrap();
+    assert_eq!(card.frame_path, vec!["iframe#billing", "iframe#card"]);
+    assert_eq!(card.inputs.len(), 1);
+    assert_eq!(card.actions[0].label.as_deref(), Some("Pay now"));
+
+    // Main-frame content outside a form is still standalone
+    assert_eq!(semantics.standalone_actions.len(), 1);
+    assert_eq!(semantics.standalone_actions[0].label.as_deref(), Some("Cancel"));
+}
+
+#[test]
+fn classify_groups_formless_inputs_into_virtual_forms() {
+    let elements: Vec<DomElement> = serde_json::from_value(serde_json::json!([
+        {"tag": "input", "type": "text", "ariaLabel": "Card holder", "formId": null,
+         "disabled": false, "required": true, "text": null, "role": null,
+         "semantic_section": "main", "section_heading": "Checkout", "fieldset_legend": "Billing"},
+        {"tag": "input", "type": "text", "ariaLabel": "Street", "formId": null,
+         "disabled": false, "required": true, "text": null, "role": null,
+         "semantic_section": "main", "section_heading": "Checkout", "fieldset_legend": "Shipping"},
+        {"tag": "button", "text": "Place order", "formId": null, "type": "button",
+         "disabled": false, "required": false, "ariaLabel": null, "role": null,
+         "semantic_section": "main", "section_heading": "Checkout"},
+        {"tag": "input", "type": "email", "ariaLabel": "Email", "formId": null,
+         "disabled": false, "required": false, "text": null, "role": null,
+         "semantic_section": "footer", "section_heading": "Newsletter"},
+        {"tag": "div", "role": "button", "text": "Subscribe", "formId": null,
+         "disabled": false, "required": false, "ariaLabel": null, "type": null,
+         "semantic_section": "footer", "section_heading": "Newsletter"},
+        {"tag": "input", "type": "search", "ariaLabel": "Search", "formId": null,
+         "disabled": false, "required": false, "text": null, "role": null},
+        {"tag": "a", "text": "Help", "href": "/help", "formId": null,
+         "disabled": false, "required": false, "ariaLabel": null, "role": null, "type": null,
+         "semantic_section": "footer", "section_heading": "Newsletter"},
+        {"tag": "button", "text": "Menu", "formId": null, "type": "button",
+         "disabled": false, "required": false, "ariaLabel": null, "role": null,
+         "semantic_section": "nav"}
+    ]))
+    .unwrap();
+
+    let semantics = classify(&elements);
+    let mut ids: Vec<&str> = semantics.forms.iter().map(|f| f.id.as_str()).collect();
+    ids.sort();
+    assert_eq!(
+        ids,
+        vec![
+            "virtual:footer-newsletter",
+            "virtual:main-checkout-billing",
+            "virtual:main-checkout-shipping",
+            "virtual:page",
+        ]
+    );
+    assert!(ids.iter().all(|id| is_virtual_form_id(id)));
+
+    let form = |id: &str| semantics.forms.iter().find(|f| f.id == id).unwrap();
+    // A button outside the fieldsets joins the last group of its section
+    assert!(form("virtual:main-checkout-billing").actions.is_empty());
+    assert_eq!(form("virtual:main-checkout-shipping").actions[0].label.as_deref(), Some("Place order"));
+    let newsletter = form("virtual:footer-newsletter");
+    assert_eq!(newsletter.inputs[0].label.as_deref(), Some("Email"));
+    assert_eq!(newsletter.primary_action, None);
+    assert_eq!(newsletter.actions[0].label.as_deref(), Some("Subscribe"));
+    assert_eq!(newsletter.intent.as_ref().unwrap().label, "Newsletter");
+    assert_eq!(form("virtual:page").inputs[0].label.as_deref(), Some("Search"));
+
+    // Links and buttons away from any inputs stay standalone
+    let standalone: Vec<_> = semantics.standalone_actions.iter().filter_map(|a| a.label.as_deref()).collect();
+    assert_eq!(standalone, vec!["Help", "Menu"]);
+}
+
+#[test]
+fn selector_hint_does_not_scope_to_virtual_forms() {
+    let hint = SelectorHint {
+        role: Some("textbox".into()),
+        name: Some("Email".into()),
+        tag: None,
+        input_type: None,
+        form_id: Some("virtual:footer-newsletter".into()),
+        frame_path: Vec::new(),
+        shadow_path: Vec::new(),
+    };
+    assert_eq!(hint.form_scope(), None);
+    let json = serde_json::to_value(&hint).unwrap();
+    assert!(json.get("formId").is_none(), "virtual form ids are not sent to the browser");
+
+    let hint = SelectorHint { form_id: Some("login".into()), ..hint };
+    assert_eq!(hint.form_scope(), Some("login"));
+    assert_eq!(serde_json::to_value(&hint).unwrap()["formId"], "login");
+}
+
+#[test]
+fn selector_hint_does_not_scope_to_implicit_frame_forms() {
+    let hint = SelectorHint {
+        role: Some("textbox".into()),
+        name: Some("Cardholder".into()),
+        tag: None,
+        input_type: None,
+        form_id: Some("iframe#payment".into()),
+        frame_path: vec!["iframe#payment".into()],
+        shadow_path: Vec::new(),
+    };
+    assert_eq!(hint.form_scope(), None);
+    let json = serde_json::to_value(&hint).unwrap();
+    assert!(json.get("formId").is_none(), "a frame's implicit form is not sent to the browser");
+    assert_eq!(json["framePath"], serde_json::json!(["iframe#payment"]));
+
+    // A real form inside the frame still scopes the search
+    let hint = SelectorHint { form_id: Some("card-form".into()), ..hint };
+    assert_eq!(hint.form_scope(), Some("card-form"));
 }
 
 // =========================================================================
@@ -229,6 +425,8 @@ fn browser_request_fill_serializes_correctly() {
         tag: Some("input".into()),
         input_type: Some("email".into()),
         form_id: Some("login".into()),
+        frame_path: Vec::new(),
+        shadow_path: Vec::new(),
     };
     let req = BrowserRequest::fill(&selector, "test@example.com");
     let json: serde_json::Value = serde_json::to_value(&req).unwrap();
@@ -251,6 +449,8 @@ fn browser_request_click_serializes_correctly() {
         tag: None,
         input_type: None,
         form_id: None,
+        frame_path: Vec::new(),
+        shadow_path: Vec::new(),
     };
     let req = BrowserRequest::click(&selector);
     let json: serde_json::Value = serde_json::to_value(&req).unwrap();
@@ -376,6 +576,50 @@ fn browser_response_deserializes_ready_signal() {
     assert_eq!(resp.ready, Some(true));
 }
 
+#[test]
+fn handshake_requires_matching_or_missing_protocol_version() {
+    let ready: BrowserResponse =
+        serde_json::from_str(r#"{"ok":true,"ready":true,"protocol":1,"capabilities":["navigate","extract"]}"#).unwrap();
+    let handshake = ServerHandshake::from_ready(&ready).unwrap();
+    assert_eq!(handshake.protocol, PROTOCOL_VERSION);
+    assert!(handshake.supports("extract"));
+    assert!(!handshake.supports("tabs"));
+
+    let open: BrowserResponse = serde_json::from_str(r#"{"ok":true,"ready":true,"protocol":1}"#).unwrap();
+    assert!(ServerHandshake::from_ready(&open).unwrap().supports("tabs"), "no list: everything is attempted");
+
+    let legacy: BrowserResponse = serde_json::from_str(r#"{"ok":true,"ready":true}"#).unwrap();
+    let handshake = ServerHandshake::from_ready(&legacy).unwrap();
+    assert_eq!(handshake.protocol, LEGACY_PROTOCOL_VERSION);
+    assert!(handshake.supports("extract"));
+    assert!(!handshake.supports("tabs"), "unversioned: legacy commands only");
+
+    let newer: BrowserResponse = serde_json::from_str(r#"{"ok":true,"ready":true,"protocol":2}"#).unwrap();
+    assert!(matches!(
+        ServerHandshake::from_ready(&newer),
+        Err(AgentError::ProtocolMismatch { found: 2, .. })
+    ));
+    let not_ready: BrowserResponse = serde_json::from_str(r#"{"ok":true,"protocol":1}"#).unwrap();
+    assert!(matches!(ServerHandshake::from_ready(&not_ready), Err(AgentError::SessionProtocol { .. })));
+}
+
+#[test]
+fn browser_response_deserializes_id_and_error_code() {
+    let resp: BrowserResponse =
+        serde_json::from_str(r#"{"id":7,"ok":false,"code":"unsupported_command","error":"no"}"#).unwrap();
+    assert_eq!(resp.id, Some(7));
+    assert_eq!(resp.code, Some(ErrorCode::UnsupportedCommand));
+    let resp: BrowserResponse = serde_json::from_str(r#"{"ok":false,"code":"browser_closed"}"#).unwrap();
+    assert_eq!(resp.code, Some(ErrorCode::Other));
+}
+
+#[test]
+fn browser_request_reports_its_command() {
+    assert_eq!(BrowserRequest::dialogs().command(), "dialogs");
+    assert_eq!(BrowserRequest::scroll_by(0, 400).command(), "action");
+    assert_eq!(BrowserRequest::unroute(None).command(), "unroute");
+}
+
 #[test]
 fn test_browser_response_with_text() {
     let json = r#"{"ok":true,"text":"Hello World"}"#;
@@ -434,6 +678,8 @@ fn browser_request_select_option_serialization() {
         tag: Some("select".into()),
         input_type: None,
         form_id: Some("address".into()),
+        frame_path: Vec::new(),
+        shadow_path: Vec::new(),
     };
     let request = BrowserRequest::select_option(&selector, "us");
     let json: serde_json::Value = serde_json::to_value(&request).unwrap();
@@ -453,6 +699,8 @@ fn browser_request_check_serialization() {
         tag: Some("input".into()),
         input_type: Some("checkbox".into()),
         form_id: None,
+        frame_path: Vec::new(),
+        shadow_path: Vec::new(),
     };
     let request = BrowserRequest::check(&selector);
     let json: serde_json::Value = serde_json::to_value(&request).unwrap();
@@ -470,6 +718,8 @@ fn browser_request_uncheck_serialization() {
         tag: Some("input".into()),
         input_type: Some("checkbox".into()),
         form_id: None,
+        frame_path: Vec::new(),
+        shadow_path: Vec::new(),
     };
     let request = BrowserRequest::uncheck(&selector);
     let json: serde_json::Value = serde_json::to_value(&request).unwrap();
@@ -478,6 +728,59 @@ fn browser_request_uncheck_serialization() {
     assert_eq!(json["action"], "uncheck");
 }
 
+#[test]
+fn browser_request_interactions_serialize_their_arguments() {
+    let field = SelectorHint {
+        role: Some("textbox".into()),
+        name: Some("City".into()),
+        tag: Some("input".into()),
+        input_type: None,
+        form_id: None,
+        frame_path: Vec::new(),
+        shadow_path: Vec::new(),
+    };
+    let card = SelectorHint { name: Some("Card A".into()), ..field.clone() };
+
+    let json = serde_json::to_value(BrowserRequest::type_text(&field, "Ber", 80)).unwrap();
+    assert_eq!(json["action"], "type");
+    assert_eq!(json["value"], "Ber");
+    assert_eq!(json["delay_ms"], 80);
+
+    let json = serde_json::to_value(BrowserRequest::press(None, "Control+A")).unwrap();
+    assert_eq!(json, serde_json::json!({"cmd": "action", "action": "press", "key": "Control+A"}));
+
+    let json = serde_json::to_value(BrowserRequest::scroll_by(0, -300)).unwrap();
+    assert_eq!(json, serde_json::json!({"cmd": "action", "action": "scroll_by", "delta_x": 0, "delta_y": -300}));
+
+    let json = serde_json::to_value(BrowserRequest::drag(&card, &field)).unwrap();
+    assert_eq!(json["action"], "drag");
+    assert_eq!(json["selector"]["name"], "Card A");
+    assert_eq!(json["target"]["name"], "City");
+
+    let files = vec!["/tmp/a.png".to_string()];
+    let json = serde_json::to_value(BrowserRequest::set_input_files(&field, &files)).unwrap();
+    assert_eq!(json["action"], "set_input_files");
+    assert_eq!(json["files"], serde_json::json!(["/tmp/a.png"]));
+
+    let json = serde_json::to_value(BrowserRequest::hover(&card)).unwrap();
+    assert_eq!(json.as_object().unwrap().len(), 3, "no unset arguments: {}", json);
+}
+
+#[test]
+fn classify_treats_file_inputs_as_form_fields() {
+    let dom: Vec<DomElement> = serde_json::from_value(serde_json::json!([
+        {"tag": "input", "type": "file", "ariaLabel": "Resume", "formId": "apply",
+         "disabled": false, "required": true, "visible": true},
+        {"tag": "button", "type": "submit", "text": "Apply", "formId": "apply",
+         "disabled": false, "required": false, "visible": true}
+    ]))
+    .unwrap();
+    let semantics = classify(&dom);
+    let form = semantics.forms.iter().find(|f| f.id == "apply").unwrap();
+    assert_eq!(form.inputs.len(), 1);
+    assert_eq!(form.inputs[0].input_type.as_deref(), Some("file"));
+}
+
 // =========================================================================
 // New: DomElement with new fields deserialization
 // =========================================================================
@@ -638,3 +941,326 @@ fn screen_state_structural_outline_backward_compat() {
     assert!(outline.headings.is_empty());
     assert!(outline.landmarks.is_empty());
 }
+
+// =========================================================================
+// Network log and HAR export
+// =========================================================================
+
+fn network_entry(url: &str, method: &str, status: Option<u16>) -> NetworkEntry {
+    NetworkEntry {
+        url: url.into(),
+        method: method.into(),
+        status,
+        resource_type: Some("xhr".into()),
+        mime_type: Some("application/json".into()),
+        started_at: Some("2024-05-01T10:00:00.000Z".into()),
+        duration_ms: Some(42.5),
+        size: Some(128),
+        failure: None,
+    }
+}
+
+#[test]
+fn network_entry_deserializes_with_defaults() {
+    let entry: NetworkEntry =
+        serde_json::from_str(r#"{"url": "https://a.test/api", "method": "GET"}"#).unwrap();
+    assert!(entry.status.is_none());
+    assert!(!entry.is_failed(400), "a pending request is not a failure");
+}
+
+#[test]
+fn network_entry_failure_detection() {
+    assert!(network_entry("/api", "GET", Some(500)).is_failed(400));
+    assert!(!network_entry("/api", "GET", Some(404)).is_failed(500));
+    let refused = NetworkEntry {
+        failure: Some("net::ERR_CONNECTION_REFUSED".into()),
+        ..network_entry("/api", "POST", None)
+    };
+    assert!(refused.is_failed(400));
+    assert_eq!(refused.summary(), "POST /api → net::ERR_CONNECTION_REFUSED");
+}
+
+#[test]
+fn to_har_builds_valid_log() {
+    let entries = vec![
+        network_entry("https://a.test/", "GET", Some(200)),
+        NetworkEntry {
+            failure: Some("net::ERR_FAILED".into()),
+            ..network_entry("https://a.test/api", "POST", None)
+        },
+    ];
+    let har = to_har(&entries, "Checkout");
+    assert_eq!(har["log"]["version"], "1.2");
+    assert_eq!(har["log"]["pages"][0]["title"], "Checkout");
+    let har_entries = har["log"]["entries"].as_array().unwrap();
+    assert_eq!(har_entries.len(), 2);
+    assert_eq!(har_entries[0]["request"]["method"], "GET");
+    assert_eq!(har_entries[0]["response"]["status"], 200);
+    assert_eq!(har_entries[0]["response"]["content"]["size"], 128);
+    assert_eq!(har_entries[0]["time"], 42.5);
+    assert_eq!(har_entries[1]["response"]["status"], 0);
+    assert_eq!(har_entries[1]["_failureText"], "net::ERR_FAILED");
+}
+
+#[test]
+fn browser_request_network_serializes_correctly() {
+    let json = serde_json::to_string(&BrowserRequest::network()).unwrap();
+    assert_eq!(json, r#"{"cmd":"network"}"#);
+}
+
+// =========================================================================
+// Console log
+// =========================================================================
+
+#[test]
+fn console_entry_deserializes_levels() {
+    let entries: Vec<ConsoleEntry> = serde_json::from_str(
+        r#"[
+            {"level": "warning", "text": "deprecated", "location": "https://a.test/app.js:3:7"},
+            {"level": "pageerror", "text": "x is undefined", "stack": "at main (app.js:10:5)"},
+            {"level": "table", "text": "[object]"}
+        ]"#,
+    )
+    .unwrap();
+    assert_eq!(entries[0].level, ConsoleLevel::Warning);
+    assert!(!entries[0].is_error());
+    assert!(entries[1].is_page_error() && entries[1].is_error());
+    assert_eq!(entries[2].level, ConsoleLevel::Other);
+    assert!(entries.iter().all(|e| e.step.is_none()));
+}
+
+#[test]
+fn console_entry_summary_includes_step_and_location() {
+    let entry = ConsoleEntry {
+        level: ConsoleLevel::Error,
+        text: "boom".into(),
+        location: Some("app.js:10:5".into()),
+        stack: None,
+        step: Some(2),
+    };
+    assert_eq!(entry.summary(), "[step 2] error: boom (app.js:10:5)");
+    let start = ConsoleEntry { level: ConsoleLevel::PageError, location: None, step: None, ..entry };
+    assert_eq!(start.summary(), "[start] uncaught exception: boom");
+}
+
+#[test]
+fn browser_request_console_serializes_correctly() {
+    let json = serde_json::to_string(&BrowserRequest::console()).unwrap();
+    assert_eq!(json, r#"{"cmd":"console"}"#);
+}
+
+// =========================================================================
+// Tabs
+// =========================================================================
+
+fn tab(index: usize, url: &str, title: &str) -> TabInfo {
+    TabInfo { index, url: url.into(), title: title.into(), active: index == 0 }
+}
+
+#[test]
+fn tab_selector_picks_newest_match() {
+    let tabs = vec![
+        tab(0, "https://app.test/login", "Login"),
+        tab(1, "https://accounts.test/oauth?step=1", "Sign in"),
+        tab(2, "https://accounts.test/oauth?step=2", "Consent"),
+    ];
+    assert_eq!(TabSelector::default().pick(&tabs).unwrap().index, 2);
+    assert_eq!(TabSelector::url_contains("oauth").pick(&tabs).unwrap().index, 2);
+    assert_eq!(TabSelector::index(0).pick(&tabs).unwrap().title, "Login");
+    let both = TabSelector { title_contains: Some("Sign".into()), ..TabSelector::url_contains("oauth") };
+    assert_eq!(both.pick(&tabs).unwrap().index, 1);
+    assert!(TabSelector::url_contains("billing").pick(&tabs).is_none());
+    assert_eq!(both.describe(), "tab with URL containing 'oauth', title containing 'Sign'");
+}
+
+#[test]
+fn browser_request_tab_commands_serialize_correctly() {
+    let json = serde_json::to_string(&BrowserRequest::switch_tab(&TabSelector::url_contains("oauth"))).unwrap();
+    assert_eq!(json, r#"{"cmd":"switch_tab","url_contains":"oauth"}"#);
+    let json = serde_json::to_string(&BrowserRequest::close_tab(&TabSelector::default())).unwrap();
+    assert_eq!(json, r#"{"cmd":"close_tab"}"#);
+    let json = serde_json::to_string(&BrowserRequest::wait_for_popup(3000)).unwrap();
+    assert_eq!(json, r#"{"cmd":"wait_for_popup","timeout_ms":3000}"#);
+    assert_eq!(serde_json::to_string(&BrowserRequest::tabs()).unwrap(), r#"{"cmd":"tabs"}"#);
+}
+
+// =========================================================================
+// Route mocks
+// =========================================================================
+
+#[test]
+fn glob_matches_playwright_style_patterns() {
+    assert!(glob_matches("**/api/orders", "https://app.test/api/orders"));
+    assert!(glob_matches("**/api/orders*", "https://app.test/api/orders?page=2"));
+    assert!(glob_matches("https://app.test/*/edit", "https://app.test/42/edit"));
+    assert!(!glob_matches("https://app.test/*/edit", "https://app.test/users/42/edit"));
+    assert!(glob_matches("https://cdn.test/**", "https://cdn.test/js/app.js"));
+    assert!(!glob_matches("**/api/orders", "https://app.test/api/orders/7"));
+    assert!(glob_matches("https://app.test/", "https://app.test/"));
+}
+
+#[test]
+fn route_mock_matches_method_case_insensitively() {
+    let mut mock = RouteMock::status("**/api/orders", 500);
+    assert!(mock.matches("https://app.test/api/orders", "DELETE"));
+    mock.method = Some("post".into());
+    assert!(mock.matches("https://app.test/api/orders", "POST"));
+    assert!(!mock.matches("https://app.test/api/orders", "GET"));
+}
+
+#[test]
+fn route_mock_resolves_body_and_content_type() {
+    let mut mock = RouteMock::status("**/api/orders", 200);
+    mock.body = Some(serde_json::json!({"orders": []}));
+    mock.method = Some("get".into());
+    let resolved = mock.resolve().unwrap();
+    assert_eq!(resolved.body, r#"{"orders":[]}"#);
+    assert_eq!(resolved.content_type, "application/json");
+    assert_eq!(resolved.method.as_deref(), Some("GET"));
+
+    mock.body = Some(serde_json::json!("maintenance"));
+    assert_eq!(mock.resolve().unwrap().content_type, "text/plain");
+    mock.content_type = Some("text/html".into());
+    assert_eq!(mock.resolve().unwrap().content_type, "text/html");
+}
+
+#[test]
+fn route_mock_reads_fixture_files() {
+    let path = std::env::temp_dir().join(format!("sd_route_fixture_{}.json", std::process::id()));
+    std::fs::write(&path, r#"[{"id":1}]"#).unwrap();
+    let mut mock = RouteMock::status("**/api/orders", 200);
+    mock.fixture = Some(path.to_string_lossy().to_string());
+    let resolved = mock.resolve().unwrap();
+    assert_eq!(resolved.body, r#"[{"id":1}]"#);
+    assert_eq!(resolved.content_type, "application/json");
+
+    mock.body = Some(serde_json::json!("inline"));
+    assert!(mock.resolve().unwrap_err().to_string().contains("both body and fixture"));
+    std::fs::remove_file(&path).unwrap();
+    mock.body = None;
+    assert!(mock.resolve().is_err());
+}
+
+#[test]
+fn browser_request_route_commands_serialize_correctly() {
+    let mut mock = RouteMock::status("**/api/stats", 503);
+    mock.delay_ms = 250;
+    let json = serde_json::to_value(BrowserRequest::route(&mock.resolve().unwrap())).unwrap();
+    assert_eq!(
+        json,
+        serde_json::json!({
+            "cmd": "route", "url": "**/api/stats", "status": 503,
+            "body": "", "content_type": "text/plain", "delay_ms": 250
+        })
+    );
+    let json = serde_json::to_string(&BrowserRequest::unroute(None)).unwrap();
+    assert_eq!(json, r#"{"cmd":"unroute"}"#);
+    let json = serde_json::to_string(&BrowserRequest::unroute(Some("**/api/stats"))).unwrap();
+    assert_eq!(json, r#"{"cmd":"unroute","url":"**/api/stats"}"#);
+}
+
+// =========================================================================
+// Recording
+// =========================================================================
+
+#[test]
+fn browser_request_record_commands_serialize_correctly() {
+    assert_eq!(serde_json::to_string(&BrowserRequest::record_start()).unwrap(), r#"{"cmd":"record_start"}"#);
+    assert_eq!(serde_json::to_string(&BrowserRequest::record_events()).unwrap(), r#"{"cmd":"record_events"}"#);
+}
+
+#[test]
+fn recorded_events_deserialize_with_extracted_elements() {
+    let raw = serde_json::json!([
+        {"kind": "select", "value": "de", "element": {
+            "tag": "select", "text": null, "role": "combobox", "type": null, "ariaLabel": "Country",
+            "disabled": false, "required": false, "formId": "signup", "frame_path": ["iframe#embed"]
+        }},
+        {"kind": "navigate", "url": "https://app.test/done"}
+    ]);
+    let events: Vec<RecordedEvent> = serde_json::from_value(raw).unwrap();
+    match &events[0] {
+        RecordedEvent::Select { element, value } => {
+            assert_eq!(value, "de");
+            assert_eq!(element.form_id.as_deref(), Some("signup"));
+            assert_eq!(element.frame_path, vec!["iframe#embed"]);
+        }
+        other => panic!("expected select, got {:?}", other),
+    }
+    assert!(matches!(&events[1], RecordedEvent::Navigate { url } if url == "https://app.test/done"));
+}
+
+// =========================================================================
+// Dialogs
+// =========================================================================
+
+#[test]
+fn browser_request_dialog_commands_serialize_correctly() {
+    let respond = DialogPolicy::Respond { text: "Ada".into() };
+    assert_eq!(
+        serde_json::to_string(&BrowserRequest::dialog_policy(&respond)).unwrap(),
+        r#"{"cmd":"dialog_policy","policy":"respond","text":"Ada"}"#
+    );
+    assert_eq!(
+        serde_json::to_string(&BrowserRequest::dialog_policy(&DialogPolicy::Dismiss)).unwrap(),
+        r#"{"cmd":"dialog_policy","policy":"dismiss"}"#
+    );
+    assert_eq!(serde_json::to_string(&BrowserRequest::dialogs()).unwrap(), r#"{"cmd":"dialogs"}"#);
+}
+
+#[test]
+fn dialog_policy_follows_expectation() {
+    assert_eq!(DialogPolicy::default(), DialogPolicy::Dismiss);
+    assert_eq!(DialogPolicy::from_expectation(true, None), DialogPolicy::Accept);
+    assert_eq!(DialogPolicy::from_expectation(false, Some("x")), DialogPolicy::Dismiss);
+    assert_eq!(
+        DialogPolicy::from_expectation(true, Some("Ada")),
+        DialogPolicy::Respond { text: "Ada".into() }
+    );
+}
+
+#[test]
+fn dialog_entry_deserializes_and_summarizes() {
+    let raw = r#"[{"kind":"beforeunload","message":"","accepted":true},
+        {"kind":"prompt","message":"Your name?","default_value":"anon","accepted":true,"prompt_text":"Ada","url":"https://app.test/"}]"#;
+    let dialogs: Vec<DialogEntry> = serde_json::from_str(raw).unwrap();
+    assert_eq!(dialogs[0].kind, DialogKind::BeforeUnload);
+    assert_eq!(dialogs[1].default_value.as_deref(), Some("anon"));
+    assert!(dialogs[1].mentions("YOUR NAME"));
+    assert_eq!(dialogs[1].summary(), r#"prompt "Your name?" (accepted)"#);
+}
+
+// =========================================================================
+// Nearest-match suggestions
+// =========================================================================
+
+#[test]
+fn nearest_candidates_rank_similar_names_in_the_same_frame() {
+    let dom = vec![
+        serde_json::json!({"tag": "button", "text": "Log in", "role": "button", "formId": "login"}),
+        serde_json::json!({"tag": "a", "text": "Sign up", "role": "link"}),
+        serde_json::json!({"tag": "button", "text": "Sign-In", "role": "button", "formId": "login"}),
+        serde_json::json!({"tag": "button", "text": "Sign in", "frame_path": ["iframe#sso"]}),
+        serde_json::json!({"tag": "p", "text": "Sign in below"}),
+        serde_json::json!({"tag": "a", "text": "Privacy policy", "role": "link"}),
+    ];
+    let hint = SelectorHint {
+        role: Some("button".into()),
+        name: Some("Sign in".into()),
+        tag: None,
+        input_type: None,
+        form_id: Some("signin".into()),
+        frame_path: Vec::new(),
+        shadow_path: Vec::new(),
+    };
+    let candidates = nearest_candidates(&dom, &hint);
+    let names: Vec<_> = candidates.iter().map(|c| c.name.as_str()).collect();
+    assert_eq!(names, vec!["Sign-In", "Sign up", "Log in"]);
+    // Right name, wrong form: slightly below a perfect score
+    assert!((candidates[0].score - 0.9).abs() < 1e-9);
+    assert_eq!(candidates[0].summary(), "button 'Sign-In' <button> in form 'login' (0.90)");
+    assert!(candidates[1].score > candidates[2].score);
+
+    let unnamed = SelectorHint { name: None, ..hint };
+    assert!(nearest_candidates(&dom, &unnamed).is_empty());
+}
@@ -2,7 +2,8 @@ use screen_detection::canonical::diff::SemanticSignal;
 
 use crate::common::{
     semantic_diff::{
-        diff_between_pages, diff_mutating, diff_static, diff_static_with_initial_flag,
+        diff_between_html_pages, diff_between_pages, diff_html_initial, diff_mutating, diff_static,
+        diff_static_with_initial_flag,
     },
     utils::{is_terminal_success, page},
 };
@@ -179,3 +180,83 @@ fn correct_form_is_marked_submitted() {
         "Correct form must be marked submitted"
     );
 }
+
+#[test]
+fn footer_change_is_not_results() {
+    let diff = diff_between_html_pages(
+        &page("11_catalog.html"),
+        &page("12_catalog_footer_changed.html"),
+    );
+
+    println!("Diff = {:?}", diff);
+    assert!(
+        diff.outputs.added.iter().any(|id| id.starts_with("screen:output:Footer:")),
+        "The new footer text is placed in the footer"
+    );
+    assert_eq!(diff.signals, vec![SemanticSignal::NoOp]);
+}
+
+#[test]
+fn result_list_lands_in_results_region() {
+    let diff = diff_between_html_pages(&page("11_catalog.html"), &page("13_catalog_results.html"));
+
+    println!("Diff = {:?}", diff);
+    let results = diff
+        .outputs
+        .added
+        .iter()
+        .filter(|id| id.starts_with("screen:output:Results:"))
+        .count();
+    // The two list items; the status line is a toast
+    assert_eq!(results, 2);
+    assert!(diff.signals.contains(&SemanticSignal::ResultsAppeared));
+    assert!(diff.signals.contains(&SemanticSignal::ToastAppeared {
+        text: "2 products found".into()
+    }));
+}
+
+#[test]
+fn opening_a_modal_is_not_results() {
+    let diff = diff_between_html_pages(&page("11_catalog.html"), &page("14_catalog_cookie_banner.html"));
+
+    println!("Diff = {:?}", diff);
+    assert_eq!(diff.signals, vec![SemanticSignal::ModalOpened]);
+}
+
+#[test]
+fn closing_a_modal_is_detected() {
+    let diff = diff_between_html_pages(&page("14_catalog_cookie_banner.html"), &page("11_catalog.html"));
+
+    println!("Diff = {:?}", diff);
+    assert_eq!(diff.signals, vec![SemanticSignal::ModalClosed]);
+}
+
+#[test]
+fn replacing_a_modal_with_another_is_detected() {
+    // Both dialogs have a "Close" button; their titles differ
+    let diff = diff_between_html_pages(&page("14_catalog_cookie_banner.html"), &page("18_catalog_newsletter_modal.html"));
+
+    println!("Diff = {:?}", diff);
+    assert_eq!(diff.signals, vec![SemanticSignal::ModalClosed, SemanticSignal::ModalOpened]);
+}
+
+#[test]
+fn page_loading_with_a_modal_reports_it() {
+    let diff = diff_html_initial(&page("14_catalog_cookie_banner.html"));
+
+    assert_eq!(
+        diff.signals,
+        vec![SemanticSignal::ScreenLoaded, SemanticSignal::ModalOpened]
+    );
+}
+
+#[test]
+fn live_region_message_is_a_toast() {
+    let diff = diff_between_html_pages(&page("11_catalog.html"), &page("15_catalog_saved_toast.html"));
+
+    println!("Diff = {:?}", diff);
+    assert_eq!(
+        diff.signals,
+        vec![SemanticSignal::ToastAppeared { text: "Saved to your list".into() }]
+    );
+}
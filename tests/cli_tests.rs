@@ -80,10 +80,12 @@ fn cli_parse_run_minimal() {
             spec,
             format,
             output,
+            jobs,
         } => {
             assert_eq!(spec, "test.yaml");
             assert_eq!(format, "console");
             assert!(output.is_none());
+            assert!(jobs.is_none());
         }
         _ => panic!("Expected Run command"),
     }
@@ -106,6 +108,7 @@ fn cli_parse_run_with_format() {
             spec,
             format,
             output,
+            ..
         } => {
             assert_eq!(spec, "test.yaml");
             assert_eq!(format, "html");
@@ -115,6 +118,15 @@ fn cli_parse_run_with_format() {
     }
 }
 
+#[test]
+fn cli_parse_run_with_jobs() {
+    let cli = Cli::parse_from(["screen-detection", "run", "--spec", "specs/", "-j", "4"]);
+    match cli.command {
+        Commands::Run { jobs, .. } => assert_eq!(jobs, Some(4)),
+        _ => panic!("Expected Run command"),
+    }
+}
+
 #[test]
 fn cli_parse_generate_minimal() {
     let cli = Cli::parse_from(["screen-detection", "generate", "--url", "https://example.com"]);
@@ -170,6 +182,25 @@ fn cli_parse_generate_all_args() {
     }
 }
 
+#[test]
+fn cli_parse_record() {
+    let cli = Cli::parse_from(["screen-detection", "record", "--url", "https://test.com/login", "-o", "login.yaml"]);
+    match cli.command {
+        Commands::Record { url, output, name } => {
+            assert_eq!(url, "https://test.com/login");
+            assert_eq!(output.as_deref(), Some("login.yaml"));
+            assert!(name.is_none());
+        }
+        _ => panic!("Expected Record command"),
+    }
+}
+
+#[test]
+fn cli_parse_doctor() {
+    let cli = Cli::parse_from(["screen-detection", "doctor"]);
+    assert!(matches!(cli.command, Commands::Doctor));
+}
+
 #[test]
 fn cli_parse_global_verbose() {
     let cli = Cli::parse_from(["screen-detection", "-v", "run", "--spec", "t.yaml"]);
@@ -315,6 +346,33 @@ steps:
     std::fs::remove_dir(&dir).ok();
 }
 
+#[test]
+fn load_specs_resolves_fixtures_next_to_the_spec() {
+    use screen_detection::cli::commands::load_specs;
+
+    let dir = std::env::temp_dir().join(format!("screen_detection_cli_fixtures_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("orders.json"), r#"[{"id":1}]"#).unwrap();
+    let yaml = r##"
+name: "Orders"
+start_url: "https://example.com/orders"
+mocks:
+  - url: "**/api/orders"
+    fixture: orders.json
+steps: []
+"##;
+    std::fs::write(dir.join("orders.yaml"), yaml).unwrap();
+
+    // Loaded from elsewhere than the spec's directory
+    let specs = load_specs(dir.to_str().unwrap()).unwrap();
+    let fixture = specs[0].mocks[0].fixture.clone().unwrap();
+    let route = specs[0].mocks[0].resolve();
+    std::fs::remove_dir_all(&dir).ok();
+
+    assert_eq!(std::path::PathBuf::from(fixture), dir.join("orders.json"));
+    assert_eq!(route.unwrap().body, r#"[{"id":1}]"#);
+}
+
 // ============================================================================
 // AuthConfig tests
 // ============================================================================
@@ -327,6 +385,7 @@ fn auth_config_defaults() {
     assert!(auth.submit_label.is_none());
     assert!(auth.success_url_contains.is_none());
     assert!(auth.success_text.is_none());
+    assert!(auth.storage_state.is_none());
 }
 
 #[test]
@@ -347,6 +406,7 @@ credentials:
 submit_label: "Sign In"
 success_url_contains: "/dashboard"
 success_text: "Welcome"
+storage_state: ".auth/admin.json"
 "#;
     let auth: AuthConfig = serde_yaml::from_str(yaml).unwrap();
     assert_eq!(auth.login_url, Some("https://myapp.com/login".into()));
@@ -355,6 +415,7 @@ success_text: "Welcome"
     assert_eq!(auth.submit_label, Some("Sign In".into()));
     assert_eq!(auth.success_url_contains, Some("/dashboard".into()));
     assert_eq!(auth.success_text, Some("Welcome".into()));
+    assert_eq!(auth.storage_state, Some(".auth/admin.json".into()));
 
     // Roundtrip
     let serialized = serde_yaml::to_string(&auth).unwrap();
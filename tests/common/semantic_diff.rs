@@ -2,6 +2,7 @@ use std::thread::sleep;
 use std::time::Duration;
 
 use screen_detection::browser::playwright::extract_screen;
+use screen_detection::browser::static_html::extract_static;
 use screen_detection::canonical::diff::SemanticStateDiff;
 use screen_detection::canonical::{canonical_model::canonicalize, diff::semantic_diff};
 use screen_detection::screen::{classifier::classify, screen_model::DomElement};
@@ -67,3 +68,18 @@ pub fn diff_between_pages(url1: &str, url2: &str) -> SemanticStateDiff {
 
     run_diff_from_raw(raw1, raw2, false)
 }
+
+/// `diff_between_pages` over the pure-Rust static extractor (no browser).
+pub fn diff_between_html_pages(url1: &str, url2: &str) -> SemanticStateDiff {
+    let raw1 = extract_static(url1).unwrap();
+    let raw2 = extract_static(url2).unwrap();
+
+    run_diff_from_raw(raw1, raw2, false)
+}
+
+/// Initial observation of a page read by the static extractor.
+pub fn diff_html_initial(url: &str) -> SemanticStateDiff {
+    let raw = extract_static(url).unwrap();
+
+    run_diff_from_raw(raw.clone(), raw, true)
+}
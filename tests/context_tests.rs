@@ -21,6 +21,7 @@ fn empty_screen() -> ScreenState {
         forms: vec![],
         standalone_actions: vec![],
         outputs: vec![],
+        tables: vec![],
         identities: HashMap::new(),
         structural_outline: Default::default(),
     }
@@ -48,6 +49,7 @@ fn page_model_with_purpose(purpose: &str, domain: &str) -> PageModel {
                 },
             ],
             submit_label: Some("Next".to_string()),
+            frame_path: Vec::new(),
             expected_outcome: Default::default(),
         }],
         outputs: vec![],
@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 
 use screen_detection::agent::app_context::AppContext;
-use screen_detection::agent::data_generator::DataGenerator;
+use screen_detection::agent::data_generator::{DataGenerator, placeholder_upload_path, upload_files};
 use screen_detection::agent::page_model::{FieldAnalysis, FieldModel, FieldType, FormModel, PageModel};
 use screen_detection::explorer::test_generator::generate_test_plan;
 use screen_detection::explorer::app_map::{AppMap, ExplorerConfig, PageNode};
@@ -56,6 +56,7 @@ fn page_model_with_form(fields: Vec<FieldModel>) -> PageModel {
             purpose: "Test form".to_string(),
             fields,
             submit_label: Some("Submit".to_string()),
+            frame_path: Vec::new(),
             expected_outcome: Default::default(),
         }],
         outputs: vec![],
@@ -372,3 +373,28 @@ fn field_model_input_type_str_select_returns_none() {
     };
     assert_eq!(f.input_type_str(), None);
 }
+
+#[test]
+fn file_fields_get_placeholder_upload() {
+    let f = FieldModel {
+        label: "Profile photo".to_string(),
+        field_type: FieldType::File,
+        required: true,
+        suggested_test_value: String::new(),
+    };
+    assert_eq!(f.input_type_str(), Some("file"));
+    let ctx = AppContext::new();
+    let value = DataGenerator::new(&ctx).generate(&f, None);
+    assert_eq!(value, placeholder_upload_path());
+}
+
+#[test]
+fn upload_files_splits_paths_and_creates_placeholder() {
+    assert_eq!(upload_files(" a.png, b.pdf ,").unwrap(), vec!["a.png", "b.pdf"]);
+    assert!(upload_files("").unwrap().is_empty());
+
+    let placeholder = placeholder_upload_path();
+    let _ = std::fs::remove_file(&placeholder);
+    assert_eq!(upload_files(&placeholder).unwrap(), vec![placeholder.clone()]);
+    assert!(std::path::Path::new(&placeholder).is_file());
+}
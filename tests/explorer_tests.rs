@@ -52,6 +52,10 @@ fn login_screen() -> ScreenState {
         nearby_help_text: None,
         autocomplete: None,
         aria_describedby_text: None,
+        semantic_section: None,
+        aria_live: None,
+        overlay: None,
+        frame_path: Vec::new(),
                 },
                 ScreenElement {
                     label: Some("Password".into()),
@@ -74,6 +78,10 @@ fn login_screen() -> ScreenState {
         nearby_help_text: None,
         autocomplete: None,
         aria_describedby_text: None,
+        semantic_section: None,
+        aria_live: None,
+        overlay: None,
+        frame_path: Vec::new(),
                 },
             ],
             actions: vec![ScreenElement {
@@ -97,6 +105,10 @@ fn login_screen() -> ScreenState {
         nearby_help_text: None,
         autocomplete: None,
         aria_describedby_text: None,
+        semantic_section: None,
+        aria_live: None,
+        overlay: None,
+        frame_path: Vec::new(),
             }],
             primary_action: Some(ScreenElement {
                 label: Some("Sign In".into()),
@@ -119,6 +131,10 @@ fn login_screen() -> ScreenState {
         nearby_help_text: None,
         autocomplete: None,
         aria_describedby_text: None,
+        semantic_section: None,
+        aria_live: None,
+        overlay: None,
+        frame_path: Vec::new(),
             }),
             intent: Some(FormIntent {
                 label: "Authentication".into(),
@@ -128,6 +144,7 @@ fn login_screen() -> ScreenState {
                     IntentSignal::ActionLabel("Sign In".into()),
                 ],
             }),
+            frame_path: Vec::new(),
         }],
         standalone_actions: vec![
             ScreenElement {
@@ -151,6 +168,10 @@ fn login_screen() -> ScreenState {
         nearby_help_text: None,
         autocomplete: None,
         aria_describedby_text: None,
+        semantic_section: None,
+        aria_live: None,
+        overlay: None,
+        frame_path: Vec::new(),
             },
             ScreenElement {
                 label: Some("Forgot Password".into()),
@@ -173,9 +194,14 @@ fn login_screen() -> ScreenState {
         nearby_help_text: None,
         autocomplete: None,
         aria_describedby_text: None,
+        semantic_section: None,
+        aria_live: None,
+        overlay: None,
+        frame_path: Vec::new(),
             },
         ],
         outputs: vec![],
+        tables: vec![],
         identities: HashMap::new(),
         structural_outline: Default::default(),
     }
@@ -208,6 +234,10 @@ fn search_screen() -> ScreenState {
         nearby_help_text: None,
         autocomplete: None,
         aria_describedby_text: None,
+        semantic_section: None,
+        aria_live: None,
+        overlay: None,
+        frame_path: Vec::new(),
             }],
             actions: vec![ScreenElement {
                 label: Some("Search".into()),
@@ -230,6 +260,10 @@ fn search_screen() -> ScreenState {
         nearby_help_text: None,
         autocomplete: None,
         aria_describedby_text: None,
+        semantic_section: None,
+        aria_live: None,
+        overlay: None,
+        frame_path: Vec::new(),
             }],
             primary_action: Some(ScreenElement {
                 label: Some("Search".into()),
@@ -252,15 +286,21 @@ fn search_screen() -> ScreenState {
         nearby_help_text: None,
         autocomplete: None,
         aria_describedby_text: None,
+        semantic_section: None,
+        aria_live: None,
+        overlay: None,
+        frame_path: Vec::new(),
             }),
             intent: Some(FormIntent {
                 label: "Search".into(),
                 confidence: 0.5,
                 signals: vec![],
             }),
+            frame_path: Vec::new(),
         }],
         standalone_actions: vec![],
         outputs: vec![],
+        tables: vec![],
         identities: HashMap::new(),
         structural_outline: Default::default(),
     }
@@ -288,6 +328,7 @@ fn sample_page_model() -> PageModel {
                 },
             ],
             submit_label: Some("Sign In".into()),
+            frame_path: Vec::new(),
             expected_outcome: ExpectedOutcome::default(),
         }],
         outputs: vec![],
@@ -322,6 +363,7 @@ fn search_page_model() -> PageModel {
                 suggested_test_value: "test query".into(),
             }],
             submit_label: Some("Search".into()),
+            frame_path: Vec::new(),
             expected_outcome: ExpectedOutcome::default(),
         }],
         outputs: vec![],
@@ -375,6 +417,7 @@ fn settings_page_model() -> PageModel {
                 suggested_test_value: "Test User".into(),
             }],
             submit_label: Some("Save".into()),
+            frame_path: Vec::new(),
             expected_outcome: ExpectedOutcome::default(),
         }],
         outputs: vec![],
@@ -413,6 +456,7 @@ fn explorer_config_yaml_roundtrip() {
         same_origin_only: false,
         explore_forms: true,
         max_forms_per_page: 5,
+        mocks: Vec::new(),
     };
     let yaml = serde_yaml::to_string(&config).unwrap();
     let parsed: ExplorerConfig = serde_yaml::from_str(&yaml).unwrap();
@@ -466,6 +510,7 @@ fn app_map_add_transition() {
         to_url: "https://example.com/register".into(),
         label: "Sign Up".into(),
         kind: TransitionKind::Link,
+        dialogs: Vec::new(),
     });
 
     assert_eq!(map.transitions.len(), 1);
@@ -498,6 +543,7 @@ fn app_map_json_roundtrip() {
         to_url: "https://example.com/search".into(),
         label: "Go to Search".into(),
         kind: TransitionKind::Link,
+        dialogs: Vec::new(),
     });
 
     let json = serde_json::to_string(&map).unwrap();
@@ -607,6 +653,7 @@ fn form_test_generation() {
             form: form_id,
             values,
             submit_label,
+            ..
         } => {
             assert_eq!(form_id, "login");
             assert_eq!(values.get("Email"), Some(&"user@example.com".to_string()));
@@ -882,6 +929,7 @@ fn transition_with_kind_json_roundtrip() {
             form_id: "search".into(),
             values,
         },
+        dialogs: Vec::new(),
     };
     let json = serde_json::to_string(&transition).unwrap();
     let parsed: Transition = serde_json::from_str(&json).unwrap();
@@ -985,6 +1033,7 @@ fn explorer_config_form_yaml_roundtrip() {
         same_origin_only: true,
         explore_forms: false,
         max_forms_per_page: 1,
+        mocks: Vec::new(),
     };
     let yaml = serde_yaml::to_string(&config).unwrap();
     let parsed: ExplorerConfig = serde_yaml::from_str(&yaml).unwrap();
@@ -1035,6 +1084,7 @@ fn detect_flows_single_form_submission() {
             form_id: "login".into(),
             values,
         },
+        dialogs: Vec::new(),
     });
 
     let flows = detect_flows(&map);
@@ -1079,6 +1129,7 @@ fn detect_flows_chain() {
             form_id: "login".into(),
             values: HashMap::new(),
         },
+        dialogs: Vec::new(),
     });
 
     // Dashboard â†’ Settings (form submit)
@@ -1090,6 +1141,7 @@ fn detect_flows_chain() {
             form_id: "profile".into(),
             values: HashMap::new(),
         },
+        dialogs: Vec::new(),
     });
 
     let flows = detect_flows(&map);
@@ -1124,6 +1176,7 @@ fn detect_flows_link_only_no_flow() {
         to_url: "https://example.com/b".into(),
         label: "Go to B".into(),
         kind: TransitionKind::Link,
+        dialogs: Vec::new(),
     });
 
     let flows = detect_flows(&map);
@@ -1194,6 +1247,7 @@ fn generate_test_plan_includes_flows() {
             form_id: "login".into(),
             values,
         },
+        dialogs: Vec::new(),
     });
 
     let specs = generate_test_plan(&map, None, None);
@@ -1453,6 +1507,7 @@ fn login_form_model() -> FormModel {
             FieldModel { label: "Password".into(), field_type: FieldType::Password, required: true, suggested_test_value: "TestPass123!".into() },
         ],
         submit_label: Some("Sign In".into()),
+        frame_path: Vec::new(),
         expected_outcome: ExpectedOutcome {
             url_not_contains: Some("/login".into()),
             success_text: vec!["welcome".into(), "dashboard".into()],
@@ -1471,6 +1526,7 @@ fn search_form_model() -> FormModel {
             FieldModel { label: "Query".into(), field_type: FieldType::Text, required: false, suggested_test_value: "test query".into() },
         ],
         submit_label: Some("Search".into()),
+        frame_path: Vec::new(),
         expected_outcome: ExpectedOutcome {
             success_text: vec!["results".into()],
             error_indicators: vec!["no results".into()],
@@ -1489,6 +1545,7 @@ fn other_form_model() -> FormModel {
             FieldModel { label: "Message".into(), field_type: FieldType::Text, required: false, suggested_test_value: "Hello".into() },
         ],
         submit_label: Some("Submit".into()),
+        frame_path: Vec::new(),
         expected_outcome: ExpectedOutcome::default(),
     }
 }
@@ -169,6 +169,8 @@ fn test_fill_input_no_crash() {
         tag: Some("input".into()),
         input_type: None,
         form_id: Some("search".into()),
+        frame_path: Vec::new(),
+        shadow_path: Vec::new(),
     };
     session.fill(&selector, "hello world").unwrap();
     // Verify page is still alive
@@ -187,6 +189,8 @@ fn test_fill_and_click_search() {
         tag: Some("input".into()),
         input_type: None,
         form_id: Some("search".into()),
+        frame_path: Vec::new(),
+        shadow_path: Vec::new(),
     };
     session.fill(&input_selector, "test query").unwrap();
     let button_selector = SelectorHint {
@@ -195,6 +199,8 @@ fn test_fill_and_click_search() {
         tag: None,
         input_type: None,
         form_id: None,
+        frame_path: Vec::new(),
+        shadow_path: Vec::new(),
     };
     session.click(&button_selector).unwrap();
 }
@@ -210,6 +216,8 @@ fn test_fill_login_form() {
         tag: Some("input".into()),
         input_type: Some("email".into()),
         form_id: Some("login".into()),
+        frame_path: Vec::new(),
+        shadow_path: Vec::new(),
     };
     session.fill(&email_selector, "user@test.com").unwrap();
     let password_selector = SelectorHint {
@@ -218,6 +226,8 @@ fn test_fill_login_form() {
         tag: Some("input".into()),
         input_type: Some("password".into()),
         form_id: Some("login".into()),
+        frame_path: Vec::new(),
+        shadow_path: Vec::new(),
     };
     session.fill(&password_selector, "secret123").unwrap();
     let login_selector = SelectorHint {
@@ -226,6 +236,8 @@ fn test_fill_login_form() {
         tag: None,
         input_type: None,
         form_id: None,
+        frame_path: Vec::new(),
+        shadow_path: Vec::new(),
     };
     session.click(&login_selector).unwrap();
 }
@@ -253,6 +265,9 @@ fn test_runner_search_page_assertions_pass() {
                 ],
             },
         ],
+        options: None,
+        auth: false,
+        mocks: Vec::new(),
     };
     let mut session = BrowserSession::launch().unwrap();
     let result = TestRunner::run(&spec, &mut session);
@@ -283,6 +298,9 @@ fn test_runner_error_page_element_assertions() {
                 ],
             },
         ],
+        options: None,
+        auth: false,
+        mocks: Vec::new(),
     };
     let mut session = BrowserSession::launch().unwrap();
     let result = TestRunner::run(&spec, &mut session);
@@ -304,9 +322,13 @@ fn test_runner_fill_and_submit_steps_run() {
                 form: "search".into(),
                 values,
                 submit_label: Some("Search".into()),
+                frame_path: Vec::new(),
             },
             TestStep::Wait { duration_ms: 500 },
         ],
+        options: None,
+        auth: false,
+        mocks: Vec::new(),
     };
     let mut session = BrowserSession::launch().unwrap();
     let result = TestRunner::run(&spec, &mut session);
@@ -333,6 +355,8 @@ fn test_click_navigation_link() {
         tag: Some("a".into()),
         input_type: None,
         form_id: None,
+        frame_path: Vec::new(),
+        shadow_path: Vec::new(),
     };
     session.click(&link_selector).unwrap();
     session.wait_idle(1000).unwrap();
@@ -436,6 +460,8 @@ fn test_form_submit_and_snapshot() {
         tag: Some("input".into()),
         input_type: None,
         form_id: Some("search".into()),
+        frame_path: Vec::new(),
+        shadow_path: Vec::new(),
     };
     session.fill(&input_selector, "test query").unwrap();
 
@@ -446,6 +472,8 @@ fn test_form_submit_and_snapshot() {
         tag: None,
         input_type: None,
         form_id: None,
+        frame_path: Vec::new(),
+        shadow_path: Vec::new(),
     };
     session.click(&button_selector).unwrap();
     session.wait_idle(500).unwrap();
@@ -477,6 +505,8 @@ fn test_form_submit_login_and_snapshot() {
         tag: Some("input".into()),
         input_type: Some("email".into()),
         form_id: Some("login".into()),
+        frame_path: Vec::new(),
+        shadow_path: Vec::new(),
     };
     session.fill(&email_selector, "user@test.com").unwrap();
 
@@ -486,6 +516,8 @@ fn test_form_submit_login_and_snapshot() {
         tag: Some("input".into()),
         input_type: Some("password".into()),
         form_id: Some("login".into()),
+        frame_path: Vec::new(),
+        shadow_path: Vec::new(),
     };
     session.fill(&password_selector, "secret123").unwrap();
 
@@ -495,6 +527,8 @@ fn test_form_submit_login_and_snapshot() {
         tag: None,
         input_type: None,
         form_id: None,
+        frame_path: Vec::new(),
+        shadow_path: Vec::new(),
     };
     session.click(&login_selector).unwrap();
     session.wait_idle(500).unwrap();
@@ -519,6 +553,7 @@ fn test_explore_live_with_forms() {
         same_origin_only: false,
         explore_forms: true,
         max_forms_per_page: 3,
+        mocks: Vec::new(),
     };
     let map = explore_live(&config, &mut session, &MockPageAnalyzer, None, None, None).unwrap();
 
@@ -547,6 +582,7 @@ fn test_detect_flows_from_live_exploration() {
         same_origin_only: false,
         explore_forms: true,
         max_forms_per_page: 3,
+        mocks: Vec::new(),
     };
     let map = explore_live(&config, &mut session, &MockPageAnalyzer, None, None, None).unwrap();
 
@@ -641,6 +677,7 @@ fn test_llm_explore_live_with_analyzer() {
         same_origin_only: false,
         explore_forms: true,
         max_forms_per_page: 2,
+        mocks: Vec::new(),
     };
 
     let map = explore_live(&config, &mut session, &analyzer, None, None, None).unwrap();
@@ -46,6 +46,10 @@ fn login_screen() -> ScreenState {
         nearby_help_text: None,
         autocomplete: None,
         aria_describedby_text: None,
+        semantic_section: None,
+        aria_live: None,
+        overlay: None,
+        frame_path: Vec::new(),
                 },
                 ScreenElement {
                     label: Some("Password".into()),
@@ -68,6 +72,10 @@ fn login_screen() -> ScreenState {
         nearby_help_text: None,
         autocomplete: None,
         aria_describedby_text: None,
+        semantic_section: None,
+        aria_live: None,
+        overlay: None,
+        frame_path: Vec::new(),
                 },
             ],
             actions: vec![ScreenElement {
@@ -91,6 +99,10 @@ fn login_screen() -> ScreenState {
         nearby_help_text: None,
         autocomplete: None,
         aria_describedby_text: None,
+        semantic_section: None,
+        aria_live: None,
+        overlay: None,
+        frame_path: Vec::new(),
             }],
             primary_action: Some(ScreenElement {
                 label: Some("Sign In".into()),
@@ -113,6 +125,10 @@ fn login_screen() -> ScreenState {
         nearby_help_text: None,
         autocomplete: None,
         aria_describedby_text: None,
+        semantic_section: None,
+        aria_live: None,
+        overlay: None,
+        frame_path: Vec::new(),
             }),
             intent: Some(FormIntent {
                 label: "Authentication".into(),
@@ -122,6 +138,7 @@ fn login_screen() -> ScreenState {
                     IntentSignal::ActionLabel("Sign In".into()),
                 ],
             }),
+            frame_path: Vec::new(),
         }],
         standalone_actions: vec![
             ScreenElement {
@@ -145,6 +162,10 @@ fn login_screen() -> ScreenState {
         nearby_help_text: None,
         autocomplete: None,
         aria_describedby_text: None,
+        semantic_section: None,
+        aria_live: None,
+        overlay: None,
+        frame_path: Vec::new(),
             },
             ScreenElement {
                 label: Some("Forgot Password".into()),
@@ -167,9 +188,14 @@ fn login_screen() -> ScreenState {
         nearby_help_text: None,
         autocomplete: None,
         aria_describedby_text: None,
+        semantic_section: None,
+        aria_live: None,
+        overlay: None,
+        frame_path: Vec::new(),
             },
         ],
         outputs: vec![],
+        tables: vec![],
         identities: HashMap::new(),
         structural_outline: Default::default(),
     }
@@ -202,6 +228,10 @@ fn search_screen() -> ScreenState {
         nearby_help_text: None,
         autocomplete: None,
         aria_describedby_text: None,
+        semantic_section: None,
+        aria_live: None,
+        overlay: None,
+        frame_path: Vec::new(),
             }],
             actions: vec![ScreenElement {
                 label: Some("Search".into()),
@@ -224,6 +254,10 @@ fn search_screen() -> ScreenState {
         nearby_help_text: None,
         autocomplete: None,
         aria_describedby_text: None,
+        semantic_section: None,
+        aria_live: None,
+        overlay: None,
+        frame_path: Vec::new(),
             }],
             primary_action: Some(ScreenElement {
                 label: Some("Search".into()),
@@ -246,15 +280,21 @@ fn search_screen() -> ScreenState {
         nearby_help_text: None,
         autocomplete: None,
         aria_describedby_text: None,
+        semantic_section: None,
+        aria_live: None,
+        overlay: None,
+        frame_path: Vec::new(),
             }),
             intent: Some(FormIntent {
                 label: "Search".into(),
                 confidence: 0.5,
                 signals: vec![],
             }),
+            frame_path: Vec::new(),
         }],
         standalone_actions: vec![],
         outputs: vec![],
+        tables: vec![],
         identities: HashMap::new(),
         structural_outline: Default::default(),
     }
@@ -267,6 +307,7 @@ fn empty_screen() -> ScreenState {
         forms: vec![],
         standalone_actions: vec![],
         outputs: vec![],
+        tables: vec![],
         identities: HashMap::new(),
         structural_outline: Default::default(),
     }
@@ -294,6 +335,7 @@ fn sample_page_model() -> PageModel {
                 },
             ],
             submit_label: Some("Sign In".into()),
+            frame_path: Vec::new(),
             expected_outcome: ExpectedOutcome::default(),
         }],
         outputs: vec![OutputModel {
@@ -818,7 +860,7 @@ fn field_classification_new_input_types() {
     assert_eq!(classify_field_type(Some("hidden"), None, None), FieldType::Hidden);
     assert_eq!(classify_field_type(Some("color"), None, None), FieldType::Other);
     assert_eq!(classify_field_type(Some("range"), None, None), FieldType::Other);
-    assert_eq!(classify_field_type(Some("file"), None, None), FieldType::Other);
+    assert_eq!(classify_field_type(Some("file"), None, None), FieldType::File);
     assert_eq!(classify_field_type(Some("month"), None, None), FieldType::Other);
     assert_eq!(classify_field_type(Some("week"), None, None), FieldType::Other);
 }
@@ -892,11 +934,17 @@ fn mock_analyzer_extracts_required() {
         nearby_help_text: None,
         autocomplete: None,
         aria_describedby_text: None,
+        semantic_section: None,
+        aria_live: None,
+        overlay: None,
+        frame_path: Vec::new(),
             }],
             actions: vec![],
             primary_action: None,
+            frame_path: Vec::new(),
         }],
         outputs: vec![],
+        tables: vec![],
         standalone_actions: vec![],
         identities: std::collections::HashMap::new(),
         structural_outline: Default::default(),
@@ -941,11 +989,17 @@ fn mock_analyzer_select_uses_first_option() {
         nearby_help_text: None,
         autocomplete: None,
         aria_describedby_text: None,
+        semantic_section: None,
+        aria_live: None,
+        overlay: None,
+        frame_path: Vec::new(),
             }],
             actions: vec![],
             primary_action: None,
+            frame_path: Vec::new(),
         }],
         outputs: vec![],
+        tables: vec![],
         standalone_actions: vec![],
         identities: std::collections::HashMap::new(),
         structural_outline: Default::default(),
@@ -1138,6 +1192,10 @@ fn mock_analyzer_classifies_output_semantics() {
         nearby_help_text: None,
         autocomplete: None,
         aria_describedby_text: None,
+        semantic_section: None,
+        aria_live: None,
+        overlay: None,
+        frame_path: Vec::new(),
             },
             ScreenElement {
                 label: Some("Welcome back!".into()),
@@ -1150,10 +1208,15 @@ fn mock_analyzer_classifies_output_semantics() {
         nearby_help_text: None,
         autocomplete: None,
         aria_describedby_text: None,
+        semantic_section: None,
+        aria_live: None,
+        overlay: None,
+        frame_path: Vec::new(),
             },
         ],
         identities: HashMap::new(),
         structural_outline: Default::default(),
+        tables: vec![],
     };
     let model = MockPageAnalyzer.analyze(&screen).unwrap();
     assert_eq!(model.outputs.len(), 2);
@@ -1183,6 +1246,10 @@ fn mock_analyzer_skips_readonly_inputs() {
         nearby_help_text: None,
         autocomplete: None,
         aria_describedby_text: None,
+        semantic_section: None,
+        aria_live: None,
+        overlay: None,
+        frame_path: Vec::new(),
                 },
                 ScreenElement {
                     label: Some("Email".into()), kind: ElementKind::Input,
@@ -1194,12 +1261,18 @@ fn mock_analyzer_skips_readonly_inputs() {
         nearby_help_text: None,
         autocomplete: None,
         aria_describedby_text: None,
+        semantic_section: None,
+        aria_live: None,
+        overlay: None,
+        frame_path: Vec::new(),
                 },
             ],
             actions: vec![], primary_action: None,
+            frame_path: Vec::new(),
         }],
         standalone_actions: vec![], outputs: vec![], identities: HashMap::new(),
         structural_outline: Default::default(),
+        tables: vec![],
     };
     let model = MockPageAnalyzer.analyze(&screen).unwrap();
     assert_eq!(model.forms[0].fields.len(), 1, "Readonly input should be filtered");
@@ -1224,6 +1297,10 @@ fn mock_analyzer_includes_non_readonly_inputs() {
         nearby_help_text: None,
         autocomplete: None,
         aria_describedby_text: None,
+        semantic_section: None,
+        aria_live: None,
+        overlay: None,
+        frame_path: Vec::new(),
                 },
                 ScreenElement {
                     label: Some("Email".into()), kind: ElementKind::Input,
@@ -1235,12 +1312,18 @@ fn mock_analyzer_includes_non_readonly_inputs() {
         nearby_help_text: None,
         autocomplete: None,
         aria_describedby_text: None,
+        semantic_section: None,
+        aria_live: None,
+        overlay: None,
+        frame_path: Vec::new(),
                 },
             ],
             actions: vec![], primary_action: None,
+            frame_path: Vec::new(),
         }],
         standalone_actions: vec![], outputs: vec![], identities: HashMap::new(),
         structural_outline: Default::default(),
+        tables: vec![],
     };
     let model = MockPageAnalyzer.analyze(&screen).unwrap();
     assert_eq!(model.forms[0].fields.len(), 2, "Both non-readonly inputs should be included");
@@ -1271,7 +1354,13 @@ fn screen_element_carries_constraints() {
         section_heading: None,
         nearby_help_text: None,
         semantic_section: None,
+        aria_live: None,
+        in_dialog: false,
+        live_region: None,
         visible: false,
+        frame_path: Vec::new(),
+        shadow_path: Vec::new(),
+        table: None,
     }];
     let semantics = classify(&elements);
     let form = semantics.forms.iter().find(|f| f.id == "f").unwrap();
@@ -1353,6 +1442,7 @@ fn form_model_with_outcome_serde() {
         purpose: "Authentication".into(),
         fields: vec![],
         submit_label: Some("Sign In".into()),
+        frame_path: Vec::new(),
         expected_outcome: ExpectedOutcome {
             url_not_contains: Some("/login".into()),
             error_indicators: vec!["invalid".into()],
@@ -1391,9 +1481,14 @@ fn mock_analyzer_generates_richer_assertions() {
         nearby_help_text: None,
         autocomplete: None,
         aria_describedby_text: None,
+        semantic_section: None,
+        aria_live: None,
+        overlay: None,
+        frame_path: Vec::new(),
         }],
         identities: std::collections::HashMap::new(),
         structural_outline: Default::default(),
+        tables: vec![],
     };
     let model = MockPageAnalyzer.analyze(&screen).unwrap();
     // Should have a text_absent assertion for the error output
@@ -1534,6 +1629,7 @@ fn llm_page_level_outcome_enriched() {
         forms: vec![],
         standalone_actions: vec![],
         outputs: vec![],
+        tables: vec![],
         identities: HashMap::new(),
         structural_outline: Default::default(),
     };
@@ -1561,6 +1657,7 @@ fn llm_prompt_includes_outcome_request() {
         forms: vec![],
         standalone_actions: vec![],
         outputs: vec![],
+        tables: vec![],
         identities: HashMap::new(),
         structural_outline: Default::default(),
     };
@@ -1641,6 +1738,7 @@ fn mock_page_analyzer_derives_domain_from_title() {
         forms: vec![],
         standalone_actions: vec![],
         outputs: vec![],
+        tables: vec![],
         identities: std::collections::HashMap::new(),
         structural_outline: Default::default(),
     };
@@ -1755,13 +1853,19 @@ fn rich_prompt_contains_fieldset_legend() {
                 nearby_help_text: None,
                 autocomplete: None,
                 aria_describedby_text: None,
+                semantic_section: None,
+                aria_live: None,
+                overlay: None,
+                frame_path: Vec::new(),
             }],
             actions: vec![],
             primary_action: None,
             intent: None,
+            frame_path: Vec::new(),
         }],
         standalone_actions: vec![],
         outputs: vec![],
+        tables: vec![],
         identities: HashMap::new(),
         structural_outline: Default::default(),
     };
@@ -1800,13 +1904,19 @@ fn rich_prompt_contains_nearby_help_text() {
                 nearby_help_text: Some("Enter at least 10 characters".to_string()),
                 autocomplete: None,
                 aria_describedby_text: None,
+                semantic_section: None,
+                aria_live: None,
+                overlay: None,
+                frame_path: Vec::new(),
             }],
             actions: vec![],
             primary_action: None,
             intent: None,
+            frame_path: Vec::new(),
         }],
         standalone_actions: vec![],
         outputs: vec![],
+        tables: vec![],
         identities: HashMap::new(),
         structural_outline: Default::default(),
     };
@@ -1843,13 +1953,19 @@ fn rich_prompt_contains_aria_describedby() {
                 nearby_help_text: None,
                 autocomplete: None,
                 aria_describedby_text: Some("Must be at least 8 characters with one uppercase letter".to_string()),
+                semantic_section: None,
+                aria_live: None,
+                overlay: None,
+                frame_path: Vec::new(),
             }],
             actions: vec![],
             primary_action: None,
             intent: None,
+            frame_path: Vec::new(),
         }],
         standalone_actions: vec![],
         outputs: vec![],
+        tables: vec![],
         identities: HashMap::new(),
         structural_outline: Default::default(),
     };
@@ -1876,6 +1992,7 @@ fn rich_prompt_contains_structural_outline() {
                 LandmarkEntry { tag: "main".to_string(), label: "Registration form".to_string() },
             ],
         },
+        tables: vec![],
     };
     let prompt = build_rich_page_prompt(&screen, None);
     assert!(prompt.contains("H1: Patient Registration"), "prompt should contain H1 heading");
@@ -1909,6 +2026,10 @@ fn rich_prompt_contains_all_outputs_not_just_3() {
             nearby_help_text: None,
             autocomplete: None,
             aria_describedby_text: None,
+            semantic_section: None,
+            aria_live: None,
+            overlay: None,
+            frame_path: Vec::new(),
         })
         .collect();
     let screen = ScreenState {
@@ -1919,6 +2040,7 @@ fn rich_prompt_contains_all_outputs_not_just_3() {
         outputs,
         identities: HashMap::new(),
         structural_outline: Default::default(),
+        tables: vec![],
     };
     let prompt = build_rich_page_prompt(&screen, None);
     // All 5 outputs should be present (old prompt would have stopped at 3)
@@ -1,3 +1,5 @@
+use screen_detection::browser::candidates::ElementCandidate;
+use screen_detection::browser::console::{ConsoleEntry, ConsoleLevel};
 use screen_detection::report::console::format_console_report;
 use screen_detection::report::html::generate_html_report;
 use screen_detection::report::junit::generate_junit_xml;
@@ -26,6 +28,11 @@ fn passing_result(name: &str) -> TestResult {
         duration_ms: None,
         screenshots: vec![],
         retry_attempts: 0,
+        har_path: None,
+        trace_path: None,
+        video_path: None,
+        console: Vec::new(),
+        suggestions: Vec::new(),
     }
 }
 
@@ -58,6 +65,11 @@ fn failing_result(name: &str) -> TestResult {
         duration_ms: None,
         screenshots: vec![],
         retry_attempts: 0,
+        har_path: None,
+        trace_path: None,
+        video_path: None,
+        console: Vec::new(),
+        suggestions: Vec::new(),
     }
 }
 
@@ -236,6 +248,84 @@ fn html_report_fail_color() {
     assert!(html.contains("SOME TESTS FAILED"));
 }
 
+#[test]
+fn html_report_links_har_file() {
+    let mut result = failing_result("Checkout");
+    result.har_path = Some("screenshots/checkout_network_1.har".into());
+    let report = TestSuiteReport::from_results("Suite", vec![result, passing_result("Home")]);
+    let html = generate_html_report(&report);
+    assert!(html.contains("<a href=\"screenshots/checkout_network_1.har\">"));
+    assert_eq!(html.matches("Network log:").count(), 1);
+}
+
+fn with_console(mut result: TestResult) -> TestResult {
+    result.console = vec![
+        ConsoleEntry {
+            level: ConsoleLevel::Log,
+            text: "loaded <app>".into(),
+            location: None,
+            stack: None,
+            step: None,
+        },
+        ConsoleEntry {
+            level: ConsoleLevel::PageError,
+            text: "x is undefined".into(),
+            location: Some("app.js:10:5".into()),
+            stack: Some("at render (app.js:10:5)".into()),
+            step: Some(1),
+        },
+    ];
+    result
+}
+
+#[test]
+fn html_report_shows_console_messages() {
+    let report = TestSuiteReport::from_results("Suite", vec![with_console(failing_result("Checkout"))]);
+    let html = generate_html_report(&report);
+    assert!(html.contains("<details class=\"console\" open>"));
+    assert!(html.contains("Console: 2 message(s), 1 error(s)"));
+    assert!(html.contains("loaded &lt;app&gt;"));
+    assert!(html.contains("<li class=\"console-error\">[step 1] uncaught exception: x is undefined (app.js:10:5)</li>"));
+}
+
+fn with_missing_element(mut result: TestResult) -> TestResult {
+    result.error = Some("Step 0 failed: Element 'Sign in' not found: click on '/login' failed".into());
+    result.suggestions = vec![ElementCandidate {
+        role: Some("button".into()),
+        name: "Sign-in <now>".into(),
+        tag: "button".into(),
+        form_id: Some("login".into()),
+        score: 0.8,
+    }];
+    result
+}
+
+#[test]
+fn reports_show_did_you_mean_hint_for_missing_element() {
+    let report = TestSuiteReport::from_results("Suite", vec![with_missing_element(failing_result("Login"))]);
+    let console = format_console_report(&report);
+    assert!(console.contains("    [HINT] did you mean 'Sign-in <now>'?\n"));
+
+    let html = generate_html_report(&report);
+    assert!(html.contains("<p class=\"hint\">Hint: did you mean &#39;Sign-in &lt;now&gt;&#39;?</p>"));
+
+    let plain = TestSuiteReport::from_results("Suite", vec![failing_result("Login")]);
+    assert!(!format_console_report(&plain).contains("[HINT]"));
+}
+
+#[test]
+fn html_report_links_trace_and_video() {
+    let mut result = failing_result("Checkout");
+    result.trace_path = Some("screenshots/checkout_trace_1.zip".into());
+    result.video_path = Some("screenshots/checkout_video_1.webm".into());
+    let report = TestSuiteReport::from_results("Suite", vec![result, passing_result("Home")]);
+    let html = generate_html_report(&report);
+    assert!(html.contains("<a href=\"screenshots/checkout_trace_1.zip\">"));
+    assert!(html.contains("<a href=\"screenshots/checkout_video_1.webm\">"));
+    assert_eq!(html.matches("Trace:").count(), 1);
+    assert_eq!(html.matches("Video:").count(), 1);
+}
+
 // ============================================================================
 // 14. JUnit XML — structure
 // ============================================================================
@@ -263,3 +353,16 @@ fn junit_xml_failure_element() {
     assert!(xml.contains("assertion(s) failed"));
     assert!(xml.contains("Broken Test"));
 }
+
+#[test]
+fn junit_xml_console_output() {
+    let report = TestSuiteReport::from_results(
+        "Suite",
+        vec![with_console(passing_result("Home")), passing_result("Plain")],
+    );
+    let xml = generate_junit_xml(&report);
+    assert!(xml.contains("<system-out>[start] log: loaded &lt;app&gt;</system-out>"));
+    assert!(xml.contains("<system-err>[step 1] uncaught exception: x is undefined (app.js:10:5)\nat render (app.js:10:5)</system-err>"));
+    assert!(!xml.contains("<failure"));
+    assert!(xml.contains("<testcase name=\"Plain\" classname=\"screen-detection\" />"));
+}
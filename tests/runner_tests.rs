@@ -3,7 +3,7 @@ use screen_detection::report::console::format_console_report;
 use screen_detection::report::html::generate_html_report;
 use screen_detection::report::junit::generate_junit_xml;
 use screen_detection::report::report_model::TestSuiteReport;
-use screen_detection::spec::runner_config::RunnerConfig;
+use screen_detection::spec::runner_config::{ArtifactMode, RunnerConfig};
 use screen_detection::spec::spec_model::{AssertionResult, AssertionSpec, TestResult};
 
 // ============================================================================
@@ -26,6 +26,11 @@ fn make_result(duration_ms: Option<u128>, screenshots: Vec<String>, retry_attemp
         duration_ms,
         screenshots,
         retry_attempts,
+        har_path: None,
+        trace_path: None,
+        video_path: None,
+        console: Vec::new(),
+        suggestions: Vec::new(),
     }
 }
 
@@ -45,6 +50,11 @@ fn failing_result_with_duration(duration_ms: Option<u128>) -> TestResult {
         duration_ms,
         screenshots: vec![],
         retry_attempts: 0,
+        har_path: None,
+        trace_path: None,
+        video_path: None,
+        console: Vec::new(),
+        suggestions: Vec::new(),
     }
 }
 
@@ -76,6 +86,11 @@ fn runner_config_yaml_roundtrip() {
         retry_delay_ms: 1000,
         screenshot_on_failure: false,
         screenshot_dir: "ci-screenshots".to_string(),
+        storage_state: None,
+        record_har: false,
+        record_trace: ArtifactMode::OnFailure,
+        record_video: ArtifactMode::Off,
+        fail_on_page_error: false,
     };
 
     let yaml = serde_yaml::to_string(&config).expect("Failed to serialize RunnerConfig to YAML");
@@ -86,6 +101,24 @@ fn runner_config_yaml_roundtrip() {
     assert_eq!(restored.retry_delay_ms, 1000);
     assert!(!restored.screenshot_on_failure);
     assert_eq!(restored.screenshot_dir, "ci-screenshots");
+    assert_eq!(restored.record_trace, ArtifactMode::OnFailure);
+    assert_eq!(restored.record_video, ArtifactMode::Off);
+}
+
+#[test]
+fn artifact_mode_keeps_by_outcome() {
+    let modes: RunConfig = serde_yaml::from_str("record_trace: on_retry\nrecord_video: always\n").unwrap();
+    assert_eq!(modes.record_trace, ArtifactMode::OnRetry);
+    assert_eq!(modes.record_video, ArtifactMode::Always);
+    assert_eq!(RunConfig::default().record_trace, ArtifactMode::Off);
+
+    // (passed, retry_attempts)
+    let outcomes = [(true, 0), (false, 0), (true, 2)];
+    let kept = |mode: ArtifactMode| outcomes.map(|(passed, retries)| mode.keeps(passed, retries));
+    assert_eq!(kept(ArtifactMode::Off), [false, false, false]);
+    assert_eq!(kept(ArtifactMode::Always), [true, true, true]);
+    assert_eq!(kept(ArtifactMode::OnFailure), [false, true, false]);
+    assert_eq!(kept(ArtifactMode::OnRetry), [false, false, true]);
 }
 
 // ============================================================================
@@ -373,6 +406,11 @@ fn run_config_yaml_roundtrip() {
         retry_delay_ms: 1000,
         screenshot_on_failure: false,
         screenshot_dir: "ci/screenshots".to_string(),
+        jobs: 4,
+        record_har: true,
+        record_trace: ArtifactMode::Always,
+        record_video: ArtifactMode::OnFailure,
+        fail_on_page_error: true,
     };
 
     let yaml = serde_yaml::to_string(&config).expect("Failed to serialize RunConfig");
@@ -383,6 +421,17 @@ fn run_config_yaml_roundtrip() {
     assert_eq!(restored.output, Some("report.html".to_string()));
     assert_eq!(restored.max_retries, 3);
     assert_eq!(restored.retry_delay_ms, 1000);
+    assert_eq!(restored.jobs, 4);
+    assert!(restored.record_har);
+    assert!(restored.fail_on_page_error);
+
+    let runner = restored.runner_config();
+    assert_eq!(runner.max_assertion_retries, 3);
+    assert_eq!(runner.screenshot_dir, "ci/screenshots");
+    assert!(runner.record_har);
+    assert!(runner.fail_on_page_error);
+    assert_eq!(runner.record_trace, ArtifactMode::Always);
+    assert_eq!(runner.record_video, ArtifactMode::OnFailure);
     assert!(!restored.screenshot_on_failure);
     assert_eq!(restored.screenshot_dir, "ci/screenshots");
 }
@@ -409,6 +458,11 @@ fn url_not_contains_in_report() {
         duration_ms: None,
         screenshots: vec![],
         retry_attempts: 0,
+        har_path: None,
+        trace_path: None,
+        video_path: None,
+        console: Vec::new(),
+        suggestions: Vec::new(),
     };
 
     let report = single_result_report(failed_result);
@@ -0,0 +1,592 @@
+//! BrowserSession timeout and crash-recovery tests against a fake `sh` server.
+#![cfg(unix)]
+
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use screen_detection::agent::error::AgentError;
+use screen_detection::browser::dialogs::DialogPolicy;
+use screen_detection::browser::doctor::run_checks;
+use screen_detection::browser::options::{SessionOptions, Viewport};
+use screen_detection::browser::pipeline::{EventKind, ServerEvent};
+use screen_detection::browser::playwright::SelectorHint;
+use screen_detection::browser::routes::RouteMock;
+use screen_detection::browser::scripts::{
+    EXTRACT_SCRIPT, INTERACT_SCRIPT, SERVER_SCRIPT, resolve_script, unpack_scripts,
+};
+use screen_detection::browser::session::{
+    BrowserRequest, BrowserSession, LEGACY_PROTOCOL_VERSION, PROTOCOL_VERSION, SessionConfig,
+};
+use screen_detection::cli::config::AppConfig;
+
+// =========================================================================
+// Helpers
+// =========================================================================
+
+/// Fake browser_server.js (protocol 1): answers every command with `ok`,
+/// hangs on any request mentioning "hang", exits on "crash", answers `tabs`
+/// with a stale response before the real one, rejects `record_start` as
+/// unsupported, fails actions on "Sign in", "Cart" or "Later" (extract
+/// serves a login page with a "Sign-in" button, a "Cart" link and a
+/// disabled "Later" button), answers queries for `#slow` half a second late and out of
+/// order, pushes three events before answering `subscribe`, and appends its
+/// launch arguments plus each navigate, configure, load_storage_state,
+/// route, dialog_policy, subscribe, cancel, trace_start and trace_stop
+/// request to `log`.
+const FAKE_SERVER: &str = r##"
+[ $# -gt 0 ] && echo "args $*" >> "$LOG"
+reply() { id=${line#*'"id":'}; id=${id%%,*}; echo "{\"id\":$id,$1}"; }
+echo '{"ok":true,"ready":true,"protocol":1}'
+while IFS= read -r line; do
+  case "$line" in
+    *hang*) sleep 5 ;;
+    *crash*) exit 1 ;;
+    *'"cmd":"quit"'*) echo '{"ok":true}'; exit 0 ;;
+    *'"cmd":"tabs"'*) echo '{"id":0,"ok":true,"data":[]}'
+      reply '"ok":true,"data":[{"index":0,"url":"https://app.test/"}]' ;;
+    *'"selector":"#slow"'*) (sleep 0.5; reply '"ok":true,"text":"slow"') & ;;
+    *'"selector":"#fast"'*) reply '"ok":true,"text":"fast"' ;;
+    *'"cmd":"cancel"'*) echo "$line" >> "$LOG"; reply '"ok":true' ;;
+    *'"cmd":"subscribe"'*) echo "$line" >> "$LOG"
+      echo '{"event":"console","data":{"level":"error","text":"boom"}}'
+      echo '{"event":"navigation","data":{"url":"https://app.test/next","tab":0}}'
+      echo '{"event":"heartbeat"}'
+      echo '{"ok":true}' ;;
+    *'"cmd":"record_start"'*) echo '{"ok":false,"code":"unsupported_command","error":"unknown cmd record_start"}' ;;
+    *'"name":"Sign in"'*|*'"name":"Cart"'*|*'"name":"Later"'*) echo '{"ok":false,"error":"locator.click: Timeout 300ms exceeded"}' ;;
+    *'"cmd":"extract"'*) echo '{"ok":true,"data":{"url":"https://app.test/login","dom":[{"tag":"button","text":"Sign-in","formId":"login"},{"tag":"a","text":"Cart","role":"link"},{"tag":"a","text":"Help","role":"link"},{"tag":"button","text":"Later","disabled":true}]}}' ;;
+    *'"cmd":"navigate"'*|*'"cmd":"configure"'*|*'"cmd":"load_storage_state"'*|*'"cmd":"route"'*|*'"cmd":"dialog_policy"'*|*'"cmd":"trace_st'*) echo "$line" >> "$LOG"; echo '{"ok":true}' ;;
+    *) echo '{"ok":true,"count":1}' ;;
+  esac
+done
+"##;
+
+/// A path in a per-test temp directory; the directory is removed on drop.
+struct TempPath {
+    dir: PathBuf,
+    path: PathBuf,
+}
+
+impl TempPath {
+    fn new(prefix: &str, name: &str, file: &str) -> Self {
+        let dir = std::env::temp_dir().join(format!("{}_{}_{}", prefix, name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(file);
+        TempPath { dir, path }
+    }
+}
+
+impl Deref for TempPath {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for TempPath {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.dir);
+    }
+}
+
+fn fake_server(name: &str) -> (SessionConfig, TempPath) {
+    fake_server_with_ready(name, r#"{"ok":true,"ready":true,"protocol":1}"#)
+}
+
+/// `fake_server` announcing `ready` instead of protocol 1 with no capabilities.
+///
+/// The returned log path keeps the server's temp directory alive.
+fn fake_server_with_ready(name: &str, ready: &str) -> (SessionConfig, TempPath) {
+    let log = TempPath::new("sd_session", name, "requests.log");
+    let script = log.dir.join("server.sh");
+    let body = FAKE_SERVER
+        .replace("\"$LOG\"", &format!("'{}'", log.display()))
+        .replacen(r#"{"ok":true,"ready":true,"protocol":1}"#, ready, 1);
+    std::fs::write(&script, body).unwrap();
+
+    let config = SessionConfig {
+        node_path: "sh".into(),
+        server_script: script.to_string_lossy().to_string(),
+        command_timeout_ms: 300,
+        navigate_timeout_ms: 300,
+        launch_timeout_ms: 5_000,
+        ..SessionConfig::default()
+    };
+    (config, log)
+}
+
+fn logged(log: &Path) -> Vec<String> {
+    std::fs::read_to_string(log)
+        .unwrap_or_default()
+        .lines()
+        .map(String::from)
+        .collect()
+}
+
+// =========================================================================
+// Configuration
+// =========================================================================
+
+#[test]
+fn session_config_defaults() {
+    let config = SessionConfig::default();
+    assert_eq!(config.command_timeout_ms, 30_000);
+    assert_eq!(config.navigate_timeout_ms, 60_000);
+    assert_eq!(config.max_restarts, 3);
+    assert_eq!(config.node_path, "node");
+    assert_eq!(config.server_script, "browser_server.js");
+    assert!(config.scripts_dir.is_none());
+    assert!(config.restore_storage_state.is_none());
+}
+
+#[test]
+fn app_config_session_section_from_yaml() {
+    let yaml = "session:\n  command_timeout_ms: 5000\n  max_restarts: 1\n  restore_storage_state: auth.json\n  scripts_dir: /opt/sd/scripts\n  options:\n    device: Pixel 7\n    headed: true\n";
+    let config: AppConfig = serde_yaml::from_str(yaml).unwrap();
+    assert_eq!(config.session.command_timeout_ms, 5000);
+    assert_eq!(config.session.max_restarts, 1);
+    assert_eq!(config.session.navigate_timeout_ms, 60_000);
+    assert_eq!(config.session.restore_storage_state.as_deref(), Some("auth.json"));
+    assert_eq!(config.session.scripts_dir.as_deref(), Some("/opt/sd/scripts"));
+    assert_eq!(config.session.options.device.as_deref(), Some("Pixel 7"));
+    assert_eq!(config.session.options.headed, Some(true));
+}
+
+#[test]
+fn timeout_error_display() {
+    let err = AgentError::Timeout { command: "navigate".into(), timeout_ms: 1500 };
+    assert_eq!(err.to_string(), "Timed out after 1500ms waiting for 'navigate'");
+}
+
+#[test]
+fn browser_request_storage_state_serializes_correctly() {
+    let json = serde_json::to_value(BrowserRequest::load_storage_state("auth.json")).unwrap();
+    assert_eq!(json, serde_json::json!({"cmd": "load_storage_state", "path": "auth.json"}));
+    let json = serde_json::to_value(BrowserRequest::save_storage_state("auth.json")).unwrap();
+    assert_eq!(json, serde_json::json!({"cmd": "save_storage_state", "path": "auth.json"}));
+}
+
+#[test]
+fn set_input_files_rejects_missing_files_before_sending() {
+    let (config, _log) = fake_server("upload");
+    let mut session = BrowserSession::launch_with(config).unwrap();
+    let avatar = SelectorHint {
+        role: None,
+        name: Some("Avatar".into()),
+        tag: Some("input".into()),
+        input_type: Some("file".into()),
+        form_id: None,
+        frame_path: Vec::new(),
+        shadow_path: Vec::new(),
+    };
+    let err = session.set_input_files(&avatar, &["no/such/avatar.png".into()]).unwrap_err();
+    assert!(err.to_string().contains("no/such/avatar.png"));
+    assert!(session.is_alive());
+    session.set_input_files(&avatar, &[]).unwrap();
+}
+
+// =========================================================================
+// Script location and doctor
+// =========================================================================
+
+/// Temp directory of stub scripts, removed when the result is dropped.
+fn scripts_dir(name: &str) -> TempPath {
+    let dir = TempPath::new("sd_scripts", name, "");
+    for script in [SERVER_SCRIPT, EXTRACT_SCRIPT, INTERACT_SCRIPT] {
+        std::fs::write(dir.join(script), "// stub").unwrap();
+    }
+    dir
+}
+
+#[test]
+fn resolve_script_prefers_configured_dir_and_keeps_explicit_paths() {
+    let dir = scripts_dir("resolve");
+    let dir_str = dir.to_string_lossy().to_string();
+    assert_eq!(resolve_script(SERVER_SCRIPT, Some(&dir_str)).unwrap(), dir.join(SERVER_SCRIPT));
+    assert_eq!(resolve_script("./custom/server.js", Some(&dir_str)).unwrap(), PathBuf::from("./custom/server.js"));
+
+    // A configured directory is authoritative
+    let err = resolve_script("missing.js", Some(&dir_str)).unwrap_err();
+    assert!(matches!(err, AgentError::ScriptNotFound { ref script, ref searched } if script == "missing.js" && searched == &vec![dir_str.clone()]));
+    assert!(err.to_string().contains("SCREEN_DETECTION_SCRIPTS_DIR"));
+}
+
+#[test]
+fn unpack_scripts_writes_only_changed_files() {
+    let dir = TempPath::new("sd_unpack", "scripts", "");
+    unpack_scripts(&[("extract.js", "v1"), ("package.json", "{}")], &dir).unwrap();
+    assert_eq!(std::fs::read_to_string(dir.join("extract.js")).unwrap(), "v1");
+
+    unpack_scripts(&[("extract.js", "v2")], &dir).unwrap();
+    assert_eq!(std::fs::read_to_string(dir.join("extract.js")).unwrap(), "v2");
+    assert_eq!(std::fs::read_to_string(dir.join("package.json")).unwrap(), "{}");
+}
+
+#[test]
+fn launch_reports_missing_server_script() {
+    let dir = TempPath::new("sd_noscripts", "launch", "");
+    let config = SessionConfig {
+        scripts_dir: Some(dir.to_string_lossy().to_string()),
+        ..SessionConfig::default()
+    };
+    let err = BrowserSession::launch_with(config).err().expect("launch must fail");
+    assert!(matches!(err, AgentError::ScriptNotFound { ref script, .. } if script == SERVER_SCRIPT));
+}
+
+#[test]
+fn doctor_reports_missing_node_with_fix_and_skips_dependent_checks() {
+    let dir = scripts_dir("doctor");
+    let config = SessionConfig {
+        node_path: "/definitely/not/node".into(),
+        scripts_dir: Some(dir.to_string_lossy().to_string()),
+        ..SessionConfig::default()
+    };
+    let checks = run_checks(&config);
+    let names: Vec<_> = checks.iter().map(|c| c.name.as_str()).collect();
+    assert_eq!(names, vec!["Node.js", SERVER_SCRIPT, EXTRACT_SCRIPT, INTERACT_SCRIPT, "Playwright", "Chromium"]);
+
+    assert!(!checks[0].ok);
+    assert!(checks[0].fix.as_deref().unwrap().contains("session.node_path"));
+    assert!(checks[1..4].iter().all(|c| c.ok), "{:?}", checks);
+    assert!(checks[4..].iter().all(|c| !c.ok && c.detail.starts_with("skipped")));
+}
+
+// =========================================================================
+// Timeouts and crash recovery
+// =========================================================================
+
+#[test]
+fn hung_command_times_out_and_session_recovers() {
+    let (config, log) = fake_server("hang");
+    let mut session = BrowserSession::launch_with(config).unwrap();
+    session.navigate("https://app.test/home").unwrap();
+
+    let err = session.query_count("#hang").unwrap_err();
+    assert!(matches!(err, AgentError::Timeout { ref command, timeout_ms: 300 } if command == "query_count"));
+    assert!(!session.is_alive());
+
+    // Next command respawns the server and returns to the last good URL
+    assert_eq!(session.query_count("li").unwrap(), 1);
+    assert_eq!(session.restart_count(), 1);
+    let navigations = logged(&log);
+    assert_eq!(navigations.len(), 2);
+    assert!(navigations.iter().all(|l| l.contains("https://app.test/home")));
+}
+
+#[test]
+fn crashed_server_restores_storage_state_then_url() {
+    let (mut config, log) = fake_server("restore");
+    let state = log.with_file_name("state.json");
+    std::fs::write(&state, "{}").unwrap();
+    config.restore_storage_state = Some(state.to_string_lossy().to_string());
+
+    let mut session = BrowserSession::launch_with(config).unwrap();
+    session.navigate("https://app.test/orders").unwrap();
+    assert!(matches!(session.query_text("#crash").unwrap_err(), AgentError::SessionIO(_)));
+
+    session.wait_idle(1).unwrap();
+    let requests = logged(&log);
+    assert_eq!(requests.len(), 3);
+    assert!(requests[1].contains("load_storage_state"));
+    assert!(requests[2].contains("https://app.test/orders"));
+}
+
+#[test]
+fn navigate_after_crash_skips_url_restore() {
+    let (config, log) = fake_server("renav");
+    let mut session = BrowserSession::launch_with(config).unwrap();
+    session.navigate("https://app.test/a").unwrap();
+    assert!(session.query_visible("#crash").is_err());
+
+    session.navigate("https://app.test/b").unwrap();
+    let requests = logged(&log);
+    assert_eq!(requests.len(), 2);
+    assert!(requests[1].contains("https://app.test/b"));
+    assert_eq!(session.last_url(), Some("https://app.test/b"));
+}
+
+#[test]
+fn restart_limit_is_enforced() {
+    let (mut config, _log) = fake_server("limit");
+    config.max_restarts = 0;
+    let mut session = BrowserSession::launch_with(config).unwrap();
+    assert!(session.query_text("#crash").is_err());
+    let err = session.query_count("li").unwrap_err();
+    assert!(err.to_string().contains("restart limit"));
+    assert_eq!(session.restart_count(), 0);
+}
+
+// =========================================================================
+// Context options
+// =========================================================================
+
+#[test]
+fn launch_passes_options_and_configure_merges_overrides() {
+    let (mut config, log) = fake_server("options");
+    config.options.locale = Some("de-DE".into());
+    let mut session = BrowserSession::launch_with(config).unwrap();
+
+    let overrides = SessionOptions {
+        viewport: Some(Viewport { width: 390, height: 844 }),
+        ..SessionOptions::default()
+    };
+    session.configure(&overrides).unwrap();
+    session.configure(&overrides).unwrap(); // unchanged: not resent
+    assert_eq!(session.options().locale.as_deref(), Some("de-DE"));
+    assert_eq!(session.options().viewport, Some(Viewport { width: 390, height: 844 }));
+
+    session.configure(&SessionOptions::default()).unwrap();
+    assert!(session.options().viewport.is_none());
+
+    let requests = logged(&log);
+    assert_eq!(requests.len(), 3);
+    assert!(requests[0].starts_with("args --options"));
+    assert!(requests[0].contains(r#""locale":"de-DE""#));
+    assert!(requests[1].contains(r#""width":390"#) && requests[1].contains("de-DE"));
+    assert!(!requests[2].contains("width"));
+}
+
+#[test]
+fn respawn_reapplies_active_overrides() {
+    let (config, log) = fake_server("reconf");
+    let mut session = BrowserSession::launch_with(config).unwrap();
+    let french = SessionOptions { locale: Some("fr-FR".into()), ..SessionOptions::default() };
+    session.configure(&french).unwrap();
+    session.navigate("https://app.test/a").unwrap();
+    assert!(session.query_text("#crash").is_err());
+
+    session.query_count("li").unwrap();
+    let requests = logged(&log);
+    assert_eq!(requests.len(), 4);
+    assert!(requests[2].contains(r#""cmd":"configure""#) && requests[2].contains("fr-FR"));
+    assert!(requests[3].contains("https://app.test/a"));
+}
+
+// =========================================================================
+// Route mocks
+// =========================================================================
+
+#[test]
+fn routes_are_replaced_removed_and_reinstalled_after_respawn() {
+    let (config, log) = fake_server("routes");
+    let mut session = BrowserSession::launch_with(config).unwrap();
+    session.route(&RouteMock::status("**/api/orders", 500)).unwrap();
+    session.route(&RouteMock::status("**/api/orders", 503)).unwrap(); // replaces the first
+    session.route(&RouteMock::status("**/api/stats", 200)).unwrap();
+    session.unroute(Some("**/api/stats")).unwrap();
+    session.navigate("https://app.test/orders").unwrap();
+    assert!(session.query_text("#crash").is_err());
+
+    session.query_count("li").unwrap();
+    let requests = logged(&log);
+    assert_eq!(requests.len(), 6);
+    assert!(requests[4].contains(r#""cmd":"route""#) && requests[4].contains(r#""status":503"#));
+    assert!(requests[5].contains("https://app.test/orders"));
+}
+
+#[test]
+fn dialog_policy_is_reapplied_after_respawn() {
+    let (config, log) = fake_server("dialogs");
+    let mut session = BrowserSession::launch_with(config).unwrap();
+    assert_eq!(session.dialog_policy(), &DialogPolicy::Dismiss);
+    session.set_dialog_policy(&DialogPolicy::Respond { text: "Ada".into() }).unwrap();
+    session.navigate("https://app.test/profile").unwrap();
+    assert!(session.query_text("#crash").is_err());
+
+    session.query_count("li").unwrap();
+    let requests = logged(&log);
+    assert_eq!(requests.len(), 4);
+    assert!(requests[2].contains(r#""cmd":"dialog_policy","policy":"respond","text":"Ada""#));
+    assert!(requests[3].contains("https://app.test/profile"));
+}
+
+#[test]
+fn trace_is_restarted_after_respawn_until_stopped() {
+    let (config, log) = fake_server("trace");
+    let mut session = BrowserSession::launch_with(config).unwrap();
+    session.start_trace().unwrap();
+    assert!(session.query_text("#crash").is_err());
+    session.query_count("li").unwrap();
+    session.stop_trace(Some("out/checkout_trace.zip")).unwrap();
+    assert!(session.query_text("#crash").is_err());
+    session.query_count("li").unwrap();
+
+    let requests = logged(&log);
+    assert_eq!(requests.len(), 3);
+    assert!(requests[0].contains(r#""cmd":"trace_start""#));
+    assert!(requests[1].contains(r#""cmd":"trace_start""#));
+    assert!(requests[2].contains(r#""cmd":"trace_stop","path":"out/checkout_trace.zip""#));
+}
+
+// =========================================================================
+// Element diagnostics
+// =========================================================================
+
+fn named(name: &str) -> SelectorHint {
+    SelectorHint {
+        role: None,
+        name: Some(name.into()),
+        tag: None,
+        input_type: None,
+        form_id: None,
+        frame_path: Vec::new(),
+        shadow_path: Vec::new(),
+    }
+}
+
+#[test]
+fn failed_action_on_missing_element_lists_nearest_candidates() {
+    let (config, _log) = fake_server("diagnostics");
+    let mut session = BrowserSession::launch_with(config).unwrap();
+
+    match session.click(&named("Sign in")) {
+        Err(AgentError::ElementNotFound { element, context, candidates }) => {
+            assert_eq!(element, "Sign in");
+            assert!(context.contains("https://app.test/login"), "{}", context);
+            assert!(context.contains("Timeout 300ms exceeded"), "{}", context);
+            assert_eq!(candidates[0].name, "Sign-in");
+            assert_eq!(candidates[0].form_id.as_deref(), Some("login"));
+            assert!(candidates.iter().all(|c| c.name != "Help"));
+        }
+        other => panic!("expected ElementNotFound, got {:?}", other),
+    }
+
+    // The element is on the page, so the action failed for another reason
+    match session.click(&named("Cart")) {
+        Err(AgentError::SessionProtocol { error, .. }) => assert!(error.contains("Timeout")),
+        other => panic!("expected SessionProtocol, got {:?}", other),
+    }
+
+    // The element is there but disabled
+    match session.click(&named("Later")) {
+        Err(AgentError::ElementDisabled { element, .. }) => assert_eq!(element, "Later"),
+        other => panic!("expected ElementDisabled, got {:?}", other),
+    }
+}
+
+// =========================================================================
+// Protocol handshake
+// =========================================================================
+
+#[test]
+fn launch_rejects_servers_speaking_another_protocol() {
+    let (config, _log) = fake_server_with_ready("newer", r#"{"ok":true,"ready":true,"protocol":2}"#);
+    match BrowserSession::launch_with(config) {
+        Err(err @ AgentError::ProtocolMismatch { expected: PROTOCOL_VERSION, found: 2 }) => {
+            assert!(err.to_string().contains("protocol 2"), "{}", err);
+        }
+        other => panic!("expected ProtocolMismatch, got {:?}", other.err()),
+    }
+}
+
+#[test]
+fn unversioned_servers_launch_with_legacy_commands_only() {
+    let (config, log) = fake_server_with_ready("legacy", r#"{"ok":true,"ready":true}"#);
+    let mut session = BrowserSession::launch_with(config).unwrap();
+    assert_eq!(session.handshake().unwrap().protocol, LEGACY_PROTOCOL_VERSION);
+
+    let err = session.tabs().unwrap_err();
+    assert!(matches!(err, AgentError::UnsupportedCommand { ref command, .. } if command == "tabs"));
+
+    session.navigate("https://app.test/").unwrap();
+    assert!(logged(&log).iter().any(|r| r.contains(r#""cmd":"navigate""#)));
+}
+
+#[test]
+fn unsupported_commands_fail_with_typed_error_and_keep_the_server() {
+    let ready = r#"{"ok":true,"ready":true,"protocol":1,"capabilities":["navigate","tabs","record_start","quit"]}"#;
+    let (config, log) = fake_server_with_ready("caps", ready);
+    let mut session = BrowserSession::launch_with(config).unwrap();
+    assert_eq!(session.handshake().unwrap().capabilities.len(), 4);
+
+    // Not announced: rejected without being sent
+    let err = session.take_console_log().unwrap_err();
+    assert!(matches!(err, AgentError::UnsupportedCommand { ref command, .. } if command == "console"));
+    // Announced, but the server answers with the unsupported code
+    let err = session.start_recording().unwrap_err();
+    assert!(matches!(err, AgentError::UnsupportedCommand { ref command, .. } if command == "record_start"));
+
+    session.navigate("https://app.test/").unwrap();
+    assert!(session.is_alive());
+    assert_eq!(session.restart_count(), 0);
+    let requests = logged(&log);
+    assert_eq!(requests.len(), 1);
+    assert!(requests[0].starts_with(r#"{"id":2,"cmd":"navigate""#), "{}", requests[0]);
+}
+
+#[test]
+fn stale_responses_are_skipped_by_request_id() {
+    let (config, _log) = fake_server("stale");
+    let mut session = BrowserSession::launch_with(config).unwrap();
+    let tabs = session.tabs().unwrap();
+    assert_eq!(tabs.len(), 1);
+    assert_eq!(tabs[0].url, "https://app.test/");
+}
+
+
+// =========================================================================
+// Pipelining and events
+// =========================================================================
+
+#[test]
+fn pipelined_requests_resolve_out_of_order() {
+    let (config, _log) = fake_server("pipeline");
+    let mut session = BrowserSession::launch_with(config).unwrap();
+    let pipeline = session.pipeline().unwrap();
+
+    let slow = pipeline.send(&BrowserRequest::query_text("#slow")).unwrap();
+    let fast = pipeline.send(&BrowserRequest::query_text("#fast")).unwrap();
+    assert!(fast.id() > slow.id());
+
+    let fast = fast.wait(Duration::from_secs(2)).unwrap();
+    assert_eq!(fast.text.as_deref(), Some("fast"));
+    assert!(slow.try_wait().is_none(), "slow answer must still be outstanding");
+    let slow = slow.wait(Duration::from_secs(2)).unwrap();
+    assert_eq!(slow.text.as_deref(), Some("slow"));
+
+    // The blocking API keeps working on the same server
+    assert_eq!(session.query_text("#fast").unwrap().as_deref(), Some("fast"));
+}
+
+#[test]
+fn cancelled_request_fails_at_once_and_its_late_answer_is_discarded() {
+    let (config, log) = fake_server("cancel");
+    let mut session = BrowserSession::launch_with(config).unwrap();
+    let pipeline = session.pipeline().unwrap();
+
+    let slow = pipeline.send(&BrowserRequest::query_text("#slow")).unwrap();
+    pipeline.cancel(&slow).unwrap();
+    match slow.wait(Duration::from_secs(2)) {
+        Err(AgentError::Cancelled { command }) => assert_eq!(command, "query_text"),
+        other => panic!("expected Cancelled, got {:?}", other),
+    }
+
+    // The "slow" answer arriving later must not be taken for this one
+    std::thread::sleep(Duration::from_millis(600));
+    assert!(logged(&log).iter().any(|l| l.contains(r#""cmd":"cancel","target":1"#)));
+    assert_eq!(session.query_text("#fast").unwrap().as_deref(), Some("fast"));
+}
+
+#[test]
+fn subscribed_events_are_queued_and_resubscribed_after_respawn() {
+    let (config, log) = fake_server("events");
+    let mut session = BrowserSession::launch_with(config).unwrap();
+    assert!(session.take_events().is_empty());
+
+    session.subscribe(&[EventKind::Console, EventKind::Navigation]).unwrap();
+    let events = session.take_events();
+    assert_eq!(events.len(), 3);
+    assert!(matches!(&events[0], ServerEvent::Console(entry) if entry.text == "boom"));
+    assert!(matches!(&events[1], ServerEvent::Navigation(nav) if nav.url == "https://app.test/next" && nav.tab == Some(0)));
+    assert_eq!(events[2], ServerEvent::Other);
+    assert!(session.take_events().is_empty());
+
+    assert!(session.query_text("#crash").is_err());
+    session.query_count("#fast").unwrap();
+    let subscribes: Vec<String> = logged(&log).into_iter().filter(|l| l.contains("subscribe")).collect();
+    assert_eq!(subscribes.len(), 2);
+    assert!(subscribes[1].contains(r#""events":["console","navigation"]"#), "{}", subscribes[1]);
+}
@@ -1,5 +1,8 @@
 use std::collections::HashMap;
 
+use screen_detection::browser::options::{ColorScheme, Geolocation, SessionOptions, Viewport};
+use screen_detection::browser::routes::RouteMock;
+use screen_detection::browser::tabs::TabSelector;
 use screen_detection::spec::{
     context::TestContext,
     spec_model::{AssertionResult, AssertionSpec, TestResult, TestSpec, TestStep},
@@ -20,9 +23,11 @@ fn sample_test_spec() -> TestSpec {
             TestStep::FillForm {
                 form: "search".into(),
                 values,
+                frame_path: Vec::new(),
             },
             TestStep::Click {
                 label: "Add to Cart".into(),
+                frame_path: Vec::new(),
             },
             TestStep::Wait { duration_ms: 2000 },
             TestStep::Assert {
@@ -34,6 +39,7 @@ fn sample_test_spec() -> TestSpec {
             },
             TestStep::Click {
                 label: "Checkout".into(),
+                frame_path: Vec::new(),
             },
             TestStep::Assert {
                 assertions: vec![
@@ -43,6 +49,9 @@ fn sample_test_spec() -> TestSpec {
                 ],
             },
         ],
+        options: None,
+        auth: false,
+        mocks: Vec::new(),
     }
 }
 
@@ -112,6 +121,7 @@ steps:
             form,
             values,
             submit_label,
+            ..
         } => {
             assert_eq!(form, "login");
             assert_eq!(values.get("Email").unwrap(), "user@test.com");
@@ -199,6 +209,205 @@ steps:
     }
 }
 
+#[test]
+fn test_spec_options_from_yaml() {
+    let yaml = r#"
+name: "Mobile checkout"
+start_url: "https://shop.test/cart"
+options:
+  device: "iPhone 13"
+  viewport: { width: 390, height: 844 }
+  locale: de-DE
+  timezone: Europe/Berlin
+  color_scheme: dark
+  geolocation: { latitude: 52.52, longitude: 13.405 }
+  permissions: [geolocation]
+steps: []
+"#;
+    let spec: TestSpec = serde_yaml::from_str(yaml).expect("Failed to parse YAML");
+    let options = spec.options.expect("options parsed");
+    assert_eq!(options.device.as_deref(), Some("iPhone 13"));
+    assert_eq!(options.viewport, Some(Viewport { width: 390, height: 844 }));
+    assert_eq!(options.color_scheme, Some(ColorScheme::Dark));
+    assert_eq!(options.geolocation.unwrap().latitude, 52.52);
+    assert_eq!(options.permissions, vec!["geolocation".to_string()]);
+    assert!(options.headed.is_none());
+}
+
+#[test]
+fn test_spec_without_options_omits_them_in_yaml() {
+    let yaml = serde_yaml::to_string(&sample_test_spec()).unwrap();
+    assert!(!yaml.contains("options"));
+    assert!(!yaml.contains("auth"));
+    assert!(!yaml.contains("mocks"));
+}
+
+#[test]
+fn network_assertions_from_yaml() {
+    let yaml = r#"
+- type: request_made
+  url_contains: /api/orders
+  method: POST
+- type: no_failed_requests
+- type: no_failed_requests
+  min_status: 500
+"#;
+    let assertions: Vec<AssertionSpec> = serde_yaml::from_str(yaml).unwrap();
+    assert_eq!(
+        assertions[0],
+        AssertionSpec::RequestMade { url_contains: "/api/orders".into(), method: Some("POST".into()) }
+    );
+    assert_eq!(assertions[1], AssertionSpec::NoFailedRequests { min_status: 400 });
+    assert_eq!(assertions[2], AssertionSpec::NoFailedRequests { min_status: 500 });
+}
+
+#[test]
+fn table_assertions_from_yaml() {
+    let yaml = r#"
+- type: table_rows
+  min: 3
+- type: table_column_contains
+  table: Orders
+  column: Status
+  expected: Active
+- type: table_sorted
+  column: Date
+  descending: true
+"#;
+    let assertions: Vec<AssertionSpec> = serde_yaml::from_str(yaml).unwrap();
+    assert_eq!(assertions[0], AssertionSpec::TableRows { table: None, min: 3 });
+    assert_eq!(
+        assertions[1],
+        AssertionSpec::TableColumnContains {
+            table: Some("Orders".into()),
+            column: "Status".into(),
+            expected: "Active".into(),
+        }
+    );
+    assert_eq!(
+        assertions[2],
+        AssertionSpec::TableSorted { table: None, column: "Date".into(), descending: true }
+    );
+    assert!(!serde_yaml::to_string(&assertions[0]).unwrap().contains("table:"));
+}
+
+#[test]
+fn overlay_steps_and_assertions_from_yaml() {
+    let yaml = r#"
+- action: dismiss_modal
+- action: assert
+  assertions:
+    - type: modal_open
+      open: false
+    - type: modal_open
+    - type: toast_shown
+      contains: Saved
+"#;
+    let steps: Vec<TestStep> = serde_yaml::from_str(yaml).unwrap();
+    assert_eq!(steps[0], TestStep::DismissModal);
+    assert_eq!(
+        steps[1],
+        TestStep::Assert {
+            assertions: vec![
+                AssertionSpec::ModalOpen { open: false },
+                AssertionSpec::ModalOpen { open: true },
+                AssertionSpec::ToastShown { contains: Some("Saved".into()) },
+            ],
+        }
+    );
+}
+
+#[test]
+fn test_spec_auth_flag_from_yaml() {
+    let yaml = "name: Orders\nstart_url: https://app.test/orders\nauth: true\nsteps: []\n";
+    let spec: TestSpec = serde_yaml::from_str(yaml).expect("Failed to parse YAML");
+    assert!(spec.auth);
+    assert!(serde_yaml::to_string(&spec).unwrap().contains("auth: true"));
+}
+
+#[test]
+fn test_spec_mocks_from_yaml() {
+    let yaml = r#"
+name: Orders error state
+start_url: https://app.test/orders
+mocks:
+  - url: "**/api/orders*"
+    status: 500
+  - url: "**/api/stats"
+    method: GET
+    body: { total: 0 }
+    delay_ms: 200
+steps: []
+"#;
+    let spec: TestSpec = serde_yaml::from_str(yaml).expect("Failed to parse YAML");
+    assert_eq!(spec.mocks.len(), 2);
+    assert_eq!(spec.mocks[0], RouteMock::status("**/api/orders*", 500));
+    assert_eq!(spec.mocks[1].status, 200);
+    assert_eq!(spec.mocks[1].method.as_deref(), Some("GET"));
+    assert_eq!(spec.mocks[1].body, Some(serde_json::json!({"total": 0})));
+    assert_eq!(spec.mocks[1].delay_ms, 200);
+}
+
+#[test]
+fn test_spec_dialog_steps_from_yaml() {
+    let yaml = r#"
+name: Rename project
+start_url: https://app.test/projects
+steps:
+  - action: expect_dialog
+    contains: New name
+    prompt_text: Apollo
+  - action: click
+    label: Rename
+  - action: expect_dialog
+    accept: false
+  - action: click
+    label: Delete
+  - action: assert
+    assertions:
+      - type: dialog_shown
+        contains: Are you sure
+"#;
+    let spec: TestSpec = serde_yaml::from_str(yaml).expect("Failed to parse YAML");
+    assert_eq!(
+        spec.steps[0],
+        TestStep::ExpectDialog { contains: Some("New name".into()), accept: true, prompt_text: Some("Apollo".into()) }
+    );
+    assert_eq!(spec.steps[2], TestStep::ExpectDialog { contains: None, accept: false, prompt_text: None });
+    match &spec.steps[4] {
+        TestStep::Assert { assertions } => {
+            assert_eq!(assertions[0], AssertionSpec::DialogShown { contains: Some("Are you sure".into()) })
+        }
+        other => panic!("expected assert, got {:?}", other),
+    }
+}
+
+#[test]
+fn session_options_merge_prefers_overrides() {
+    let base = SessionOptions {
+        locale: Some("en-US".into()),
+        timezone: Some("UTC".into()),
+        permissions: vec!["notifications".into()],
+        ..SessionOptions::default()
+    };
+    let overrides = SessionOptions {
+        locale: Some("ja-JP".into()),
+        color_scheme: Some(ColorScheme::NoPreference),
+        geolocation: Some(Geolocation { latitude: 35.68, longitude: 139.69, accuracy: None }),
+        ..SessionOptions::default()
+    };
+    let merged = base.merged(&overrides);
+    assert_eq!(merged.locale.as_deref(), Some("ja-JP"));
+    assert_eq!(merged.timezone.as_deref(), Some("UTC"));
+    assert_eq!(merged.permissions, vec!["notifications".to_string()]);
+    assert_eq!(
+        serde_json::to_value(merged.color_scheme).unwrap(),
+        serde_json::json!("no-preference")
+    );
+    assert_eq!(base.merged(&SessionOptions::default()), base);
+    assert!(SessionOptions::default().is_empty());
+}
+
 // =========================================================================
 // TestContext tests
 // =========================================================================
@@ -323,6 +532,11 @@ fn test_result_serializes_correctly() {
         duration_ms: None,
         screenshots: vec![],
         retry_attempts: 0,
+        har_path: None,
+        trace_path: None,
+        video_path: None,
+        console: Vec::new(),
+        suggestions: Vec::new(),
     };
 
     let json = serde_json::to_string(&result).expect("serialize TestResult");
@@ -342,6 +556,11 @@ fn test_result_with_error() {
         duration_ms: None,
         screenshots: vec![],
         retry_attempts: 0,
+        har_path: None,
+        trace_path: None,
+        video_path: None,
+        console: Vec::new(),
+        suggestions: Vec::new(),
     };
 
     let json = serde_json::to_string(&result).expect("serialize TestResult");
@@ -506,3 +725,87 @@ fn url_not_contains_yaml_roundtrip() {
     // serde uses snake_case rename_all so the YAML tag is "url_not_contains"
     assert!(yaml.contains("url_not_contains"), "YAML should contain type tag 'url_not_contains', got:\n{}", yaml);
 }
+
+// ============================================================================
+// Tab steps YAML
+// ============================================================================
+
+#[test]
+fn tab_steps_parse_from_yaml() {
+    let yaml = r#"
+- action: switch_tab
+  url_contains: accounts.test
+- action: switch_tab
+  index: 0
+  timeout_ms: 0
+- action: close_tab
+"#;
+    let steps: Vec<TestStep> = serde_yaml::from_str(yaml).expect("Failed to parse tab steps");
+    assert_eq!(
+        steps,
+        vec![
+            TestStep::SwitchTab { tab: TabSelector::url_contains("accounts.test"), timeout_ms: 5000 },
+            TestStep::SwitchTab { tab: TabSelector::index(0), timeout_ms: 0 },
+            TestStep::CloseTab { tab: TabSelector::default() },
+        ]
+    );
+
+    let yaml = serde_yaml::to_string(&steps[0]).unwrap();
+    assert!(yaml.contains("action: switch_tab"));
+    assert!(yaml.contains("url_contains: accounts.test"));
+    assert!(!yaml.contains("index"));
+}
+
+#[test]
+fn frame_scoped_steps_parse_from_yaml() {
+    let yaml = r#"
+- action: fill_form
+  form: card-form
+  values:
+    Card number: "4242"
+  frame_path: ["iframe#payment", "iframe#card"]
+- action: click
+  label: Pay
+"#;
+    let steps: Vec<TestStep> = serde_yaml::from_str(yaml).expect("Failed to parse frame steps");
+    match &steps[0] {
+        TestStep::FillForm { frame_path, .. } => assert_eq!(frame_path, &vec!["iframe#payment", "iframe#card"]),
+        other => panic!("Expected FillForm, got {:?}", other),
+    }
+    assert_eq!(steps[1], TestStep::Click { label: "Pay".into(), frame_path: Vec::new() });
+    assert!(!serde_yaml::to_string(&steps[1]).unwrap().contains("frame_path"));
+}
+
+#[test]
+fn interaction_steps_parse_from_yaml() {
+    let yaml = r#"
+- action: hover
+  label: Account
+- action: type
+  label: City
+  text: Ber
+- action: press
+  key: Enter
+- action: scroll
+  delta_y: 600
+- action: drag_and_drop
+  source: Card A
+  target: Done
+- action: upload
+  label: Avatar
+  files: [fixtures/avatar.png]
+"#;
+    let steps: Vec<TestStep> = serde_yaml::from_str(yaml).expect("Failed to parse interaction steps");
+    assert_eq!(
+        steps,
+        vec![
+            TestStep::Hover { label: "Account".into(), frame_path: Vec::new() },
+            TestStep::Type { label: "City".into(), text: "Ber".into(), form: None, delay_ms: 50 },
+            TestStep::Press { key: "Enter".into(), label: None },
+            TestStep::Scroll { label: None, delta_x: 0, delta_y: 600 },
+            TestStep::DragAndDrop { source: "Card A".into(), target: "Done".into() },
+            TestStep::Upload { label: "Avatar".into(), files: vec!["fixtures/avatar.png".into()], form: None },
+        ]
+    );
+    assert!(!serde_yaml::to_string(&steps[2]).unwrap().contains("label"));
+}
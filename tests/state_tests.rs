@@ -1,7 +1,7 @@
 use screen_detection::{
     screen::{
         intent::infer_form_intent,
-        screen_model::{ElementKind, Form, ScreenElement},
+        screen_model::{ElementKind, Form, IntentSignal, ScreenElement},
     },
     state::normalize::normalize_output_text,
 };
@@ -71,6 +71,10 @@ fn form_intent_scoring_boundaries() {
         nearby_help_text: None,
         autocomplete: None,
         aria_describedby_text: None,
+        semantic_section: None,
+        aria_live: None,
+        overlay: None,
+        frame_path: Vec::new(),
         }],
         actions: vec![ScreenElement {
             label: Some("Submit".into()),
@@ -93,9 +97,14 @@ fn form_intent_scoring_boundaries() {
         nearby_help_text: None,
         autocomplete: None,
         aria_describedby_text: None,
+        semantic_section: None,
+        aria_live: None,
+        overlay: None,
+        frame_path: Vec::new(),
         }],
         primary_action: None,
         intent: None,
+        frame_path: Vec::new(),
     };
     let intent = infer_form_intent(&plain_form);
     assert_eq!(intent.label, "Unknown", "No auth signals â†’ Unknown");
@@ -125,6 +134,10 @@ fn form_intent_scoring_boundaries() {
         nearby_help_text: None,
         autocomplete: None,
         aria_describedby_text: None,
+        semantic_section: None,
+        aria_live: None,
+        overlay: None,
+        frame_path: Vec::new(),
         }],
         actions: vec![ScreenElement {
             label: Some("Submit".into()),
@@ -147,14 +160,21 @@ fn form_intent_scoring_boundaries() {
         nearby_help_text: None,
         autocomplete: None,
         aria_describedby_text: None,
+        semantic_section: None,
+        aria_live: None,
+        overlay: None,
+        frame_path: Vec::new(),
         }],
         primary_action: None,
         intent: None,
+        frame_path: Vec::new(),
     };
     let intent = infer_form_intent(&password_form);
     assert_eq!(intent.label, "Unknown", "score=0.4 is NOT > 0.4, so Unknown");
+    assert_eq!(intent.confidence, 0.0, "Unknown carries no confidence");
+    assert!(intent.signals.is_empty(), "Unknown carries no signals");
 
-    // Login action only (score=0.4) â†’ "Unknown"
+    // Login action + "username" keyword (score=0.6) → Authentication
     let login_form = Form {
         id: "login".into(),
         inputs: vec![ScreenElement {
@@ -178,6 +198,10 @@ fn form_intent_scoring_boundaries() {
         nearby_help_text: None,
         autocomplete: None,
         aria_describedby_text: None,
+        semantic_section: None,
+        aria_live: None,
+        overlay: None,
+        frame_path: Vec::new(),
         }],
         actions: vec![ScreenElement {
             label: Some("Login".into()),
@@ -200,12 +224,18 @@ fn form_intent_scoring_boundaries() {
         nearby_help_text: None,
         autocomplete: None,
         aria_describedby_text: None,
+        semantic_section: None,
+        aria_live: None,
+        overlay: None,
+        frame_path: Vec::new(),
         }],
         primary_action: None,
         intent: None,
+        frame_path: Vec::new(),
     };
     let intent = infer_form_intent(&login_form);
-    assert_eq!(intent.label, "Unknown", "Login action only â†’ score=0.4 â†’ Unknown");
+    assert_eq!(intent.label, "Authentication", "Login action + username keyword → score=0.6 → Authentication");
+    assert!(intent.signals.contains(&IntentSignal::Keyword("username".into())));
 
     // Password + Sign In (score=0.8) â†’ Authentication
     let auth_form = Form {
@@ -231,6 +261,10 @@ fn form_intent_scoring_boundaries() {
         nearby_help_text: None,
         autocomplete: None,
         aria_describedby_text: None,
+        semantic_section: None,
+        aria_live: None,
+        overlay: None,
+        frame_path: Vec::new(),
         }],
         actions: vec![ScreenElement {
             label: Some("Sign In".into()),
@@ -253,15 +287,152 @@ fn form_intent_scoring_boundaries() {
         nearby_help_text: None,
         autocomplete: None,
         aria_describedby_text: None,
+        semantic_section: None,
+        aria_live: None,
+        overlay: None,
+        frame_path: Vec::new(),
         }],
         primary_action: None,
         intent: None,
+        frame_path: Vec::new(),
     };
     let intent = infer_form_intent(&auth_form);
     assert_eq!(intent.label, "Authentication", "Password + Sign In â†' Authentication");
     assert!(intent.confidence > 0.7, "Confidence should exceed 0.7");
 }
 
+fn field(label: &str, input_type: &str, autocomplete: Option<&str>) -> ScreenElement {
+    let tag = if matches!(input_type, "select" | "textarea") { input_type } else { "input" };
+    ScreenElement {
+        label: Some(label.into()),
+        kind: ElementKind::Input,
+        tag: Some(tag.into()),
+        role: None,
+        input_type: (tag == "input").then(|| input_type.into()),
+        required: false,
+        placeholder: None,
+        id: None,
+        href: None,
+        options: None,
+        name: None,
+        value: None,
+        maxlength: None,
+        minlength: None,
+        readonly: false,
+        fieldset_legend: None,
+        section_heading: None,
+        nearby_help_text: None,
+        autocomplete: autocomplete.map(String::from),
+        aria_describedby_text: None,
+        semantic_section: None,
+        aria_live: None,
+        overlay: None,
+        frame_path: Vec::new(),
+    }
+}
+
+fn intent_of(inputs: Vec<ScreenElement>, action: &str) -> screen_detection::screen::screen_model::FormIntent {
+    let mut button = field(action, "submit", None);
+    button.kind = ElementKind::Action;
+    button.tag = Some("button".into());
+    infer_form_intent(&Form {
+        id: "f".into(),
+        inputs,
+        actions: vec![button],
+        primary_action: None,
+        intent: None,
+        frame_path: Vec::new(),
+    })
+}
+
+#[test]
+fn form_intent_recognizes_expanded_taxonomy() {
+    let cases = vec![
+        (intent_of(vec![field("Search products", "search", None)], "Go"), "Search"),
+        (
+            intent_of(
+                vec![
+                    field("First name", "text", Some("given-name")),
+                    field("Email", "email", Some("email")),
+                    field("Password", "password", Some("new-password")),
+                    field("Confirm password", "password", Some("new-password")),
+                ],
+                "Create account",
+            ),
+            "Registration",
+        ),
+        (
+            intent_of(
+                vec![field("New password", "password", Some("new-password")), field("Repeat", "password", None)],
+                "Reset password",
+            ),
+            "Password Reset",
+        ),
+        (
+            intent_of(
+                vec![
+                    field("Card number", "text", Some("cc-number")),
+                    field("Expiry", "text", Some("cc-exp")),
+                    field("CVC", "text", Some("cc-csc")),
+                ],
+                "Pay now",
+            ),
+            "Checkout",
+        ),
+        (
+            intent_of(vec![field("Your email", "email", None), field("Message", "textarea", None)], "Send"),
+            "Contact",
+        ),
+        (intent_of(vec![field("Get our newsletter", "email", None)], "Subscribe"), "Newsletter"),
+        (
+            intent_of(vec![field("Sort by", "select", None), field("In stock", "checkbox", None)], "Apply"),
+            "Filter",
+        ),
+        (
+            intent_of(
+                vec![
+                    field("Street", "text", Some("address-line1")),
+                    field("City", "text", Some("address-level2")),
+                    field("ZIP", "text", Some("postal-code")),
+                ],
+                "Continue",
+            ),
+            "Address",
+        ),
+    ];
+    for (intent, expected) in cases {
+        assert_eq!(intent.label, expected, "signals: {:?}", intent.signals);
+        assert!(intent.confidence > 0.4 && intent.confidence <= 1.0);
+        assert!(!intent.signals.is_empty());
+    }
+}
+
+#[test]
+fn form_intent_reports_signals_and_uses_headings_and_prefill() {
+    let intent = intent_of(vec![field("Search", "search", None)], "Search");
+    assert_eq!(intent.confidence, 1.0);
+    assert!(intent.signals.contains(&IntentSignal::InputType("search".into())));
+    assert!(intent.signals.contains(&IntentSignal::ActionLabel("Search".into())));
+    assert!(intent.signals.contains(&IntentSignal::Structure("single text field".into())));
+
+    // Generic fields: the section heading and prefilled values decide
+    let mut name = field("Name", "text", None);
+    name.value = Some("Ada Lovelace".into());
+    name.section_heading = Some("Edit profile".into());
+    let mut website = field("Website", "url", None);
+    website.value = Some("https://ada.dev".into());
+    let intent = intent_of(vec![name, website], "Save changes");
+    assert_eq!(intent.label, "Profile Edit");
+    assert!(intent.signals.contains(&IntentSignal::Keyword("profile".into())));
+    assert!(intent.signals.contains(&IntentSignal::Structure("prefilled fields".into())));
+
+    // Structure alone is not evidence
+    let intent = intent_of(vec![field("Name", "text", None)], "OK");
+    assert_eq!(intent.label, "Unknown");
+    assert_eq!(intent.confidence, 0.0);
+    assert!(intent.signals.is_empty());
+}
+
 // =========================================================================
 // Phase 13: ScreenElement carries constraint fields
 // =========================================================================
@@ -290,7 +461,13 @@ fn screen_element_carries_maxlength() {
         section_heading: None,
         nearby_help_text: None,
         semantic_section: None,
+        aria_live: None,
+        in_dialog: false,
+        live_region: None,
         visible: false,
+        frame_path: Vec::new(),
+        shadow_path: Vec::new(),
+        table: None,
     }];
     let semantics = classify(&elements);
     let form = semantics.forms.iter().find(|f| f.id == "f").unwrap();
@@ -321,7 +498,13 @@ fn screen_element_carries_minlength() {
         section_heading: None,
         nearby_help_text: None,
         semantic_section: None,
+        aria_live: None,
+        in_dialog: false,
+        live_region: None,
         visible: false,
+        frame_path: Vec::new(),
+        shadow_path: Vec::new(),
+        table: None,
     }];
     let semantics = classify(&elements);
     let form = semantics.forms.iter().find(|f| f.id == "f").unwrap();
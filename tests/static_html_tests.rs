@@ -0,0 +1,551 @@
+use screen_detection::agent::error::AgentError;
+use screen_detection::browser::backend::BrowserBackend;
+use screen_detection::browser::playwright::SelectorHint;
+use screen_detection::browser::static_html::{StaticBrowser, extract_html, extract_static};
+use screen_detection::screen::classifier::classify;
+use screen_detection::canonical::canonical_model::canonicalize;
+use screen_detection::canonical::diff::SemanticSignal;
+use screen_detection::screen::screen_model::{DomElement, OutputRegion, Overlay, StructuralOutline, TableKind};
+use screen_detection::screen::table::find_table;
+use screen_detection::state::state_builder::{build_state, build_state_with_outline};
+use serde_json::Value;
+
+#[allow(dead_code)]
+mod common;
+use common::semantic_diff::{diff_between_html_pages, diff_html_initial};
+use common::utils::page;
+
+// =========================================================================
+// Helpers
+// =========================================================================
+
+fn elements(raw: &Value) -> Vec<DomElement> {
+    serde_json::from_value(raw["dom"].clone()).unwrap()
+}
+
+fn find<'a>(els: &'a [DomElement], tag: &str, name: &str) -> &'a DomElement {
+    els.iter()
+        .find(|e| e.tag == tag && e.name.as_deref() == Some(name))
+        .unwrap_or_else(|| panic!("no <{}> named '{}'", tag, name))
+}
+
+const SIGNUP: &str = r#"<!doctype html>
+<html><head><title>Sign up</title><script>var x = "not text";</script></head>
+<body>
+  <header><nav aria-label="Primary"><a href="/home">Home</a></nav></header>
+  <main>
+    <h1>Create account</h1>
+    <form id="signup" action="/register" method="POST">
+      <fieldset>
+        <legend>Your details</legend>
+        <label for="email">Email address</label>
+        <input id="email" name="email" type="email" required autocomplete="email"
+               aria-describedby="email-help">
+        <small id="email-help">We never share it</small>
+        <label>Password <input name="password" type="password" minlength="8" maxlength="64"></label>
+        <select name="country"><option value="">Choose…</option><option value="de" selected>Germany</option></select>
+      </fieldset>
+      <fieldset disabled><input name="promo" aria-label="Promo code"></fieldset>
+      <input name="token" type="hidden" value="abc">
+      <button type="submit">Create account</button>
+    </form>
+    <input name="outside" form="signup" aria-label="Referral">
+  </main>
+  <footer><p style="display: none">Hidden note</p><p>Terms apply</p></footer>
+</body></html>"#;
+
+// =========================================================================
+// extract_html field population
+// =========================================================================
+
+#[test]
+fn extract_html_returns_url_title_and_dom() {
+    let raw = extract_html(SIGNUP, "https://example.test/signup");
+    assert_eq!(raw["url"], "https://example.test/signup");
+    assert_eq!(raw["title"], "Sign up");
+    assert!(!raw["dom"].as_array().unwrap().is_empty());
+    let all_text = serde_json::to_string(&raw["dom"]).unwrap();
+    assert!(!all_text.contains("not text"), "script contents must not be extracted");
+}
+
+#[test]
+fn extract_html_emits_only_elements_owning_text() {
+    let raw = extract_html(
+        "<html><body><div><h1>Hi</h1><div><p>Para one</p><p>Para two</p></div><button>Go</button></div></body></html>",
+        "file:///nested.html",
+    );
+    let els: Vec<_> = elements(&raw)
+        .into_iter()
+        .map(|e| (e.tag, e.text.unwrap_or_default()))
+        .collect();
+
+    assert_eq!(
+        els,
+        vec![
+            ("h1".to_string(), "Hi".to_string()),
+            ("p".to_string(), "Para one".to_string()),
+            ("p".to_string(), "Para two".to_string()),
+            ("button".to_string(), "Go".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn extract_html_resolves_form_and_label_context() {
+    let els = elements(&extract_html(SIGNUP, "https://example.test/signup"));
+
+    let email = find(&els, "input", "email");
+    assert_eq!(email.form_id.as_deref(), Some("signup"));
+    assert_eq!(email.associated_label_text.as_deref(), Some("Email address"));
+    assert_eq!(email.fieldset_legend.as_deref(), Some("Your details"));
+    assert_eq!(email.section_heading.as_deref(), Some("Create account"));
+    assert_eq!(email.aria_describedby_text.as_deref(), Some("We never share it"));
+    assert_eq!(email.nearby_help_text.as_deref(), Some("We never share it"));
+    assert_eq!(email.autocomplete.as_deref(), Some("email"));
+    assert_eq!(email.form_action.as_deref(), Some("/register"));
+    assert_eq!(email.form_method.as_deref(), Some("post"));
+    assert_eq!(email.semantic_section.as_deref(), Some("form"));
+    assert!(email.required);
+    assert!(email.visible);
+
+    let password = find(&els, "input", "password");
+    assert_eq!(password.associated_label_text.as_deref(), Some("Password"));
+    assert_eq!(password.minlength, Some(8));
+    assert_eq!(password.maxlength, Some(64));
+}
+
+#[test]
+fn extract_html_handles_select_disabled_hidden_and_form_attribute() {
+    let els = elements(&extract_html(SIGNUP, "https://example.test/signup"));
+
+    let country = find(&els, "select", "country");
+    let options = country.options.as_ref().unwrap();
+    assert_eq!(options.len(), 2);
+    assert_eq!(country.value.as_deref(), Some("de"));
+
+    assert!(find(&els, "input", "promo").disabled, "disabled fieldset disables its inputs");
+    assert!(!find(&els, "input", "token").visible);
+    assert_eq!(find(&els, "input", "outside").form_id.as_deref(), Some("signup"));
+
+    let hidden_note = els.iter().find(|e| e.text.as_deref() == Some("Hidden note")).unwrap();
+    assert!(!hidden_note.visible);
+}
+
+#[test]
+fn extract_html_builds_structural_outline() {
+    let raw = extract_html(SIGNUP, "https://example.test/signup");
+    let outline: StructuralOutline = serde_json::from_value(raw["structural_outline"].clone()).unwrap();
+    assert_eq!(outline.headings.len(), 1);
+    assert_eq!(outline.headings[0].level, 1);
+    assert_eq!(outline.headings[0].text, "Create account");
+    let tags: Vec<_> = outline.landmarks.iter().map(|l| l.tag.as_str()).collect();
+    assert_eq!(tags, vec!["header", "nav", "main", "footer"]);
+    assert_eq!(outline.landmarks[1].label, "Primary");
+}
+
+#[test]
+fn classify_works_on_static_extraction() {
+    let els = elements(&extract_html(SIGNUP, "https://example.test/signup"));
+    let semantics = classify(&els);
+    let form = semantics.forms.iter().find(|f| f.id == "signup").unwrap();
+    let labels: Vec<_> = form.inputs.iter().filter_map(|i| i.label.clone()).collect();
+    assert!(labels.contains(&"Email address".to_string()));
+    assert!(labels.contains(&"Referral".to_string()));
+    assert!(form.actions.iter().any(|a| a.label.as_deref() == Some("Create account")));
+}
+
+const STORE: &str = r#"<!doctype html>
+<html><head><title>Store</title></head>
+<body>
+  <header><p>Free shipping over $50</p></header>
+  <main>
+    <h1>Search results</h1>
+    <p role="status">2 items found</p>
+    <div aria-live="polite"><span>Cart updated</span> <b>3 items</b><p aria-live="off">Quiet note</p></div>
+    <ul><li>Hammer</li><li>Wrench</li></ul>
+  </main>
+  <aside><p>Related: nails</p></aside>
+  <div role="dialog" aria-label="Cookies"><p>We use cookies</p></div>
+  <footer><p>Contact us anytime</p></footer>
+</body></html>"#;
+
+#[test]
+fn extract_html_reports_live_regions() {
+    let els = elements(&extract_html(STORE, "https://example.test/search"));
+    let live = |text: &str| {
+        els.iter()
+            .find(|e| e.text.as_deref() == Some(text))
+            .unwrap_or_else(|| panic!("no element '{}'", text))
+            .aria_live
+            .clone()
+    };
+    assert_eq!(live("2 items found").as_deref(), Some("polite"));
+    assert_eq!(live("Cart updated").as_deref(), Some("polite"));
+    assert_eq!(live("Quiet note"), None);
+    assert_eq!(live("Hammer"), None);
+}
+
+#[test]
+fn outputs_are_placed_in_regions_by_structure() {
+    let raw = extract_html(STORE, "https://example.test/search");
+    let outline: StructuralOutline = serde_json::from_value(raw["structural_outline"].clone()).unwrap();
+    let state = build_state_with_outline(None, "Store", classify(&elements(&raw)), outline);
+    let region = |text: &str| {
+        state
+            .identities
+            .values()
+            .find(|i| i.element.label.as_deref() == Some(text))
+            .unwrap_or_else(|| panic!("no output '{}'", text))
+            .region
+            .clone()
+    };
+
+    assert_eq!(region("Free shipping over $50"), OutputRegion::Header);
+    assert_eq!(region("Contact us anytime"), OutputRegion::Footer);
+    assert_eq!(region("We use cookies"), OutputRegion::Modal);
+    assert_eq!(region("Related: nails"), OutputRegion::Unknown);
+    assert_eq!(region("Hammer"), OutputRegion::Results);
+    // A status line is a toast, wherever it sits
+    assert_eq!(region("2 items found"), OutputRegion::Main);
+    // Under the "Search results" heading
+    assert_eq!(region("Quiet note"), OutputRegion::Results);
+
+    // Without landmarks, wording is all there is to go on
+    let bare = extract_html(
+        "<html><body><p>Read our privacy policy</p><p>Welcome back</p></body></html>",
+        "https://example.test/",
+    );
+    let state = build_state(None, "", classify(&elements(&bare)));
+    let region_of = |text: &str| {
+        state.identities.values().find(|i| i.element.label.as_deref() == Some(text)).unwrap().region.clone()
+    };
+    assert_eq!(region_of("Read our privacy policy"), OutputRegion::Footer);
+    assert_eq!(region_of("Welcome back"), OutputRegion::Main);
+}
+
+#[test]
+fn standalone_actions_are_placed_in_regions_like_outputs() {
+    let raw = extract_html(
+        r#"<html><body>
+          <header><nav><a href="/account">Account</a></nav></header>
+          <main><h1>Tools</h1><button>Load more</button></main>
+          <div role="dialog" aria-label="Cookies"><button>Accept</button></div>
+          <footer><a href="/privacy">Privacy</a></footer>
+        </body></html>"#,
+        "https://example.test/",
+    );
+    let outline: StructuralOutline = serde_json::from_value(raw["structural_outline"].clone()).unwrap();
+    let state = build_state_with_outline(None, "Tools", classify(&elements(&raw)), outline);
+    let region = |label: &str| {
+        let action = state
+            .identities
+            .values()
+            .find(|i| i.scope == "screen" && i.element.label.as_deref() == Some(label))
+            .unwrap_or_else(|| panic!("no action '{}'", label));
+        action.region.clone()
+    };
+
+    assert_eq!(region("Account"), OutputRegion::Header);
+    assert_eq!(region("Load more"), OutputRegion::Main);
+    assert_eq!(region("Accept"), OutputRegion::Modal);
+    assert_eq!(region("Privacy"), OutputRegion::Footer);
+}
+
+#[test]
+fn classify_tags_modals_and_toasts() {
+    let raw = extract_static(&page("14_catalog_cookie_banner.html")).unwrap();
+    let els = elements(&raw);
+    let in_dialog = |text: &str| els.iter().find(|e| e.text.as_deref() == Some(text)).unwrap().in_dialog;
+    // Under an aria-modal container, though its nearest landmark is a <section>
+    assert!(in_dialog("We use cookies to improve your visit."));
+    assert!(in_dialog("Close"));
+    assert!(!in_dialog("Browse our full range of tools."));
+
+    let semantics = classify(&els);
+    assert!(semantics.modal_open());
+    let close = semantics.standalone_actions.iter().find(|a| a.label.as_deref() == Some("Close")).unwrap();
+    assert_eq!(close.overlay, Some(Overlay::Modal));
+
+    let semantics = classify(&elements(&extract_html(STORE, "https://example.test/search")));
+    let overlay = |text: &str| {
+        semantics.outputs.iter().find(|o| o.label.as_deref() == Some(text)).unwrap().overlay
+    };
+    assert_eq!(overlay("We use cookies"), Some(Overlay::Modal));
+    assert_eq!(overlay("Cart updated 3 items"), Some(Overlay::Toast));
+    assert_eq!(overlay("Quiet note"), None);
+    assert_eq!(overlay("Hammer"), None);
+    // One toast per live region, made of all its texts
+    assert_eq!(semantics.toasts(), vec!["2 items found", "Cart updated 3 items"]);
+}
+
+const ORDERS: &str = r#"<!doctype html>
+<html><head><title>Orders</title></head>
+<body>
+  <nav><ul><li><a href="/">Home</a></li><li><a href="/orders">Orders</a></li></ul></nav>
+  <main>
+    <h2>Recent orders</h2>
+    <table id="orders">
+      <caption>Order history</caption>
+      <thead><tr><th>Order</th><th>Total</th><th>Status</th></tr></thead>
+      <tbody>
+        <tr><td>#1003</td><td>$1,250.00</td><td>Active</td></tr>
+        <tr><td>#1002</td><td>$99.50</td><td>Shipped <table><tr><td>nested</td></tr></table></td></tr>
+        <tr style="display: none"><td>#0000</td><td>$0</td><td>Hidden</td></tr>
+        <tr><td>#1001</td><td>$12</td><td>Cancelled</td></tr>
+      </tbody>
+    </table>
+    <div role="grid" aria-label="Users">
+      <div role="row"><span role="columnheader">Name</span><span role="columnheader">Role</span></div>
+      <div role="row"><span role="gridcell">Ada</span><span role="gridcell">Admin</span></div>
+      <div role="row"><span role="gridcell">Bob</span><span role="gridcell">Viewer</span></div>
+    </div>
+    <ul class="results"><li>Drill</li><li>Saw</li><li>Hammer</li></ul>
+    <ul><li>Only one</li></ul>
+    <table role="presentation"><tr><td>Layout</td></tr></table>
+    <table><tr><td>No</td><td>header</td></tr><tr><td>row</td><td>here</td></tr></table>
+    <table><tr><th>Alone</th></tr></table>
+  </main>
+</body></html>"#;
+
+#[test]
+fn classify_reads_tables_grids_and_lists() {
+    let raw = extract_html(ORDERS, "https://example.test/orders");
+    let tables = classify(&elements(&raw)).tables;
+    let ids: Vec<&str> = tables.iter().map(|t| t.id.as_str()).collect();
+    // Menus, single items, layout, header-less or one-cell tables and tables
+    // nested in a reported one are not tables
+    assert_eq!(ids, vec!["table:orders", "table:users", "table:recent-orders"]);
+
+    let orders = &tables[0];
+    assert_eq!(orders.kind, TableKind::Table);
+    assert_eq!(orders.label.as_deref(), Some("Order history"));
+    assert_eq!(orders.headers, vec!["Order", "Total", "Status"]);
+    assert_eq!(orders.row_count(), 3, "hidden rows and nested tables' rows are left out");
+    assert_eq!(orders.rows[0], vec!["#1003", "$1,250.00", "Active"]);
+    assert_eq!(orders.column("status").unwrap(), vec!["Active", "Shipped nested", "Cancelled"]);
+    assert_eq!(orders.is_sorted_by("Total", true), Some(true));
+    assert_eq!(orders.is_sorted_by("Order", false), Some(false));
+    assert_eq!(orders.is_sorted_by("Missing", false), None);
+
+    let users = &tables[1];
+    assert_eq!(users.kind, TableKind::Grid);
+    assert_eq!(users.headers, vec!["Name", "Role"]);
+    assert_eq!(users.is_sorted_by("name", false), Some(true));
+
+    let list = &tables[2];
+    assert_eq!(list.kind, TableKind::List);
+    assert!(list.headers.is_empty());
+    assert_eq!(list.rows, vec![vec!["Drill"], vec!["Saw"], vec!["Hammer"]]);
+
+    assert_eq!(find_table(&tables, Some("order history"), None).unwrap().id, "table:orders");
+    assert_eq!(find_table(&tables, None, Some("Role")).unwrap().id, "table:users");
+
+    // A data table inside a layout table is still reported
+    let layout = extract_html(
+        "<table><tr><td><table><tr><th>A</th><th>B</th></tr><tr><td>1</td><td>2</td></tr></table></td></tr></table>",
+        "https://example.test/layout",
+    );
+    let tables = classify(&elements(&layout)).tables;
+    assert_eq!(tables.len(), 1);
+    assert_eq!(tables[0].headers, vec!["A", "B"]);
+
+    // Tables are part of the screen state and its canonical form
+    let state = build_state(None, "Orders", classify(&elements(&raw)));
+    assert_eq!(state.tables.len(), 3);
+    let canonical = canonicalize(&state, None);
+    assert_eq!(canonical.tables.len(), 3);
+    assert_eq!(canonical.tables["table:users"].row_count(), 2);
+}
+
+#[test]
+fn extract_static_reads_fixture_file() {
+    let raw = extract_static(&page("06_multiple_forms.html")).unwrap();
+    assert_eq!(raw["title"], "Multiple Forms");
+    let semantics = classify(&elements(&raw));
+    let mut ids: Vec<_> = semantics.forms.iter().map(|f| f.id.clone()).collect();
+    ids.sort();
+    assert_eq!(ids, vec!["newsletter", "search"]);
+}
+
+#[test]
+fn extract_static_missing_file_is_page_load_error() {
+    let err = extract_static("file:///definitely/not/here.html").unwrap_err();
+    assert!(err.to_string().contains("Failed to load"));
+}
+
+#[test]
+fn static_extraction_detects_search_submission_and_results() {
+    let diff = diff_between_html_pages(&page("01_search_form.html"), &page("03_search_results.html"));
+
+    println!("Diff = {:?}", diff);
+    assert!(diff.signals.contains(&SemanticSignal::FormSubmitted {
+        form_id: "search".into()
+    }));
+    assert!(diff.signals.contains(&SemanticSignal::ResultsAppeared));
+    assert!(!diff.signals.contains(&SemanticSignal::NavigationOccurred));
+}
+
+#[test]
+fn static_extraction_detects_errors() {
+    let diff = diff_between_html_pages(&page("02_search_submitted.html"), &page("05_error.html"));
+
+    println!("Diff = {:?}", diff);
+    assert!(diff.signals.contains(&SemanticSignal::ErrorAppeared));
+}
+
+#[test]
+fn static_extraction_of_initial_page_is_screen_loaded() {
+    let diff = diff_html_initial(&page("01_search_form.html"));
+
+    assert_eq!(diff.signals, vec![SemanticSignal::ScreenLoaded]);
+}
+
+// =========================================================================
+// StaticBrowser backend
+// =========================================================================
+
+#[test]
+fn static_browser_queries_with_css() {
+    let mut browser = StaticBrowser::new();
+    browser.load_html("https://example.test/signup", SIGNUP);
+    assert_eq!(browser.query_count("form#signup input").unwrap(), 4);
+    assert_eq!(browser.query_text("main h1").unwrap(), Some("Create account".into()));
+    assert!(!browser.query_visible("footer p").unwrap());
+    assert!(browser.query_text("::nope(").is_err());
+}
+
+#[test]
+fn static_browser_follows_links_but_not_buttons() {
+    let mut browser = StaticBrowser::new();
+    browser.navigate(&page("08_navigation.html")).unwrap();
+
+    let submit = SelectorHint {
+        role: Some("button".into()),
+        name: Some("Missing".into()),
+        tag: None,
+        input_type: None,
+        form_id: None,
+        frame_path: Vec::new(),
+        shadow_path: Vec::new(),
+    };
+    assert!(browser.click(&submit).is_err());
+
+    let link = SelectorHint { name: Some("Go to results".into()), ..submit };
+    browser.click(&link).unwrap();
+    assert!(browser.current_url().unwrap().ends_with("03_search_results.html"));
+    assert_eq!(browser.extract().unwrap()["title"], "Results");
+}
+
+#[test]
+fn static_browser_names_inputs_by_value_and_title_and_reports_disabled() {
+    let mut browser = StaticBrowser::new();
+    browser.load_html(
+        "https://example.test/prompt",
+        r#"<form id="f"><input type="submit" value="Go"><input type="search" title="Find"></form>
+           <button disabled>Later</button>"#,
+    );
+
+    let go = SelectorHint {
+        role: Some("button".into()),
+        name: Some("Go".into()),
+        tag: None,
+        input_type: None,
+        form_id: None,
+        frame_path: Vec::new(),
+        shadow_path: Vec::new(),
+    };
+    // Found, but a static page cannot submit it
+    assert!(matches!(browser.click(&go).unwrap_err(), AgentError::BrowserAction(_)));
+    let find = SelectorHint { role: Some("searchbox".into()), name: Some("Find".into()), ..go.clone() };
+    browser.fill(&find, "shoes").unwrap();
+
+    let later = SelectorHint { name: Some("Later".into()), ..go };
+    let err = browser.click(&later).unwrap_err();
+    assert!(
+        matches!(&err, AgentError::ElementDisabled { element, .. } if element == "Later"),
+        "got {err}"
+    );
+}
+
+// =========================================================================
+// Shadow DOM
+// =========================================================================
+
+const WEB_COMPONENTS: &str = r#"<!doctype html>
+<html><head><title>Checkout</title></head>
+<body>
+  <form id="checkout" action="/pay">
+    <ds-field id="email">
+      <template shadowrootmode="open">
+        <label>Email <input name="email" type="email"></label>
+      </template>
+    </ds-field>
+    <ds-card>
+      <template shadowrootmode="open">
+        <ds-field id="number">
+          <template shadowrootmode="open"><input aria-label="Card number" name="card"></template>
+        </ds-field>
+      </template>
+    </ds-card>
+    <ds-button><template shadowrootmode="open"><button type="submit">Pay</button></template></ds-button>
+  </form>
+  <ds-field id="coupon" form="checkout">
+    <template shadowrootmode="open"><input aria-label="Coupon" name="coupon"></template>
+  </ds-field>
+  <ds-secret><template shadowrootmode="closed"><input aria-label="Hidden PIN"></template></ds-secret>
+</body></html>"#;
+
+#[test]
+fn extract_html_traverses_open_shadow_roots() {
+    let els = elements(&extract_html(WEB_COMPONENTS, "https://example.test/checkout"));
+
+    let email = find(&els, "input", "email");
+    assert_eq!(email.shadow_path, vec!["ds-field#email"]);
+    assert_eq!(email.associated_label_text.as_deref(), Some("Email"));
+
+    let card = find(&els, "input", "card");
+    assert_eq!(card.shadow_path, vec!["ds-card", "ds-field#number"]);
+
+    assert!(els.iter().all(|e| e.tag != "template"));
+    assert!(
+        !els.iter().any(|e| e.aria_label.as_deref() == Some("Hidden PIN")),
+        "closed shadow roots are not traversed"
+    );
+}
+
+#[test]
+fn shadow_controls_belong_to_their_hosts_form() {
+    let els = elements(&extract_html(WEB_COMPONENTS, "https://example.test/checkout"));
+    for name in ["email", "card", "coupon"] {
+        assert_eq!(find(&els, "input", name).form_id.as_deref(), Some("checkout"), "{}", name);
+    }
+    assert_eq!(find(&els, "input", "card").form_action.as_deref(), Some("/pay"));
+
+    let semantics = classify(&els);
+    let form = semantics.forms.iter().find(|f| f.id == "checkout").unwrap();
+    assert_eq!(form.inputs.len(), 3);
+    assert!(form.actions.iter().any(|a| a.label.as_deref() == Some("Pay")));
+}
+
+#[test]
+fn static_browser_targets_elements_inside_shadow_trees() {
+    let mut browser = StaticBrowser::new();
+    browser.load_html("https://example.test/checkout", WEB_COMPONENTS);
+
+    let card = SelectorHint {
+        role: Some("textbox".into()),
+        name: Some("Card number".into()),
+        tag: None,
+        input_type: None,
+        form_id: Some("checkout".into()),
+        frame_path: Vec::new(),
+        shadow_path: Vec::new(),
+    };
+    // Open shadow roots are pierced by default...
+    browser.fill(&card, "4242").unwrap();
+    // ...and a host path narrows the search
+    let scoped = SelectorHint { shadow_path: vec!["ds-card".into()], ..card.clone() };
+    browser.fill(&scoped, "4242").unwrap();
+    let wrong_host = SelectorHint { shadow_path: vec!["ds-field#email".into()], ..card };
+    assert!(browser.fill(&wrong_host, "4242").is_err());
+}